@@ -0,0 +1,33 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use zkp_ceaser::{circle_evaluate, circle_interpolate, circle_low_degree_extension, M31Element, TwiddleTree};
+
+fn sample_coefficients(log_size: u32) -> Vec<M31Element> {
+    (0..1u32 << log_size)
+        .map(|i| M31Element::new(i.wrapping_mul(2654435761) >> 1))
+        .collect()
+}
+
+fn bench_circle_fft(c: &mut Criterion) {
+    let mut group = c.benchmark_group("circle_fft");
+    for log_size in [10u32, 14, 16] {
+        // Los twiddles se cachean; se calculan antes para medir solo la FFT
+        TwiddleTree::for_log_size(log_size).unwrap();
+        TwiddleTree::for_log_size(log_size + 2).unwrap();
+        let coefficients = sample_coefficients(log_size);
+        let evaluations = circle_evaluate(&coefficients).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("evaluate", log_size), &coefficients, |b, coefficients| {
+            b.iter(|| circle_evaluate(black_box(coefficients)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("interpolate", log_size), &evaluations, |b, evaluations| {
+            b.iter(|| circle_interpolate(black_box(evaluations)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("lde_blowup_4", log_size), &evaluations, |b, evaluations| {
+            b.iter(|| circle_low_degree_extension(black_box(evaluations), 2).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_circle_fft);
+criterion_main!(benches);
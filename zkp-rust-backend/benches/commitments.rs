@@ -0,0 +1,42 @@
+use ark_bn254::Fr;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use zkp_ceaser::value_commitment::{commit_value, value_blinding_from_bytes};
+use zkp_ceaser::{
+    generate_asset_commitment_for_domain, generate_nullifier_for_domain, generate_pedersen_commitment_for_domain,
+    Domain,
+};
+
+const AMOUNT_WEI: &str = "1000000000000000000";
+const NONCE: &str = "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+const SECRET: &str = "0xfedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210";
+
+fn bench_commitments(c: &mut Criterion) {
+    let mut group = c.benchmark_group("commitment");
+    let domain = Domain::default();
+    let blinding: Fr = value_blinding_from_bytes(b"bench-blinding");
+
+    group.bench_function("pedersen_keccak", |b| {
+        b.iter(|| generate_pedersen_commitment_for_domain(black_box(AMOUNT_WEI), NONCE, &domain).unwrap())
+    });
+    group.bench_function("asset_commitment", |b| {
+        b.iter(|| generate_asset_commitment_for_domain("STRK", black_box(AMOUNT_WEI), NONCE, &domain).unwrap())
+    });
+    group.bench_function("bn254_value_commitment", |b| {
+        b.iter(|| commit_value(black_box(1_000_000_000_000_000_000u128), &blinding))
+    });
+    group.finish();
+}
+
+fn bench_nullifiers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("nullifier");
+    let domain = Domain::default();
+    let commitment = generate_pedersen_commitment_for_domain(AMOUNT_WEI, NONCE, &domain).unwrap();
+
+    group.bench_function("derive", |b| {
+        b.iter(|| generate_nullifier_for_domain(black_box(&commitment.commitment_hash), SECRET, &domain).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_commitments, bench_nullifiers);
+criterion_main!(benches);
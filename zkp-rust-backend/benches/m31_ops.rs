@@ -0,0 +1,64 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use zkp_ceaser::{batch_inverse, M31Element, M31Vector};
+
+fn sample_elements(size: usize, seed: u32) -> Vec<M31Element> {
+    (0..size as u32)
+        .map(|i| M31Element::new(i.wrapping_mul(2654435761).wrapping_add(seed) | 1))
+        .collect()
+}
+
+fn bench_scalar_ops(c: &mut Criterion) {
+    let mut group = c.benchmark_group("m31_scalar");
+    let a = M31Element::new(1234567891);
+    let b = M31Element::new(987654321);
+
+    group.bench_function("add", |bench| bench.iter(|| black_box(a).add(black_box(&b))));
+    group.bench_function("sub", |bench| bench.iter(|| black_box(a).sub(black_box(&b))));
+    group.bench_function("mul", |bench| bench.iter(|| black_box(a).mul(black_box(&b))));
+    group.bench_function("inv", |bench| bench.iter(|| black_box(a).inv().unwrap()));
+    group.finish();
+}
+
+fn bench_inversion(c: &mut Criterion) {
+    let mut group = c.benchmark_group("m31_inversion");
+    for log_size in [10u32, 14, 16] {
+        let elements = sample_elements(1 << log_size, 7);
+
+        group.bench_with_input(BenchmarkId::new("per_element", log_size), &elements, |b, elements| {
+            b.iter(|| elements.iter().map(|e| e.inv().unwrap()).collect::<Vec<_>>())
+        });
+        group.bench_with_input(BenchmarkId::new("batch", log_size), &elements, |b, elements| {
+            b.iter(|| batch_inverse(black_box(elements)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_vector_ops(c: &mut Criterion) {
+    let mut group = c.benchmark_group("m31_vector");
+    for log_size in [10u32, 16, 20] {
+        let left = M31Vector::from_elements(sample_elements(1 << log_size, 3));
+        let right = M31Vector::from_elements(sample_elements(1 << log_size, 11));
+        let scalar = M31Element::new(123456789);
+
+        group.bench_with_input(BenchmarkId::new("mul", log_size), &log_size, |b, _| {
+            b.iter(|| left.mul_vector(black_box(&right)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("sub", log_size), &log_size, |b, _| {
+            b.iter(|| left.sub_vector(black_box(&right)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("scale", log_size), &log_size, |b, _| {
+            b.iter(|| left.scale(black_box(&scalar)))
+        });
+        group.bench_with_input(BenchmarkId::new("sum", log_size), &log_size, |b, _| {
+            b.iter(|| black_box(&left).sum())
+        });
+        group.bench_with_input(BenchmarkId::new("product", log_size), &log_size, |b, _| {
+            b.iter(|| black_box(&left).product())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_scalar_ops, bench_inversion, bench_vector_ops);
+criterion_main!(benches);
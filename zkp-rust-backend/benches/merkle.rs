@@ -0,0 +1,62 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use sha3::{Digest, Keccak256};
+use zkp_ceaser::{verify_merkle_proof_with_index, IncrementalMerkleTree};
+
+const HEIGHTS: [u32; 3] = [10, 20, 32];
+/// Hojas ya escritas antes de medir: los caminos mezclan nodos reales y ceros
+const PREFILLED_LEAVES: u32 = 1024;
+
+fn leaf(index: u32) -> String {
+    format!("0x{}", hex::encode(Keccak256::digest(index.to_le_bytes())))
+}
+
+fn prefilled_tree(height: u32) -> IncrementalMerkleTree {
+    let mut tree = IncrementalMerkleTree::new(height).unwrap();
+    for index in 0..PREFILLED_LEAVES.min(1 << (height - 1)) {
+        tree.append(&leaf(index)).unwrap();
+    }
+    tree
+}
+
+fn bench_merkle_append(c: &mut Criterion) {
+    let mut group = c.benchmark_group("merkle_append");
+    let next_leaf = leaf(u32::MAX);
+    for height in HEIGHTS {
+        let tree = prefilled_tree(height);
+        group.bench_with_input(BenchmarkId::new("append", height), &tree, |b, tree| {
+            b.iter_batched(|| tree.clone(), |mut tree| tree.append(black_box(&next_leaf)).unwrap(), BatchSize::SmallInput)
+        });
+    }
+    group.finish();
+}
+
+fn bench_merkle_proof(c: &mut Criterion) {
+    let mut group = c.benchmark_group("merkle_proof");
+    for height in HEIGHTS {
+        let tree = prefilled_tree(height);
+        let leaf_index = (tree.len() / 2) as u32;
+        group.bench_with_input(BenchmarkId::new("generate", height), &tree, |b, tree| {
+            b.iter(|| tree.proof(black_box(leaf_index)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_merkle_verify(c: &mut Criterion) {
+    let mut group = c.benchmark_group("merkle_verify");
+    for height in HEIGHTS {
+        let tree = prefilled_tree(height);
+        let proof = tree.proof((tree.len() / 2) as u32).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("proof_with_index", height), &proof, |b, proof| {
+            b.iter(|| {
+                verify_merkle_proof_with_index(proof.proof_path.clone(), &proof.root, black_box(&proof.leaf_hash), proof.leaf_index)
+                    .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_merkle_append, bench_merkle_proof, bench_merkle_verify);
+criterion_main!(benches);
@@ -0,0 +1,68 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use zkp_ceaser::{build_deposit_proof, verify_deposit, ZKProofConfig};
+
+const AMOUNT_WEI: &str = "1000000000000000000";
+const NONCE: &str = "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+const USER_SECRET: &str = "0x00000000000000000000000000000000000000000000000000000000c0ffee";
+/// Commitment al que se liga la range proof aislada
+const COMMITMENT_HASH: &str = "0xd78890a1ce6d182ba25c324dc99e089670c35518c39c8c82dde0378f9eaaa5db";
+
+/// Nombre del backend activo para agrupar los resultados
+fn backend() -> &'static str {
+    if cfg!(feature = "real-stwo") { "real_stwo" } else { "mock_stwo" }
+}
+
+fn bench_deposit(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("deposit_{}", backend()));
+    group.sample_size(20);
+    let config = ZKProofConfig::default();
+    let proof = build_deposit_proof(AMOUNT_WEI, NONCE, USER_SECRET, &config).unwrap();
+
+    group.bench_function("prove", |b| {
+        b.iter(|| build_deposit_proof(black_box(AMOUNT_WEI), NONCE, USER_SECRET, &config).unwrap())
+    });
+    group.bench_function("verify", |b| {
+        b.iter(|| verify_deposit(black_box(&proof), &config).unwrap())
+    });
+    group.finish();
+}
+
+#[cfg(all(feature = "mock-stwo", not(feature = "real-stwo")))]
+fn bench_range_proof(c: &mut Criterion) {
+    use zkp_ceaser::{generate_stwo_range_proof, verify_stwo_range_proof};
+
+    let mut group = c.benchmark_group("range_proof_mock_stwo");
+    group.sample_size(20);
+    let config = ZKProofConfig::default();
+    let proof = generate_stwo_range_proof(AMOUNT_WEI, NONCE, &config.min_amount, &config.max_amount, COMMITMENT_HASH).unwrap();
+
+    group.bench_function("prove", |b| {
+        b.iter(|| {
+            generate_stwo_range_proof(black_box(AMOUNT_WEI), NONCE, &config.min_amount, &config.max_amount, COMMITMENT_HASH).unwrap()
+        })
+    });
+    group.bench_function("verify", |b| b.iter(|| verify_stwo_range_proof(black_box(&proof)).unwrap()));
+    group.finish();
+}
+
+#[cfg(feature = "real-stwo")]
+fn bench_range_proof(c: &mut Criterion) {
+    use zkp_ceaser::{generate_real_stwo_range_proof, verify_real_stwo_range_proof};
+
+    let mut group = c.benchmark_group("range_proof_real_stwo");
+    group.sample_size(10);
+    let config = ZKProofConfig::default();
+    let proof = generate_real_stwo_range_proof(AMOUNT_WEI, NONCE, &config.min_amount, &config.max_amount, COMMITMENT_HASH).unwrap();
+
+    group.bench_function("prove", |b| {
+        b.iter(|| {
+            generate_real_stwo_range_proof(black_box(AMOUNT_WEI), NONCE, &config.min_amount, &config.max_amount, COMMITMENT_HASH)
+                .unwrap()
+        })
+    });
+    group.bench_function("verify", |b| b.iter(|| verify_real_stwo_range_proof(black_box(&proof)).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, bench_deposit, bench_range_proof);
+criterion_main!(benches);
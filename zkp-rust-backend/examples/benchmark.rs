@@ -0,0 +1,112 @@
+//! Benchmark rápido de las operaciones principales con salida JSON en stdout,
+//! pensado para guardar y comparar entre versiones (las benches de criterion
+//! dan estadística más fina en `target/criterion/*/new/estimates.json`).
+//!
+//! Uso: `cargo run --release --example benchmark [-- iteraciones]`
+use serde::Serialize;
+use std::hint::black_box;
+use std::time::Instant;
+use zkp_ceaser::value_commitment::{commit_value, value_blinding_from_bytes};
+use zkp_ceaser::{
+    build_deposit_proof, circle_evaluate, circle_interpolate, generate_nullifier_for_domain,
+    generate_pedersen_commitment_for_domain, verify_deposit, verify_merkle_proof_with_index, Domain, M31Element,
+    ZKProofConfig,
+};
+
+const AMOUNT_WEI: &str = "1000000000000000000";
+const NONCE: &str = "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+const USER_SECRET: &str = "0x00000000000000000000000000000000000000000000000000000000c0ffee";
+const SECRET: &str = "0xfedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210";
+
+#[derive(Serialize)]
+struct BenchmarkResult {
+    name: String,
+    iterations: usize,
+    mean_ns: f64,
+    min_ns: u128,
+    max_ns: u128,
+}
+
+#[derive(Serialize)]
+struct BenchmarkReport {
+    crate_version: &'static str,
+    backend: &'static str,
+    results: Vec<BenchmarkResult>,
+}
+
+fn measure<T>(name: &str, iterations: usize, mut f: impl FnMut() -> T) -> BenchmarkResult {
+    black_box(f()); // calentamiento (twiddles, generadores, etc.)
+    let samples: Vec<u128> = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed().as_nanos()
+        })
+        .collect();
+    BenchmarkResult {
+        name: name.to_string(),
+        iterations,
+        mean_ns: samples.iter().sum::<u128>() as f64 / iterations as f64,
+        min_ns: samples.iter().copied().min().unwrap_or(0),
+        max_ns: samples.iter().copied().max().unwrap_or(0),
+    }
+}
+
+fn main() {
+    let iterations = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse::<usize>().ok())
+        .unwrap_or(20)
+        .max(1);
+
+    let config = ZKProofConfig::default();
+    let domain = Domain::default();
+    let mut results = Vec::new();
+
+    let a = M31Element::new(123_456_789);
+    let b = M31Element::new(987_654_321);
+    results.push(measure("m31/mul_1k", iterations, || (0..1000).fold(a, |acc, _| acc.mul(&b))));
+    results.push(measure("m31/inv", iterations, || a.inv().unwrap()));
+
+    for log_size in [10u32, 14, 16] {
+        let coefficients: Vec<M31Element> = (0..1u32 << log_size).map(M31Element::new).collect();
+        let evaluations = circle_evaluate(&coefficients).expect("power-of-two size");
+        results.push(measure(&format!("circle_fft/evaluate/{}", log_size), iterations, || {
+            circle_evaluate(&coefficients).unwrap()
+        }));
+        results.push(measure(&format!("circle_fft/interpolate/{}", log_size), iterations, || {
+            circle_interpolate(&evaluations).unwrap()
+        }));
+    }
+
+    let leaf = format!("0x{:064x}", 1);
+    let root = format!("0x{:064x}", 2);
+    for height in [10u32, 20, 32] {
+        let path: Vec<String> = (0..height).map(|level| format!("0x{:064x}", level + 3)).collect();
+        results.push(measure(&format!("merkle/verify/{}", height), iterations, || {
+            verify_merkle_proof_with_index(path.clone(), &root, &leaf, 0).unwrap()
+        }));
+    }
+
+    let blinding = value_blinding_from_bytes(b"bench-blinding");
+    results.push(measure("commitment/pedersen_keccak", iterations, || {
+        generate_pedersen_commitment_for_domain(AMOUNT_WEI, NONCE, &domain).unwrap()
+    }));
+    results.push(measure("commitment/bn254_value", iterations, || commit_value(1_000_000_000_000_000_000, &blinding)));
+
+    let commitment = generate_pedersen_commitment_for_domain(AMOUNT_WEI, NONCE, &domain).unwrap();
+    results.push(measure("nullifier/derive", iterations, || {
+        generate_nullifier_for_domain(&commitment.commitment_hash, SECRET, &domain).unwrap()
+    }));
+
+    let proof = build_deposit_proof(AMOUNT_WEI, NONCE, USER_SECRET, &config).expect("valid deposit");
+    results.push(measure("deposit/prove", iterations, || build_deposit_proof(AMOUNT_WEI, NONCE, USER_SECRET, &config).unwrap()));
+    results.push(measure("deposit/verify", iterations, || verify_deposit(&proof, &config).unwrap()));
+
+    let report = BenchmarkReport {
+        crate_version: env!("CARGO_PKG_VERSION"),
+        backend: if cfg!(feature = "real-stwo") { "real-stwo" } else { "mock-stwo" },
+        results,
+    };
+    println!("{}", serde_json::to_string_pretty(&report).expect("report serializes to JSON"));
+}
@@ -0,0 +1,42 @@
+//! Pico de memoria por operación usando `TrackingAllocator`. Imprime en JSON el
+//! historial de ejecuciones y el resumen agregado del registro de estadísticas.
+//!
+//! Uso: `cargo run --release --example memory_profile [-- ejecuciones]`
+use zkp_ceaser::{
+    build_deposit_proof, circle_low_degree_extension, performance_history, performance_stats, time_phase,
+    M31Element, ProofPhase, ProofRecorder, TrackingAllocator, ZKProofConfig,
+};
+
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+fn main() {
+    let runs = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse::<usize>().ok())
+        .unwrap_or(3);
+
+    let config = ZKProofConfig::default();
+    for run in 0..runs {
+        let recorder = ProofRecorder::start("deposit_proof");
+        let nonce = format!("0x{:064x}", run + 1);
+        let proof = build_deposit_proof("1000000000000000000", &nonce, &nonce, &config).expect("valid deposit");
+        let json = time_phase(ProofPhase::Serialization, || serde_json::to_string(&proof))
+            .expect("deposit proof serializes to JSON");
+        recorder.finish(json.len());
+    }
+
+    for log_size in [10u32, 14, 16] {
+        let evaluations: Vec<M31Element> = (0..1u32 << log_size).map(M31Element::new).collect();
+        let recorder = ProofRecorder::start(&format!("circle_lde_{}", log_size));
+        let extended = time_phase(ProofPhase::TraceGeneration, || circle_low_degree_extension(&evaluations, 2))
+            .expect("power-of-two size");
+        recorder.finish(extended.len() * std::mem::size_of::<M31Element>());
+    }
+
+    let report = serde_json::json!({
+        "history": performance_history(),
+        "stats": performance_stats(),
+    });
+    println!("{}", serde_json::to_string_pretty(&report).expect("stats serialize to JSON"));
+}
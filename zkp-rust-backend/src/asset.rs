@@ -0,0 +1,163 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use ark_bn254::{Fr, G1Projective};
+use ark_ff::UniformRand;
+use rand::{CryptoRng, RngCore};
+use sha3::{Digest, Keccak256};
+use crate::denomination::{bind_denomination, commitment_from_hash};
+use crate::value_commitment::{blinding_generator, hash_to_g1, point_from_hex, point_to_hex, OrProof};
+use crate::{
+    generate_pedersen_commitment_for_domain, normalize_felt_hex, Domain, PedersenCommitment,
+    TokenRegistry, Transcript,
+};
+
+/// Identificador de activo: dirección del token normalizada ("STRK" o "0x04718f…")
+pub fn resolve_asset_id(asset: &str) -> Result<String, String> {
+    let asset = asset.trim();
+    if asset.starts_with("0x") || asset.starts_with("0X") {
+        return Ok(normalize_felt_hex(asset));
+    }
+    Ok(normalize_felt_hex(&TokenRegistry::default().get(asset)?.address))
+}
+
+/// Activos permitidos por defecto: todos los tokens del registro
+pub fn default_allowed_assets() -> Vec<String> {
+    TokenRegistry::default()
+        .tokens()
+        .iter()
+        .map(|token| normalize_felt_hex(&token.address))
+        .collect()
+}
+
+/// Una lista vacía no admite ningún activo (la prueba de pertenencia necesita
+/// al menos uno); los configs la rechazan al parsearse
+pub fn check_allowed_assets(allowed_assets: &[String]) -> Result<(), String> {
+    if allowed_assets.is_empty() {
+        return Err("allowed_assets must not be empty; omit it to accept every registered token".to_string());
+    }
+    Ok(())
+}
+
+/// Comprueba que el activo está en la lista permitida
+pub fn check_asset_allowed(asset_id: &str, allowed_assets: &[String]) -> Result<(), String> {
+    let asset_id = normalize_felt_hex(asset_id);
+    if !allowed_assets.iter().any(|allowed| normalize_felt_hex(allowed) == asset_id) {
+        return Err(format!("Asset {} is not allowed in this pool", asset_id));
+    }
+    Ok(())
+}
+
+/// Generador por activo A = hash_to_g1(asset_id); nadie conoce log_G(A) ni log_H(A)
+pub fn asset_generator(asset_id: &str) -> G1Projective {
+    let mut label = b"ceaser-asset-generator-".to_vec();
+    label.extend_from_slice(normalize_felt_hex(asset_id).as_bytes());
+    hash_to_g1(&label).into()
+}
+
+/// Hoja de cualquier nota del árbol (depósito, salida de join-split o la que
+/// gasta `CeaserZKProof` desde v1): Keccak(separador ‖ N) con N = V + S el
+/// punto de la nota, V = v·A + r·H su commitment de valor y S = s·K_d su clave
+/// de gasto. En modo denominación la hoja queda ligada además al ID
+pub fn note_leaf(
+    note_commitment: &G1Projective,
+    denomination_id: Option<u32>,
+    domain: &Domain,
+) -> Result<PedersenCommitment, String> {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"ceaser-note-leaf-v1");
+    hasher.update(domain.separator());
+    hasher.update(point_to_hex(note_commitment).as_bytes());
+    let leaf = commitment_from_hash(&hasher.finalize());
+    match denomination_id {
+        Some(id) => bind_denomination(&leaf, id),
+        None => Ok(leaf),
+    }
+}
+
+/// Commitment de nota ligado a (asset_id, amount, nonce): gastar la nota con
+/// otro activo produce otra hoja y la merkle proof falla
+#[wasm_bindgen]
+pub fn generate_asset_commitment_for_domain(
+    asset_id: &str,
+    amount_wei: &str,
+    nonce: &str,
+    domain: &Domain,
+) -> Result<PedersenCommitment, JsValue> {
+    let base = generate_pedersen_commitment_for_domain(amount_wei, nonce, domain)?;
+    let base_hash = hex::decode(base.commitment_hash.trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid commitment hash: {}", e))?;
+    let asset_id = resolve_asset_id(asset_id)?;
+
+    let mut hasher = Keccak256::new();
+    hasher.update(b"ceaser-asset-commitment-v1");
+    hasher.update((asset_id.len() as u32).to_le_bytes());
+    hasher.update(asset_id.as_bytes());
+    hasher.update(&base_hash);
+    Ok(commitment_from_hash(&hasher.finalize()))
+}
+
+/// Generador de activo cegado A' = A + r·H más la prueba de que A' corresponde
+/// a alguno de los activos permitidos (sin revelar cuál). La prueba no habla de
+/// las notas: las liga el balance del join-split, porque el V de cada hoja usa
+/// el generador de su propio activo y solo cuadra en A' si es el mismo
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlindedAsset {
+    pub generator: String,
+    pub surjection_proof: AssetSurjectionProof,
+}
+
+/// Prueba de pertenencia del activo: OR de Schnorr sobre A' − A_k = r·H,
+/// un par (reto, respuesta) por activo permitido
+pub type AssetSurjectionProof = OrProof;
+
+impl BlindedAsset {
+    /// Ciega el generador de `asset_id` y prueba su pertenencia a `allowed_assets`.
+    /// Devuelve también el factor de cegado r (necesario para los commitments)
+    pub fn prove<R: RngCore + CryptoRng>(
+        asset_id: &str,
+        allowed_assets: &[String],
+        transcript: &mut Transcript,
+        rng: &mut R,
+    ) -> Result<(BlindedAsset, Fr), String> {
+        let asset_id = normalize_felt_hex(asset_id);
+        let secret_index = allowed_assets
+            .iter()
+            .position(|allowed| normalize_felt_hex(allowed) == asset_id)
+            .ok_or_else(|| format!("Asset {} is not allowed in this pool", asset_id))?;
+
+        let h = G1Projective::from(blinding_generator());
+        let asset_blinding = Fr::rand(rng);
+        let blinded_generator = asset_generator(&asset_id) + h * asset_blinding;
+
+        transcript.append_str("asset_generator", &point_to_hex(&blinded_generator));
+        let surjection_proof = OrProof::prove(
+            &surjection_statements(&blinded_generator, allowed_assets), secret_index, &asset_blinding, transcript, rng,
+        )?;
+
+        let blinded = BlindedAsset { generator: point_to_hex(&blinded_generator), surjection_proof };
+        Ok((blinded, asset_blinding))
+    }
+
+    /// Verifica que el generador cegado es de un activo permitido (con una lista
+    /// vacía ninguno lo es, igual que en `check_asset_allowed`)
+    pub fn verify(&self, allowed_assets: &[String], transcript: &mut Transcript) -> Result<bool, String> {
+        if allowed_assets.is_empty() {
+            return Ok(false);
+        }
+        let blinded_generator = self.blinded_generator()?;
+        transcript.append_str("asset_generator", &point_to_hex(&blinded_generator));
+        self.surjection_proof.verify(&surjection_statements(&blinded_generator, allowed_assets), transcript)
+    }
+
+    pub fn blinded_generator(&self) -> Result<G1Projective, String> {
+        point_from_hex(&self.generator)
+    }
+}
+
+/// A' − A_k para cada activo permitido
+fn surjection_statements(blinded_generator: &G1Projective, allowed_assets: &[String]) -> Vec<G1Projective> {
+    allowed_assets
+        .iter()
+        .map(|allowed| *blinded_generator - asset_generator(allowed))
+        .collect()
+}
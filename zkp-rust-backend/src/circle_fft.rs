@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use wasm_bindgen::prelude::*;
+use crate::parallel::{join, map_range};
+use crate::{batch_inverse, CircleDomain, CirclePoint, Coset, M31Element, M31Vector, PARALLEL_THRESHOLD};
+
+/// FFT circular sobre dominios canónicos de M31.
+///
+/// Base de coeficientes (la misma familia que usa STWO): el coeficiente `j`
+/// multiplica a `y^{j_0} · x^{j_1} · π(x)^{j_2} · π²(x)^{j_3} · …`, con
+/// `π(x) = 2x² − 1` (coordenada x del punto duplicado) y `j_k` el bit k de `j`.
+/// Así los primeros 2^n coeficientes no dependen del tamaño del dominio y la
+/// extensión de bajo grado es rellenar con ceros.
+#[derive(Debug, Clone)]
+pub struct TwiddleTree {
+    pub log_size: u32,
+    /// y de la primera mitad del dominio (capa circular) y sus inversos
+    pub circle_twiddles: Vec<M31Element>,
+    pub circle_inv_twiddles: Vec<M31Element>,
+    /// x de la primera mitad de cada capa de línea y sus inversos
+    pub line_twiddles: Vec<Vec<M31Element>>,
+    pub line_inv_twiddles: Vec<Vec<M31Element>>,
+}
+
+static TWIDDLE_CACHE: OnceLock<Mutex<HashMap<u32, Arc<TwiddleTree>>>> = OnceLock::new();
+
+fn invert_all(values: &[M31Element]) -> Vec<M31Element> {
+    batch_inverse(values).expect("twiddles of a canonic domain are non-zero")
+}
+
+impl TwiddleTree {
+    fn compute(log_size: u32) -> Result<TwiddleTree, String> {
+        let domain = CircleDomain::canonic(log_size)?;
+        let half_size = domain.half_coset.size();
+
+        let circle_twiddles: Vec<M31Element> = domain.half_coset.iter().map(|p| p.y()).collect();
+
+        // Capa k: x de Coset::half_odds(log_size − 1 − k), que es el coset anterior duplicado
+        let mut line_twiddles = Vec::new();
+        for layer_log_size in (1..log_size).rev() {
+            let coset = Coset::half_odds(layer_log_size)?;
+            let layer_half = coset.size() / 2;
+            line_twiddles.push(coset.iter().take(layer_half).map(|p| p.x()).collect::<Vec<_>>());
+        }
+        debug_assert_eq!(circle_twiddles.len(), half_size);
+
+        Ok(TwiddleTree {
+            log_size,
+            circle_inv_twiddles: invert_all(&circle_twiddles),
+            line_inv_twiddles: line_twiddles.iter().map(|layer| invert_all(layer)).collect(),
+            circle_twiddles,
+            line_twiddles,
+        })
+    }
+
+    /// Twiddles del dominio canónico de tamaño 2^log_size (cacheados)
+    pub fn for_log_size(log_size: u32) -> Result<Arc<TwiddleTree>, String> {
+        let cache = TWIDDLE_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+        if let Some(tree) = cache.lock().map_err(|_| "Twiddle cache poisoned")?.get(&log_size) {
+            return Ok(tree.clone());
+        }
+
+        let tree = Arc::new(TwiddleTree::compute(log_size)?);
+        cache
+            .lock()
+            .map_err(|_| "Twiddle cache poisoned")?
+            .insert(log_size, tree.clone());
+        Ok(tree)
+    }
+}
+
+fn split_even_odd(values: &[M31Element]) -> (Vec<M31Element>, Vec<M31Element>) {
+    let even = values.iter().step_by(2).copied().collect();
+    let odd = values.iter().skip(1).step_by(2).copied().collect();
+    (even, odd)
+}
+
+fn interleave(even: &[M31Element], odd: &[M31Element]) -> Vec<M31Element> {
+    even.iter().zip(odd).flat_map(|(e, o)| [*e, *o]).collect()
+}
+
+/// Butterfly directo: (a + t·b, a − t·b)
+fn butterfly(
+    low: &[M31Element],
+    high: &[M31Element],
+    twiddles: &[M31Element],
+) -> Vec<M31Element> {
+    let pairs = map_range(low.len(), PARALLEL_THRESHOLD, |i| {
+        let product = twiddles[i].mul(&high[i]);
+        (low[i].add(&product), low[i].sub(&product))
+    });
+    let (mut out, upper): (Vec<M31Element>, Vec<M31Element>) = pairs.into_iter().unzip();
+    out.extend(upper);
+    out
+}
+
+/// Butterfly inverso (sin dividir por 2): (a + b, (a − b)·t⁻¹)
+fn inverse_butterfly(
+    values: &[M31Element],
+    inv_twiddles: &[M31Element],
+) -> (Vec<M31Element>, Vec<M31Element>) {
+    let half = values.len() / 2;
+    let (low, high) = values.split_at(half);
+    map_range(half, PARALLEL_THRESHOLD, |i| {
+        (low[i].add(&high[i]), low[i].sub(&high[i]).mul(&inv_twiddles[i]))
+    })
+    .into_iter()
+    .unzip()
+}
+
+fn line_evaluate(coefficients: &[M31Element], twiddles: &TwiddleTree, layer: usize) -> Vec<M31Element> {
+    if coefficients.len() == 1 {
+        return coefficients.to_vec();
+    }
+    let (even, odd) = split_even_odd(coefficients);
+    let (low, high) = join(
+        coefficients.len() >= PARALLEL_THRESHOLD,
+        || line_evaluate(&even, twiddles, layer + 1),
+        || line_evaluate(&odd, twiddles, layer + 1),
+    );
+    butterfly(&low, &high, &twiddles.line_twiddles[layer])
+}
+
+fn line_interpolate(values: &[M31Element], twiddles: &TwiddleTree, layer: usize) -> Vec<M31Element> {
+    if values.len() == 1 {
+        return values.to_vec();
+    }
+    let (sum, diff) = inverse_butterfly(values, &twiddles.line_inv_twiddles[layer]);
+    let (even, odd) = join(
+        values.len() >= PARALLEL_THRESHOLD,
+        || line_interpolate(&sum, twiddles, layer + 1),
+        || line_interpolate(&diff, twiddles, layer + 1),
+    );
+    interleave(&even, &odd)
+}
+
+fn check_fft_size(len: usize) -> Result<u32, String> {
+    if len < 2 || !len.is_power_of_two() {
+        return Err(format!("Circle FFT size must be a power of two >= 2, got {}", len));
+    }
+    Ok(len.trailing_zeros())
+}
+
+/// Evalúa los coeficientes sobre el dominio canónico del mismo tamaño.
+/// El orden de salida es el de `CircleDomain::iter` (medio coset y luego su conjugado)
+pub fn circle_evaluate(coefficients: &[M31Element]) -> Result<Vec<M31Element>, String> {
+    let twiddles = TwiddleTree::for_log_size(check_fft_size(coefficients.len())?)?;
+
+    // f(x, y) = f0(x) + y·f1(x); en el conjugado (x, −y) cambia el signo
+    let (even, odd) = split_even_odd(coefficients);
+    let (low, high) = join(
+        coefficients.len() >= PARALLEL_THRESHOLD,
+        || line_evaluate(&even, &twiddles, 0),
+        || line_evaluate(&odd, &twiddles, 0),
+    );
+    Ok(butterfly(&low, &high, &twiddles.circle_twiddles))
+}
+
+/// Interpola evaluaciones sobre el dominio canónico (inversa de `circle_evaluate`)
+pub fn circle_interpolate(evaluations: &[M31Element]) -> Result<Vec<M31Element>, String> {
+    let log_size = check_fft_size(evaluations.len())?;
+    let twiddles = TwiddleTree::for_log_size(log_size)?;
+
+    let (sum, diff) = inverse_butterfly(evaluations, &twiddles.circle_inv_twiddles);
+    let (even, odd) = join(
+        evaluations.len() >= PARALLEL_THRESHOLD,
+        || line_interpolate(&sum, &twiddles, 0),
+        || line_interpolate(&diff, &twiddles, 0),
+    );
+    let coefficients = interleave(&even, &odd);
+
+    // Cada capa acumula un factor 2 → dividir por 2^log_size al final
+    let scale = M31Element::new(1u32 << log_size)
+        .inv()
+        .map_err(|_| "Domain size is not invertible".to_string())?;
+    Ok(map_range(coefficients.len(), PARALLEL_THRESHOLD, |i| coefficients[i].mul(&scale)))
+}
+
+/// Evaluación directa en un punto cualquiera del círculo (referencia O(n log n))
+pub fn evaluate_circle_poly_at(coefficients: &[M31Element], point: &CirclePoint) -> M31Element {
+    let mut result = M31Element::new(0);
+    for (j, coefficient) in coefficients.iter().enumerate() {
+        let mut term = *coefficient;
+        if j & 1 == 1 {
+            term = term.mul(&point.y());
+        }
+        let mut x = point.x();
+        let mut bits = j >> 1;
+        while bits > 0 {
+            if bits & 1 == 1 {
+                term = term.mul(&x);
+            }
+            x = x.mul(&x).add(&x.mul(&x)).sub(&M31Element::new(1)); // π(x) = 2x² − 1
+            bits >>= 1;
+        }
+        result = result.add(&term);
+    }
+    result
+}
+
+/// Extensión de bajo grado: interpola y re-evalúa en un dominio 2^log_blowup veces mayor
+pub fn circle_low_degree_extension(
+    evaluations: &[M31Element],
+    log_blowup: u32,
+) -> Result<Vec<M31Element>, String> {
+    let mut coefficients = circle_interpolate(evaluations)?;
+    let extended_size = evaluations
+        .len()
+        .checked_shl(log_blowup)
+        .ok_or_else(|| format!("Blowup 2^{} too large", log_blowup))?;
+    coefficients.resize(extended_size, M31Element::new(0));
+    circle_evaluate(&coefficients)
+}
+
+/// FFT circular: coeficientes → evaluaciones en el dominio canónico
+#[wasm_bindgen]
+pub fn circle_fft(coefficients: &M31Vector) -> Result<M31Vector, JsValue> {
+    Ok(M31Vector::from_elements(circle_evaluate(coefficients.elements())?))
+}
+
+/// FFT circular inversa: evaluaciones en el dominio canónico → coeficientes
+#[wasm_bindgen]
+pub fn circle_ifft(evaluations: &M31Vector) -> Result<M31Vector, JsValue> {
+    Ok(M31Vector::from_elements(circle_interpolate(evaluations.elements())?))
+}
+
+/// LDE con blowup 2^log_blowup
+#[wasm_bindgen]
+pub fn circle_lde(evaluations: &M31Vector, log_blowup: u32) -> Result<M31Vector, JsValue> {
+    Ok(M31Vector::from_elements(circle_low_degree_extension(evaluations.elements(), log_blowup)?))
+}
+
+/// Evalúa un polinomio circular en un punto
+#[wasm_bindgen]
+pub fn evaluate_circle_polynomial_m31(coefficients: &M31Vector, point: &CirclePoint) -> M31Element {
+    evaluate_circle_poly_at(coefficients.elements(), point)
+}
+
+/// Autotest de la FFT: ida y vuelta, acuerdo con la evaluación directa y LDE
+#[wasm_bindgen]
+pub fn test_circle_fft(log_size: u32, log_blowup: u32) -> Result<bool, JsValue> {
+    Ok(check_circle_fft(log_size, log_blowup)?)
+}
+
+/// Núcleo de `test_circle_fft`; los tamaños se validan antes de reservar memoria
+pub fn check_circle_fft(log_size: u32, log_blowup: u32) -> Result<bool, String> {
+    let domain = CircleDomain::canonic(log_size)?;
+    let extended_log_size = log_size
+        .checked_add(log_blowup)
+        .ok_or_else(|| format!("Blowup 2^{} too large", log_blowup))?;
+    let extended_domain = CircleDomain::canonic(extended_log_size)?;
+
+    // Coeficientes deterministas (sin RNG)
+    let coefficients: Vec<M31Element> = (0..domain.size() as u32)
+        .map(|i| M31Element::new(i.wrapping_mul(2654435761).wrapping_add(12345)))
+        .collect();
+
+    let evaluations = circle_evaluate(&coefficients)?;
+    let round_trip = circle_interpolate(&evaluations)? == coefficients;
+
+    let matches_naive = domain
+        .iter()
+        .zip(&evaluations)
+        .all(|(point, value)| evaluate_circle_poly_at(&coefficients, &point) == *value);
+
+    let extended = circle_low_degree_extension(&evaluations, log_blowup)?;
+    let lde_matches = extended_domain
+        .iter()
+        .zip(&extended)
+        .all(|(point, value)| evaluate_circle_poly_at(&coefficients, &point) == *value);
+
+    crate::console_log!("🔁 Circle FFT 2^{}: round_trip={}, naive={}, lde={}",
+                        log_size, round_trip, matches_naive, lde_matches);
+
+    Ok(round_trip && matches_naive && lde_matches)
+}
@@ -0,0 +1,131 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use crate::{
+    check_asset_allowed, generate_asset_commitment_for_domain, generate_pedersen_commitment_for_domain,
+    parse_amount, resolve_asset_id, Domain, PedersenCommitment, ZKProofConfig,
+};
+
+/// Modo de montos del pool: rango libre (range proof) o denominaciones fijas.
+/// Con denominaciones todos los depósitos de un mismo ID son indistinguibles
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum AmountMode {
+    #[default]
+    Range,
+    Denomination {
+        denominations: Vec<String>, // En wei, el índice es el ID de denominación
+    },
+}
+
+/// Denominaciones por defecto: 1 / 10 / 100 / 1000 STRK
+pub fn default_strk_denominations() -> Vec<String> {
+    [1u128, 10, 100, 1000]
+        .iter()
+        .map(|strk| (strk * 1_000_000_000_000_000_000).to_string())
+        .collect()
+}
+
+impl AmountMode {
+    pub fn denominations_strk() -> AmountMode {
+        AmountMode::Denomination { denominations: default_strk_denominations() }
+    }
+
+    /// ID de denominación del monto; `None` en modo rango
+    pub fn denomination_id(&self, amount_wei: &str) -> Result<Option<u32>, String> {
+        let denominations = match self {
+            AmountMode::Range => return Ok(None),
+            AmountMode::Denomination { denominations } => denominations,
+        };
+        let amount = parse_amount(amount_wei, "amount")?;
+
+        for (id, denomination) in denominations.iter().enumerate() {
+            let value = parse_amount(denomination, "denomination")?;
+            if value == amount {
+                return Ok(Some(id as u32));
+            }
+        }
+        Err(format!("Amount {} is not an allowed denomination {:?}", amount, denominations))
+    }
+
+    /// Monto en wei de un ID de denominación
+    pub fn denomination_amount(&self, denomination_id: u32) -> Option<&str> {
+        match self {
+            AmountMode::Range => None,
+            AmountMode::Denomination { denominations } => {
+                denominations.get(denomination_id as usize).map(|d| d.trim())
+            }
+        }
+    }
+}
+
+/// Commitment ligado a un ID de denominación (no abre a otra denominación)
+#[wasm_bindgen]
+pub fn generate_pedersen_commitment_for_denomination(
+    amount_wei: &str,
+    nonce: &str,
+    denomination_id: u32,
+    domain: &Domain,
+) -> Result<PedersenCommitment, JsValue> {
+    let base = generate_pedersen_commitment_for_domain(amount_wei, nonce, domain)?;
+    Ok(bind_denomination(&base, denomination_id)?)
+}
+
+/// Liga un commitment existente (de dominio o de activo) a un ID de denominación
+pub(crate) fn bind_denomination(base: &PedersenCommitment, denomination_id: u32) -> Result<PedersenCommitment, String> {
+    let base_hash = hex::decode(base.commitment_hash.trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid commitment hash: {}", e))?;
+
+    let mut hasher = Keccak256::new();
+    hasher.update(b"ceaser-denomination-v1");
+    hasher.update(denomination_id.to_le_bytes());
+    hasher.update(&base_hash);
+    Ok(commitment_from_hash(&hasher.finalize()))
+}
+
+/// Coordenadas mock derivadas del hash del commitment (mismo esquema que el de dominio)
+pub(crate) fn commitment_from_hash(commitment_hash: &[u8]) -> PedersenCommitment {
+    let mut x_hasher = Keccak256::new();
+    x_hasher.update(commitment_hash);
+    x_hasher.update(b"x_coord");
+
+    let mut y_hasher = Keccak256::new();
+    y_hasher.update(commitment_hash);
+    y_hasher.update(b"y_coord");
+
+    PedersenCommitment {
+        x: format!("0x{}", hex::encode(x_hasher.finalize())),
+        y: format!("0x{}", hex::encode(y_hasher.finalize())),
+        commitment_hash: format!("0x{}", hex::encode(commitment_hash)),
+    }
+}
+
+/// Commitment según el modo del config; rechaza montos fuera de las denominaciones
+pub(crate) fn commit_amount_for_config(
+    amount_wei: &str,
+    nonce: &str,
+    config: &ZKProofConfig,
+) -> Result<(PedersenCommitment, Option<u32>), JsValue> {
+    let base = match &config.asset {
+        Some(asset) => {
+            let asset_id = resolve_asset_id(asset)?;
+            check_asset_allowed(&asset_id, &config.allowed_assets)?;
+            generate_asset_commitment_for_domain(&asset_id, amount_wei, nonce, &config.domain)?
+        }
+        None => generate_pedersen_commitment_for_domain(amount_wei, nonce, &config.domain)?,
+    };
+
+    match config.amount_mode.denomination_id(amount_wei)? {
+        Some(id) => {
+            crate::console_log!("🪙 Modo denominación: ID {} ({} wei)", id, amount_wei);
+            Ok((bind_denomination(&base, id)?, Some(id)))
+        }
+        None => Ok((base, None)),
+    }
+}
+
+/// Denominaciones permitidas por defecto (JSON, en wei)
+#[wasm_bindgen]
+pub fn get_default_denominations() -> String {
+    serde_json::to_string(&default_strk_denominations()).unwrap_or_default()
+}
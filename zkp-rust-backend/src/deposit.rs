@@ -0,0 +1,234 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use ark_bn254::{Fr, G1Projective};
+use crate::value_commitment::{commit_value_with_generator, point_from_hex, point_to_hex, value_blinding_from_bytes, value_generator};
+use crate::{
+    asset_generator, commit_amount_for_config, generate_backend_range_proof, note_leaf, parse_amount, parse_proof_config,
+    range_proof_bounds_match, range_proof_commitment, resolve_asset_id, spend_key_for_domain, verify_backend_range_proof,
+    verify_pedersen_commitment, CircleStarkRangeProof, Domain, time_phase, PedersenCommitment, ProofPhase,
+    ProofRecorder, Transcript, VerificationFailure, VerificationReport, ZKProofConfig, U256,
+};
+
+/// Orden de las entradas públicas del depósito (calldata de `deposit_private`)
+pub const DEPOSIT_PUBLIC_INPUTS_LAYOUT: [&str; 4] = [
+    "commitment_hash",
+    "min_amount",
+    "max_amount",
+    "domain_separator",
+];
+
+/// Prueba de depósito: el commitment está bien formado y el monto oculto está en rango.
+/// `note_commitment` es el punto de la nota N = v·A + r·H + S y `amount_commitment`
+/// la hoja que se inserta en el árbol, `note_leaf(N)`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepositProof {
+    pub note_commitment: String,
+    pub amount_commitment: PedersenCommitment,
+    pub range_proof: CircleStarkRangeProof,
+    pub domain: Domain,
+    pub public_inputs: Vec<String>,
+    pub statement_hash: String,
+    #[serde(default)]
+    pub denomination_id: Option<u32>,
+}
+
+/// Entradas públicas del depósito según `DEPOSIT_PUBLIC_INPUTS_LAYOUT`
+pub fn deposit_public_inputs(
+    commitment_hash: &str,
+    min_amount: U256,
+    max_amount: U256,
+    domain: &Domain,
+) -> Vec<String> {
+    vec![
+        commitment_hash.to_string(),
+        format!("0x{:032x}", min_amount),
+        format!("0x{:032x}", max_amount),
+        domain.separator_hex(),
+    ]
+}
+
+/// Generador de valor del pool: el del activo si el config lo fija, si no G
+pub(crate) fn pool_value_generator(config: &ZKProofConfig) -> Result<G1Projective, String> {
+    match &config.asset {
+        Some(asset) => Ok(asset_generator(&resolve_asset_id(asset)?)),
+        None => Ok(value_generator().into()),
+    }
+}
+
+/// Punto de la nota depositada N = v·A + r·H + S, con r derivado del nonce y
+/// S = s·K_d la clave de gasto del secreto del usuario. Devuelve también s, que
+/// el retiro necesita para firmar
+pub fn deposit_note_commitment(
+    amount_wei: &str,
+    nonce: &str,
+    user_secret: &str,
+    config: &ZKProofConfig,
+) -> Result<(G1Projective, Fr), JsValue> {
+    let amount = parse_amount(amount_wei, "amount")?
+        .as_u128()
+        .ok_or("Deposit amount must fit in 128 bits")?;
+    let nonce_bytes = hex::decode(nonce.trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid nonce hex: {}", e))?;
+
+    let (commitment, _) = commit_amount_for_config(amount_wei, nonce, config)?;
+    let (spend_secret, spend_key) = spend_key_for_domain(&commitment.commitment_hash, user_secret, &config.domain)?;
+    let value = commit_value_with_generator(amount, &pool_value_generator(config)?, &value_blinding_from_bytes(&nonce_bytes));
+    Ok((value + spend_key, spend_secret))
+}
+
+fn deposit_statement_hash(proof: &DepositProof) -> String {
+    let mut transcript = Transcript::new("ceaser-deposit-statement-v1");
+    proof.domain.append_to(&mut transcript);
+    for (label, input) in DEPOSIT_PUBLIC_INPUTS_LAYOUT.iter().zip(&proof.public_inputs) {
+        transcript.append_str(label, input);
+    }
+    transcript.append_str("note_commitment", &proof.note_commitment);
+    transcript.append_str("commitment_x", &proof.amount_commitment.x);
+    transcript.append_str("commitment_y", &proof.amount_commitment.y);
+    for data in &proof.range_proof.proof_data {
+        transcript.append_str("range_proof_data", data);
+    }
+    if let Some(denomination_id) = proof.denomination_id {
+        transcript.append_u64("denomination_id", denomination_id as u64);
+    }
+    format!("0x{}", hex::encode(transcript.challenge_bytes("statement")))
+}
+
+/// Verifica una prueba de depósito contra el config del pool (dominio, modo de montos y límites)
+pub fn verify_deposit(proof: &DepositProof, config: &ZKProofConfig) -> Result<VerificationReport, JsValue> {
+    let commitment_hash = proof.amount_commitment.commitment_hash.as_str();
+
+    if !proof.domain.matches(&config.domain) {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::WrongDomain, commitment_hash, "deposit belongs to another chain/contract/version",
+        ));
+    }
+    let leaf_valid = match point_from_hex(&proof.note_commitment) {
+        Ok(note_commitment) => note_leaf(&note_commitment, proof.denomination_id, &proof.domain)
+            .map(|leaf| leaf.commitment_hash == proof.amount_commitment.commitment_hash)
+            .unwrap_or(false),
+        Err(_) => false,
+    };
+    if !verify_pedersen_commitment(&proof.amount_commitment)? || !leaf_valid {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::InvalidCommitment, commitment_hash, "malformed amount commitment",
+        ));
+    }
+    if !verify_backend_range_proof(&proof.range_proof, &config.security_params())? {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::InvalidRangeProof, commitment_hash, "range proof rejected",
+        ));
+    }
+    if range_proof_commitment(&proof.range_proof) != Some(commitment_hash) {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::InvalidRangeProof, commitment_hash, "range proof is not over the amount commitment",
+        ));
+    }
+
+    // En modo denominación la range proof debe fijar el monto a la denominación declarada
+    let (min, max) = match config.range_bounds(proof.denomination_id) {
+        Ok(bounds) => bounds,
+        Err(e) => return Ok(VerificationReport::failed(VerificationFailure::InvalidDenomination, commitment_hash, &e)),
+    };
+
+    // Una range proof válida para otros límites no prueba que el monto esté en los del pool
+    let min = parse_amount(&min, "min amount")?;
+    let max = parse_amount(&max, "max amount")?;
+    if !range_proof_bounds_match(&proof.range_proof, min, max) {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::InvalidRangeProof, commitment_hash, "range proof bounds differ from pool limits",
+        ));
+    }
+
+    // Layout: [commitment_hash, min, max, domain_separator]; la range proof debe usar esos mismos límites
+    let layout_valid = proof.public_inputs.len() == DEPOSIT_PUBLIC_INPUTS_LAYOUT.len()
+        && proof.public_inputs[0] == proof.amount_commitment.commitment_hash
+        && proof.public_inputs[3] == proof.domain.separator_hex()
+        && match (parse_amount(&proof.public_inputs[1], "min amount"), parse_amount(&proof.public_inputs[2], "max amount")) {
+            (Ok(min), Ok(max)) => range_proof_bounds_match(&proof.range_proof, min, max),
+            _ => false,
+        };
+    if !layout_valid {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::InvalidPublicInputs, commitment_hash, "public inputs do not follow the deposit layout",
+        ));
+    }
+
+    if proof.statement_hash != deposit_statement_hash(proof) {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::StatementMismatch, commitment_hash, "public inputs do not match statement hash",
+        ));
+    }
+
+    Ok(VerificationReport::success(commitment_hash))
+}
+
+/// Genera la prueba de depósito (commitment + range proof)
+#[wasm_bindgen]
+pub fn generate_deposit_proof(
+    amount_wei: &str,
+    nonce: &str,
+    user_secret: &str,
+    config: &JsValue,
+) -> Result<JsValue, JsValue> {
+    console_error_panic_hook::set_once();
+
+    let config = parse_proof_config(config)?;
+    let recorder = ProofRecorder::start("deposit_proof");
+    let proof = build_deposit_proof(amount_wei, nonce, user_secret, &config)?;
+
+    let json_string = time_phase(ProofPhase::Serialization, || serde_json::to_string(&proof))
+        .map_err(|e| format!("Error serializing deposit proof: {}", e))?;
+    recorder.finish(json_string.len());
+    Ok(JsValue::from_str(&json_string))
+}
+
+/// Construye la prueba de depósito (sin serializar) con la config ya resuelta
+pub fn build_deposit_proof(
+    amount_wei: &str,
+    nonce: &str,
+    user_secret: &str,
+    config: &ZKProofConfig,
+) -> Result<DepositProof, JsValue> {
+    crate::console_log!("📥 Generando prueba de depósito: {} wei", amount_wei);
+
+    let (note_commitment, amount_commitment, denomination_id) = time_phase(ProofPhase::Commitment, || {
+        let denomination_id = config.amount_mode.denomination_id(amount_wei)?;
+        let (note_commitment, _) = deposit_note_commitment(amount_wei, nonce, user_secret, config)?;
+        let leaf = note_leaf(&note_commitment, denomination_id, &config.domain)?;
+        Ok::<_, JsValue>((note_commitment, leaf, denomination_id))
+    })?;
+    let (min, max) = config.range_bounds(denomination_id)?;
+    let range_proof = generate_backend_range_proof(
+        amount_wei, nonce, &min, &max, &amount_commitment.commitment_hash, &config.security_params(),
+    )?;
+
+    let min = parse_amount(&min, "min amount")?;
+    let max = parse_amount(&max, "max amount")?;
+
+    let mut proof = DepositProof {
+        public_inputs: deposit_public_inputs(&amount_commitment.commitment_hash, min, max, &config.domain),
+        note_commitment: point_to_hex(&note_commitment),
+        amount_commitment,
+        range_proof,
+        domain: config.domain.clone(),
+        statement_hash: String::new(),
+        denomination_id,
+    };
+    proof.statement_hash = deposit_statement_hash(&proof);
+
+    crate::console_log!("✅ Prueba de depósito generada");
+    Ok(proof)
+}
+
+/// Verifica una prueba de depósito en JSON con el config del pool
+#[wasm_bindgen]
+pub fn verify_deposit_proof(proof_json: &str, config: &JsValue) -> Result<bool, JsValue> {
+    let proof: DepositProof = serde_json::from_str(proof_json)
+        .map_err(|e| format!("Error parsing deposit proof: {}", e))?;
+    let config = parse_proof_config(config)?;
+
+    let report = verify_deposit(&proof, &config)?;
+    crate::console_log!("📋 Verificación depósito: valid={}, failure={:?}", report.valid, report.failure);
+    Ok(report.valid)
+}
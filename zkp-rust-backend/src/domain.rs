@@ -0,0 +1,96 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use crate::{normalize_felt_hex, Transcript};
+
+/// Chain IDs de Starknet (como strings cortos de Cairo)
+pub const SN_SEPOLIA: &str = "SN_SEPOLIA";
+pub const SN_MAIN: &str = "SN_MAIN";
+
+/// ZK Privacy Matrix V4 en Sepolia (ver src/lib/constants.ts del frontend)
+pub const ZK_PRIVACY_MATRIX_V4_SEPOLIA: &str =
+    "0x027f726c6917c75df9277fab3c3267ec47a056065a9a0e5d59c316d51f20e28e";
+
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Versión 0: commitments y nullifiers anteriores a la separación por dominio
+pub const LEGACY_PROTOCOL_VERSION: u32 = 0;
+
+/// Dominio de separación: una prueba o nullifier solo vale para esta
+/// combinación de cadena, contrato y versión de protocolo (anti-replay)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[wasm_bindgen(getter_with_clone)]
+pub struct Domain {
+    pub chain_id: String,
+    pub contract_address: String,
+    pub protocol_version: u32,
+}
+
+impl Default for Domain {
+    fn default() -> Self {
+        Self {
+            chain_id: SN_SEPOLIA.to_string(),
+            contract_address: ZK_PRIVACY_MATRIX_V4_SEPOLIA.to_string(),
+            protocol_version: PROTOCOL_VERSION,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Domain {
+    #[wasm_bindgen(constructor)]
+    pub fn new(chain_id: &str, contract_address: &str, protocol_version: u32) -> Domain {
+        Domain {
+            chain_id: chain_id.to_string(),
+            contract_address: contract_address.to_string(),
+            protocol_version,
+        }
+    }
+
+    /// Dominio de las notas y pruebas v0 (derivaciones sin separador)
+    #[wasm_bindgen]
+    pub fn legacy() -> Domain {
+        Domain { protocol_version: LEGACY_PROTOCOL_VERSION, ..Domain::default() }
+    }
+
+    #[wasm_bindgen]
+    pub fn is_legacy(&self) -> bool {
+        self.protocol_version == LEGACY_PROTOCOL_VERSION
+    }
+
+    /// Separador de dominio en hex
+    #[wasm_bindgen]
+    pub fn separator_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.separator()))
+    }
+}
+
+impl Domain {
+    /// Keccak256("ceaser-domain-v1" || chain_id || contrato normalizado || versión)
+    pub fn separator(&self) -> [u8; 32] {
+        let chain_id = self.chain_id.trim().to_uppercase();
+        let contract = normalize_felt_hex(&self.contract_address);
+
+        let mut hasher = Keccak256::new();
+        hasher.update(b"ceaser-domain-v1");
+        hasher.update((chain_id.len() as u32).to_le_bytes());
+        hasher.update(chain_id.as_bytes());
+        hasher.update((contract.len() as u32).to_le_bytes());
+        hasher.update(contract.as_bytes());
+        hasher.update(self.protocol_version.to_le_bytes());
+
+        let mut separator = [0u8; 32];
+        separator.copy_from_slice(&hasher.finalize());
+        separator
+    }
+
+    /// Mismo dominio salvo diferencias de formato (mayúsculas, ceros a la izquierda)
+    pub fn matches(&self, other: &Domain) -> bool {
+        self.separator() == other.separator()
+    }
+
+    /// Absorbe el dominio en un transcript Fiat–Shamir
+    pub fn append_to(&self, transcript: &mut Transcript) {
+        transcript.append_message("domain", &self.separator());
+    }
+}
@@ -1,11 +1,25 @@
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
+use crate::U256;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 /// Implementación de aritmética en el campo M31 (2^31 - 1)
 /// Usado por Circle STARKs y STWO
 
 const M31_MODULUS: u32 = (1u32 << 31) - 1; // 2^31 - 1 = 2147483647
 
+/// Reducción de Mersenne: como 2^31 ≡ 1 (mod p), x = hi·2^31 + lo ≡ hi + lo.
+/// Válida para x < 2^62 (producto de dos elementos reducidos)
+#[inline]
+fn reduce_m31(x: u64) -> u32 {
+    let folded = (x & M31_MODULUS as u64) + (x >> 31); // < 2^32
+    let folded = (folded & M31_MODULUS as u64) + (folded >> 31); // ≤ p
+    let value = folded as u32;
+    if value >= M31_MODULUS { value - M31_MODULUS } else { value }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[wasm_bindgen]
 pub struct M31Element {
@@ -17,7 +31,7 @@ impl M31Element {
     #[wasm_bindgen(constructor)]
     pub fn new(value: u32) -> M31Element {
         M31Element {
-            value: value % M31_MODULUS,
+            value: reduce_m31(value as u64),
         }
     }
     
@@ -29,8 +43,8 @@ impl M31Element {
     /// Suma en M31
     #[wasm_bindgen]
     pub fn add(&self, other: &M31Element) -> M31Element {
-        let sum = (self.value as u64 + other.value as u64) % M31_MODULUS as u64;
-        M31Element { value: sum as u32 }
+        let sum = self.value + other.value; // < 2^32
+        M31Element { value: if sum >= M31_MODULUS { sum - M31_MODULUS } else { sum } }
     }
     
     /// Resta en M31
@@ -47,8 +61,7 @@ impl M31Element {
     /// Multiplicación en M31
     #[wasm_bindgen]
     pub fn mul(&self, other: &M31Element) -> M31Element {
-        let product = (self.value as u64 * other.value as u64) % M31_MODULUS as u64;
-        M31Element { value: product as u32 }
+        M31Element { value: reduce_m31(self.value as u64 * other.value as u64) }
     }
     
     /// Inverso multiplicativo en M31
@@ -202,6 +215,43 @@ impl M31Vector {
         Ok(result)
     }
     
+    /// Resta elemento a elemento
+    #[wasm_bindgen]
+    pub fn sub_vector(&self, other: &M31Vector) -> Result<M31Vector, JsValue> {
+        Ok(M31Vector { elements: zip_map(&self.elements, &other.elements, |a, b| a.sub(b))? })
+    }
+    
+    /// Multiplicación elemento a elemento
+    #[wasm_bindgen]
+    pub fn mul_vector(&self, other: &M31Vector) -> Result<M31Vector, JsValue> {
+        Ok(M31Vector { elements: zip_map(&self.elements, &other.elements, |a, b| a.mul(b))? })
+    }
+    
+    /// Multiplica todos los elementos por un escalar
+    #[wasm_bindgen]
+    pub fn scale(&self, scalar: &M31Element) -> M31Vector {
+        let scalar = *scalar;
+        M31Vector { elements: map_elements(&self.elements, move |a| a.mul(&scalar)) }
+    }
+    
+    /// Suma de todos los elementos
+    #[wasm_bindgen]
+    pub fn sum(&self) -> M31Element {
+        reduce_elements(&self.elements, M31Element::new(0), |a, b| a.add(&b))
+    }
+    
+    /// Producto de todos los elementos
+    #[wasm_bindgen]
+    pub fn product(&self) -> M31Element {
+        reduce_elements(&self.elements, M31Element::new(1), |a, b| a.mul(&b))
+    }
+    
+    /// Inverso de todos los elementos con una sola inversión (Montgomery)
+    #[wasm_bindgen]
+    pub fn batch_inverse(&self) -> Result<M31Vector, JsValue> {
+        Ok(M31Vector { elements: batch_inverse(&self.elements)? })
+    }
+    
     /// Convierte a array de strings hex
     #[wasm_bindgen]
     pub fn to_hex_array(&self) -> Vec<String> {
@@ -209,29 +259,92 @@ impl M31Vector {
     }
 }
 
+/// A partir de este tamaño las operaciones de vector usan rayon (feature `parallel`)
+pub const PARALLEL_THRESHOLD: usize = 1 << 14;
+
+fn map_elements<F>(elements: &[M31Element], f: F) -> Vec<M31Element>
+where
+    F: Fn(&M31Element) -> M31Element + Sync + Send,
+{
+    crate::parallel::map_slice(elements, PARALLEL_THRESHOLD, f)
+}
+
+fn zip_map<F>(left: &[M31Element], right: &[M31Element], f: F) -> Result<Vec<M31Element>, String>
+where
+    F: Fn(&M31Element, &M31Element) -> M31Element + Sync + Send,
+{
+    if left.len() != right.len() {
+        return Err("Vectors must have same length".to_string());
+    }
+    Ok(crate::parallel::map_range(left.len(), PARALLEL_THRESHOLD, |i| f(&left[i], &right[i])))
+}
+
+fn reduce_elements<F>(elements: &[M31Element], identity: M31Element, f: F) -> M31Element
+where
+    F: Fn(M31Element, M31Element) -> M31Element + Sync + Send,
+{
+    // Suma y producto en M31 son asociativos y conmutativos: el reparto no cambia el resultado
+    #[cfg(feature = "parallel")]
+    if elements.len() >= PARALLEL_THRESHOLD && crate::parallel::parallel_enabled() {
+        return elements.par_iter().copied().reduce(|| identity, &f);
+    }
+    elements.iter().copied().fold(identity, f)
+}
+
+/// Inversión por lotes (truco de Montgomery): 3(n − 1) multiplicaciones y una inversión
+pub fn batch_inverse(elements: &[M31Element]) -> Result<Vec<M31Element>, String> {
+    if elements.iter().any(|e| e.value == 0) {
+        return Err("Cannot invert zero".to_string());
+    }
+    if elements.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // prefix[i] = e_0 · … · e_i
+    let mut prefix = Vec::with_capacity(elements.len());
+    let mut accumulator = M31Element::new(1);
+    for element in elements {
+        accumulator = accumulator.mul(element);
+        prefix.push(accumulator);
+    }
+
+    let mut inverse_accumulator = M31Element {
+        value: mod_inverse(accumulator.value as i64, M31_MODULUS as i64)
+            .ok_or("Inverse does not exist")? as u32,
+    };
+    let mut inverses = vec![M31Element::new(0); elements.len()];
+    for i in (1..elements.len()).rev() {
+        inverses[i] = inverse_accumulator.mul(&prefix[i - 1]);
+        inverse_accumulator = inverse_accumulator.mul(&elements[i]);
+    }
+    inverses[0] = inverse_accumulator;
+
+    Ok(inverses)
+}
+
+impl M31Vector {
+    pub fn from_elements(elements: Vec<M31Element>) -> M31Vector {
+        M31Vector { elements }
+    }
+
+    pub fn elements(&self) -> &[M31Element] {
+        &self.elements
+    }
+}
+
 /// Funciones utilitarias para aritmética M31
 
 /// Convierte un u256 (como string) a elementos M31
 #[wasm_bindgen]
 pub fn u256_to_m31_elements(value_str: &str) -> Result<M31Vector, JsValue> {
-    // Parsear el valor u256
-    let value = value_str.parse::<u128>()
+    // Parsear el valor u256 (decimal o hex)
+    let value = U256::parse(value_str)
         .map_err(|e| format!("Invalid u256: {}", e))?;
     
-    // Dividir en chunks de 31 bits
-    let mut elements = Vec::new();
-    let mut remaining = value;
-    
-    while remaining > 0 {
-        let chunk = (remaining & ((1u128 << 31) - 1)) as u32;
-        elements.push(M31Element::new(chunk));
-        remaining >>= 31;
-    }
-    
-    // Asegurar al menos un elemento
-    if elements.is_empty() {
-        elements.push(M31Element::new(0));
-    }
+    // Dividir en chunks de 30 bits (a lo sumo 9), sin los ceros de la parte alta
+    let limbs = value.to_m31_limbs();
+    let significant = limbs.iter().rposition(|limb| *limb != 0).map_or(1, |i| i + 1);
+    let elements = limbs[..significant].iter().map(|limb| M31Element::new(*limb)).collect();
     
     Ok(M31Vector { elements })
 }
@@ -256,20 +369,573 @@ pub fn evaluate_polynomial_m31(
     result
 }
 
-/// Genera puntos en el círculo unitario para Circle STARKs
+/// Punto del grupo circular x² + y² = 1 sobre M31 (orden p + 1 = 2^31)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[wasm_bindgen]
-pub fn generate_circle_points(num_points: u32) -> M31Vector {
-    let mut points = Vec::new();
-    
-    for i in 0..num_points {
-        // Generar puntos en el círculo unitario usando aproximación entera
-        let angle_num = (i as u64 * 2u64.pow(20)) / num_points as u64; // Aproximación de 2π
-        let cos_approx = ((angle_num as f64 / 2f64.powf(20.0) * 2.0 * std::f64::consts::PI).cos() * 1000000.0) as u32;
-        
-        points.push(M31Element::new(cos_approx % M31_MODULUS));
+pub struct CirclePoint {
+    x: M31Element,
+    y: M31Element,
+}
+
+/// Generador canónico del subgrupo de orden 2^31 (el mismo que usa STWO)
+const CIRCLE_GENERATOR_X: u32 = 2;
+const CIRCLE_GENERATOR_Y: u32 = 1268011823;
+const CIRCLE_LOG_ORDER: u32 = 31;
+
+#[wasm_bindgen]
+impl CirclePoint {
+    /// Crea un punto validando que esté en el círculo
+    #[wasm_bindgen(constructor)]
+    pub fn new(x: u32, y: u32) -> Result<CirclePoint, JsValue> {
+        let point = CirclePoint { x: M31Element::new(x), y: M31Element::new(y) };
+        if !point.is_on_circle() {
+            return Err(format!("Point ({}, {}) is not on the circle x^2 + y^2 = 1", x, y).into());
+        }
+        Ok(point)
     }
-    
-    M31Vector { elements: points }
+
+    /// Elemento neutro (1, 0)
+    #[wasm_bindgen]
+    pub fn zero() -> CirclePoint {
+        CirclePoint { x: M31Element::new(1), y: M31Element::new(0) }
+    }
+
+    /// Generador del subgrupo de orden 2^31
+    #[wasm_bindgen]
+    pub fn generator() -> CirclePoint {
+        CirclePoint { x: M31Element::new(CIRCLE_GENERATOR_X), y: M31Element::new(CIRCLE_GENERATOR_Y) }
+    }
+
+    /// Generador del subgrupo de orden 2^log_size
+    #[wasm_bindgen]
+    pub fn subgroup_generator(log_size: u32) -> Result<CirclePoint, JsValue> {
+        if log_size > CIRCLE_LOG_ORDER {
+            return Err(format!("Subgroup log size {} exceeds {}", log_size, CIRCLE_LOG_ORDER).into());
+        }
+        Ok(CirclePoint::generator().repeated_double(CIRCLE_LOG_ORDER - log_size))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn x(&self) -> M31Element {
+        self.x
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn y(&self) -> M31Element {
+        self.y
+    }
+
+    /// Ley de grupo: (x1·x2 − y1·y2, x1·y2 + y1·x2)
+    #[wasm_bindgen]
+    pub fn add(&self, other: &CirclePoint) -> CirclePoint {
+        CirclePoint {
+            x: self.x.mul(&other.x).sub(&self.y.mul(&other.y)),
+            y: self.x.mul(&other.y).add(&self.y.mul(&other.x)),
+        }
+    }
+
+    /// Duplicación: (2x² − 1, 2xy)
+    #[wasm_bindgen]
+    pub fn double(&self) -> CirclePoint {
+        let two = M31Element::new(2);
+        CirclePoint {
+            x: two.mul(&self.x.mul(&self.x)).sub(&M31Element::new(1)),
+            y: two.mul(&self.x.mul(&self.y)),
+        }
+    }
+
+    /// Conjugado (x, −y), que es también el inverso en el grupo
+    #[wasm_bindgen]
+    pub fn conjugate(&self) -> CirclePoint {
+        CirclePoint { x: self.x, y: M31Element::new(0).sub(&self.y) }
+    }
+
+    #[wasm_bindgen]
+    pub fn sub(&self, other: &CirclePoint) -> CirclePoint {
+        self.add(&other.conjugate())
+    }
+
+    /// Multiplicación escalar por double-and-add
+    #[wasm_bindgen]
+    pub fn mul_scalar(&self, scalar: u64) -> CirclePoint {
+        let mut result = CirclePoint::zero();
+        let mut base = *self;
+        let mut k = scalar;
+
+        while k > 0 {
+            if k & 1 == 1 {
+                result = result.add(&base);
+            }
+            base = base.double();
+            k >>= 1;
+        }
+
+        result
+    }
+
+    #[wasm_bindgen]
+    pub fn repeated_double(&self, times: u32) -> CirclePoint {
+        (0..times).fold(*self, |point, _| point.double())
+    }
+
+    /// Comprueba x² + y² = 1
+    #[wasm_bindgen]
+    pub fn is_on_circle(&self) -> bool {
+        self.x.mul(&self.x).add(&self.y.mul(&self.y)) == M31Element::new(1)
+    }
+}
+
+/// Coset `initial + <step>` de tamaño 2^log_size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Coset {
+    pub initial: CirclePoint,
+    pub step: CirclePoint,
+    pub log_size: u32,
+}
+
+impl Coset {
+    pub fn new(initial: CirclePoint, log_size: u32) -> Result<Coset, String> {
+        if log_size > CIRCLE_LOG_ORDER {
+            return Err(format!("Coset log size {} exceeds {}", log_size, CIRCLE_LOG_ORDER));
+        }
+        let step = CirclePoint::generator().repeated_double(CIRCLE_LOG_ORDER - log_size);
+        Ok(Coset { initial, step, log_size })
+    }
+
+    /// Subgrupo de orden 2^log_size
+    pub fn subgroup(log_size: u32) -> Result<Coset, String> {
+        Coset::new(CirclePoint::zero(), log_size)
+    }
+
+    /// Coset canónico G_{2n} + <G_n> (evita los puntos de orden pequeño)
+    pub fn odds(log_size: u32) -> Result<Coset, String> {
+        if log_size >= CIRCLE_LOG_ORDER {
+            return Err(format!("Odds coset log size {} must be below {}", log_size, CIRCLE_LOG_ORDER));
+        }
+        let initial = CirclePoint::generator().repeated_double(CIRCLE_LOG_ORDER - log_size - 1);
+        Coset::new(initial, log_size)
+    }
+
+    /// Medio coset G_{4n} + <G_n>, base del dominio de evaluación de tamaño 2n
+    pub fn half_odds(log_size: u32) -> Result<Coset, String> {
+        if log_size > CIRCLE_LOG_ORDER - 2 {
+            return Err(format!("Half-odds coset log size {} must be at most {}", log_size, CIRCLE_LOG_ORDER - 2));
+        }
+        let initial = CirclePoint::generator().repeated_double(CIRCLE_LOG_ORDER - log_size - 2);
+        Coset::new(initial, log_size)
+    }
+
+    pub fn size(&self) -> usize {
+        1 << self.log_size
+    }
+
+    pub fn conjugate(&self) -> Coset {
+        Coset {
+            initial: self.initial.conjugate(),
+            step: self.step.conjugate(),
+            log_size: self.log_size,
+        }
+    }
+
+    pub fn at(&self, index: usize) -> CirclePoint {
+        self.initial.add(&self.step.mul_scalar(index as u64))
+    }
+
+    pub fn iter(&self) -> CosetIterator {
+        CosetIterator { current: self.initial, step: self.step, remaining: self.size() }
+    }
+}
+
+/// Recorre los puntos de un coset en orden
+#[derive(Debug, Clone)]
+pub struct CosetIterator {
+    current: CirclePoint,
+    step: CirclePoint,
+    remaining: usize,
+}
+
+impl Iterator for CosetIterator {
+    type Item = CirclePoint;
+
+    fn next(&mut self) -> Option<CirclePoint> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let point = self.current;
+        self.current = self.current.add(&self.step);
+        self.remaining -= 1;
+        Some(point)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for CosetIterator {}
+
+/// Dominio de evaluación: medio coset más su conjugado (tamaño 2^log_size)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircleDomain {
+    pub half_coset: Coset,
+}
+
+impl CircleDomain {
+    /// Dominio canónico de tamaño 2^log_size
+    pub fn canonic(log_size: u32) -> Result<CircleDomain, String> {
+        if log_size == 0 {
+            return Err("Circle domain must have at least 2 points".to_string());
+        }
+        Ok(CircleDomain { half_coset: Coset::half_odds(log_size - 1)? })
+    }
+
+    /// Menor dominio canónico con al menos `num_points` puntos (mínimo 2)
+    pub fn covering(num_points: u32) -> Result<CircleDomain, String> {
+        let log_size = num_points
+            .checked_next_power_of_two()
+            .ok_or_else(|| format!("No circle domain covers {} points", num_points))?
+            .trailing_zeros()
+            .max(1);
+        CircleDomain::canonic(log_size)
+    }
+
+    pub fn log_size(&self) -> u32 {
+        self.half_coset.log_size + 1
+    }
+
+    pub fn size(&self) -> usize {
+        1 << self.log_size()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = CirclePoint> {
+        self.half_coset.iter().chain(self.half_coset.conjugate().iter())
+    }
+}
+
+/// Verifica que todos los puntos del dominio canónico están en el círculo y son distintos
+#[wasm_bindgen]
+pub fn verify_circle_domain(log_size: u32) -> Result<bool, JsValue> {
+    let domain = CircleDomain::canonic(log_size)?;
+    let points: Vec<CirclePoint> = domain.iter().collect();
+
+    let on_circle = points.iter().all(|point| point.is_on_circle());
+    let distinct = points
+        .iter()
+        .map(|point| (point.x.value, point.y.value))
+        .collect::<std::collections::HashSet<_>>()
+        .len() == domain.size();
+    let generator_order = CirclePoint::generator().repeated_double(CIRCLE_LOG_ORDER) == CirclePoint::zero()
+        && CirclePoint::generator().repeated_double(CIRCLE_LOG_ORDER - 1) != CirclePoint::zero();
+
+    Ok(on_circle && distinct && generator_order)
+}
+
+/// Genera las coordenadas x de los puntos del dominio circular canónico
+/// (el menor dominio de tamaño potencia de 2 que cubre `num_points`, hasta 2^30)
+#[wasm_bindgen]
+pub fn generate_circle_points(num_points: u32) -> Result<M31Vector, JsValue> {
+    if num_points == 0 {
+        return Ok(M31Vector::new());
+    }
+
+    let domain = CircleDomain::covering(num_points)?;
+    let elements = domain
+        .iter()
+        .take(num_points as usize)
+        .map(|point| {
+            debug_assert!(point.is_on_circle());
+            point.x
+        })
+        .collect();
+
+    Ok(M31Vector { elements })
+}
+
+/// Genera los puntos (x, y) del dominio circular canónico de tamaño 2^log_size
+#[wasm_bindgen]
+pub fn generate_circle_domain_points(log_size: u32) -> Result<JsValue, JsValue> {
+    let domain = CircleDomain::canonic(log_size)?;
+    let points: Vec<[u32; 2]> = domain.iter().map(|point| [point.x.value, point.y.value]).collect();
+
+    let json_string = serde_json::to_string(&points)
+        .map_err(|e| format!("Error serializing circle points: {}", e))?;
+    Ok(JsValue::from_str(&json_string))
+}
+
+/// Extensión compleja CM31 = M31[i] / (i² + 1), elemento a + b·i
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct CM31 {
+    a: M31Element,
+    b: M31Element,
+}
+
+#[wasm_bindgen]
+impl CM31 {
+    #[wasm_bindgen(constructor)]
+    pub fn new(a: u32, b: u32) -> CM31 {
+        CM31 { a: M31Element::new(a), b: M31Element::new(b) }
+    }
+
+    #[wasm_bindgen]
+    pub fn from_m31(value: &M31Element) -> CM31 {
+        CM31 { a: *value, b: M31Element::new(0) }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn a(&self) -> M31Element {
+        self.a
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn b(&self) -> M31Element {
+        self.b
+    }
+
+    #[wasm_bindgen]
+    pub fn is_zero(&self) -> bool {
+        self.a.value == 0 && self.b.value == 0
+    }
+
+    #[wasm_bindgen]
+    pub fn add(&self, other: &CM31) -> CM31 {
+        CM31 { a: self.a.add(&other.a), b: self.b.add(&other.b) }
+    }
+
+    #[wasm_bindgen]
+    pub fn sub(&self, other: &CM31) -> CM31 {
+        CM31 { a: self.a.sub(&other.a), b: self.b.sub(&other.b) }
+    }
+
+    #[wasm_bindgen]
+    pub fn neg(&self) -> CM31 {
+        CM31::new(0, 0).sub(self)
+    }
+
+    /// (a + bi)(c + di) = (ac − bd) + (ad + bc)i
+    #[wasm_bindgen]
+    pub fn mul(&self, other: &CM31) -> CM31 {
+        CM31 {
+            a: self.a.mul(&other.a).sub(&self.b.mul(&other.b)),
+            b: self.a.mul(&other.b).add(&self.b.mul(&other.a)),
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn mul_m31(&self, scalar: &M31Element) -> CM31 {
+        CM31 { a: self.a.mul(scalar), b: self.b.mul(scalar) }
+    }
+
+    /// (a + bi)⁻¹ = (a − bi) / (a² + b²)
+    #[wasm_bindgen]
+    pub fn inv(&self) -> Result<CM31, JsValue> {
+        if self.is_zero() {
+            return Err("Cannot invert zero".into());
+        }
+        let norm = self.a.mul(&self.a).add(&self.b.mul(&self.b));
+        Ok(self.conjugate().mul_m31(&norm.inv()?))
+    }
+
+    #[wasm_bindgen]
+    pub fn pow(&self, exp: u64) -> CM31 {
+        let mut result = CM31::new(1, 0);
+        let mut base = *self;
+        let mut exponent = exp;
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.mul(&base);
+            }
+            base = base.mul(&base);
+            exponent >>= 1;
+        }
+
+        result
+    }
+
+    /// Conjugado a − bi
+    #[wasm_bindgen]
+    pub fn conjugate(&self) -> CM31 {
+        CM31 { a: self.a, b: M31Element::new(0).sub(&self.b) }
+    }
+
+    /// x^p; como p ≡ 3 (mod 4), i^p = −i y coincide con el conjugado
+    #[wasm_bindgen]
+    pub fn frobenius(&self) -> CM31 {
+        self.conjugate()
+    }
+
+    #[wasm_bindgen]
+    pub fn to_hex_array(&self) -> Vec<String> {
+        vec![self.a.to_hex(), self.b.to_hex()]
+    }
+}
+
+/// Extensión de grado 4 QM31 = CM31[u] / (u² − (2 + i)), elemento a + b·u
+/// Es el campo seguro (~124 bits) para challenges y muestreo fuera del dominio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct QM31 {
+    a: CM31,
+    b: CM31,
+}
+
+/// No-residuo R = 2 + i que define QM31 (el mismo que usa STWO)
+fn qm31_irreducible() -> CM31 {
+    CM31::new(2, 1)
+}
+
+#[wasm_bindgen]
+impl QM31 {
+    /// Crea (a + bi) + (c + di)·u, mismo orden que `QM31::from_u32_unchecked` de STWO
+    #[wasm_bindgen(constructor)]
+    pub fn new(a: u32, b: u32, c: u32, d: u32) -> QM31 {
+        QM31 { a: CM31::new(a, b), b: CM31::new(c, d) }
+    }
+
+    #[wasm_bindgen]
+    pub fn from_m31(value: &M31Element) -> QM31 {
+        QM31 { a: CM31::from_m31(value), b: CM31::new(0, 0) }
+    }
+
+    #[wasm_bindgen]
+    pub fn from_cm31(a: &CM31, b: &CM31) -> QM31 {
+        QM31 { a: *a, b: *b }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn a(&self) -> CM31 {
+        self.a
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn b(&self) -> CM31 {
+        self.b
+    }
+
+    /// Coordenadas M31 [a, b, c, d]
+    #[wasm_bindgen]
+    pub fn to_m31_array(&self) -> Vec<u32> {
+        vec![self.a.a.value, self.a.b.value, self.b.a.value, self.b.b.value]
+    }
+
+    #[wasm_bindgen]
+    pub fn is_zero(&self) -> bool {
+        self.a.is_zero() && self.b.is_zero()
+    }
+
+    #[wasm_bindgen]
+    pub fn add(&self, other: &QM31) -> QM31 {
+        QM31 { a: self.a.add(&other.a), b: self.b.add(&other.b) }
+    }
+
+    #[wasm_bindgen]
+    pub fn sub(&self, other: &QM31) -> QM31 {
+        QM31 { a: self.a.sub(&other.a), b: self.b.sub(&other.b) }
+    }
+
+    #[wasm_bindgen]
+    pub fn neg(&self) -> QM31 {
+        QM31 { a: self.a.neg(), b: self.b.neg() }
+    }
+
+    /// (a + bu)(c + du) = (ac + R·bd) + (ad + bc)u
+    #[wasm_bindgen]
+    pub fn mul(&self, other: &QM31) -> QM31 {
+        QM31 {
+            a: self.a.mul(&other.a).add(&qm31_irreducible().mul(&self.b.mul(&other.b))),
+            b: self.a.mul(&other.b).add(&self.b.mul(&other.a)),
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn mul_m31(&self, scalar: &M31Element) -> QM31 {
+        QM31 { a: self.a.mul_m31(scalar), b: self.b.mul_m31(scalar) }
+    }
+
+    /// (a + bu)⁻¹ = (a − bu) / (a² − R·b²)
+    #[wasm_bindgen]
+    pub fn inv(&self) -> Result<QM31, JsValue> {
+        if self.is_zero() {
+            return Err("Cannot invert zero".into());
+        }
+        let denominator = self.a.mul(&self.a).sub(&qm31_irreducible().mul(&self.b.mul(&self.b)));
+        let denominator_inv = denominator.inv()?;
+        Ok(QM31 {
+            a: self.a.mul(&denominator_inv),
+            b: self.b.neg().mul(&denominator_inv),
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn pow(&self, exp: u64) -> QM31 {
+        let mut result = QM31::new(1, 0, 0, 0);
+        let mut base = *self;
+        let mut exponent = exp;
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.mul(&base);
+            }
+            base = base.mul(&base);
+            exponent >>= 1;
+        }
+
+        result
+    }
+
+    /// x^p = conj(a) + conj(b)·u^p, con u^p = R^((p−1)/2)·u
+    #[wasm_bindgen]
+    pub fn frobenius(&self) -> QM31 {
+        let u_frobenius = qm31_irreducible().pow(((M31_MODULUS - 1) / 2) as u64);
+        QM31 {
+            a: self.a.frobenius(),
+            b: self.b.frobenius().mul(&u_frobenius),
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn to_hex_array(&self) -> Vec<String> {
+        let mut hex = self.a.to_hex_array();
+        hex.extend(self.b.to_hex_array());
+        hex
+    }
+}
+
+/// Comprueba las leyes de CM31/QM31 sobre valores fijos (inverso, Frobenius de
+/// orden 4, consistencia de pow). La comparación con los tipos de STWO está en
+/// `tests/extension_fields.rs`
+#[wasm_bindgen]
+pub fn verify_extension_fields() -> Result<bool, JsValue> {
+    let one = QM31::new(1, 0, 0, 0);
+    let samples = [
+        QM31::new(1, 2, 3, 4),
+        QM31::new(M31_MODULUS - 1, 7, 0, 123456789),
+        QM31::new(0, 0, 0, 1),
+        QM31::new(2147483000, 5, 99, 2),
+    ];
+
+    for x in &samples {
+        if x.mul(&x.inv()?) != one {
+            return Ok(false);
+        }
+        let frobenius_4 = x.frobenius().frobenius().frobenius().frobenius();
+        if frobenius_4 != *x || x.frobenius() != x.pow(M31_MODULUS as u64) {
+            return Ok(false);
+        }
+        if x.pow(5) != x.mul(x).mul(x).mul(x).mul(x) {
+            return Ok(false);
+        }
+        let cm = x.a();
+        if cm.frobenius() != cm.pow(M31_MODULUS as u64) {
+            return Ok(false);
+        }
+        if !cm.is_zero() && cm.mul(&cm.inv()?) != CM31::new(1, 0) {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
 }
 
 /// Información sobre el campo M31
@@ -283,6 +949,12 @@ pub fn get_m31_info() -> JsValue {
         "size_bits": 31,
         "is_prime": true,
         "generator": 3,
+        "circle_generator": [CIRCLE_GENERATOR_X, CIRCLE_GENERATOR_Y],
+        "circle_group_order": "2^31",
+        "extensions": {
+            "cm31": "M31[i] / (i^2 + 1)",
+            "qm31": "CM31[u] / (u^2 - (2 + i))"
+        },
         "applications": [
             "Circle STARKs",
             "STWO Prover",
@@ -0,0 +1,400 @@
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use crate::parallel::{map_range, map_slice, HASH_PARALLEL_THRESHOLD};
+use crate::{now_ms, record_phase_ms, M31Element, ProofPhase, QM31, Transcript, TwiddleTree, PARALLEL_THRESHOLD};
+
+/// FRI educativo sobre dominios circulares de M31 (retos en QM31).
+///
+/// Capa 0: evaluaciones en el dominio canónico de tamaño 2^log_domain_size.
+/// El primer plegado es circular (pares p, p̄), el resto son de línea (pares x, −x):
+///   f'(i) = (f(i) + f(i + h)) / 2 + α · (f(i) − f(i + h)) / (2·t_i)
+/// con t_i = y_i o x_i según la capa. Tras `log_domain_size − log_blowup −
+/// log_last_layer_degree_bound` plegados la última capa (grado < 2^log_last_layer_degree_bound)
+/// se envía completa; el verificador la pliega hasta una constante. Antes de las
+/// consultas el prover hace grinding de `pow_bits` bits.
+/// No es zero-knowledge: las aperturas revelan evaluaciones.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FriProof {
+    pub layer_roots: Vec<String>,
+    pub last_layer: Vec<String>,
+    pub queries: Vec<FriQueryProof>,
+    #[serde(default)]
+    pub proof_of_work: u64,
+}
+
+/// Aperturas de una consulta: un par (i, i + h) por capa comprometida
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FriQueryProof {
+    pub index: usize,
+    pub layers: Vec<FriDecommitment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FriDecommitment {
+    pub values: [String; 2],
+    pub paths: [Vec<String>; 2],
+}
+
+/// Parámetros del FRI (tamaños en log2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FriParams {
+    pub log_domain_size: u32,
+    pub log_blowup: u32,
+    pub num_queries: usize,
+    pub pow_bits: u32,
+    pub log_last_layer_degree_bound: u32,
+}
+
+/// Cota del grinding para que el prover termine en tiempo razonable
+pub const MAX_POW_BITS: u32 = 32;
+
+impl FriParams {
+    pub fn num_folds(&self) -> Result<usize, String> {
+        if self.log_blowup == 0 {
+            return Err("FRI needs a blowup factor of at least 2".to_string());
+        }
+        if self.log_blowup + self.log_last_layer_degree_bound >= self.log_domain_size {
+            return Err(format!(
+                "Blowup 2^{} times last layer degree 2^{} must be smaller than the domain 2^{}",
+                self.log_blowup, self.log_last_layer_degree_bound, self.log_domain_size
+            ));
+        }
+        if self.num_queries == 0 {
+            return Err("FRI needs at least one query".to_string());
+        }
+        if self.pow_bits > MAX_POW_BITS {
+            return Err(format!("Proof of work is limited to {} bits, got {}", MAX_POW_BITS, self.pow_bits));
+        }
+        Ok((self.log_domain_size - self.log_blowup - self.log_last_layer_degree_bound) as usize)
+    }
+
+    fn last_layer_size(&self) -> usize {
+        1usize << (self.log_blowup + self.log_last_layer_degree_bound)
+    }
+}
+
+/// 1/2 en M31
+const INV_TWO: u32 = 1 << 30;
+
+pub fn qm31_to_hex(value: &QM31) -> String {
+    let limbs = value.to_m31_array();
+    format!("0x{:08x}{:08x}{:08x}{:08x}", limbs[0], limbs[1], limbs[2], limbs[3])
+}
+
+pub fn qm31_from_hex(value: &str) -> Result<QM31, String> {
+    let digits = value.trim_start_matches("0x");
+    if digits.len() != 32 {
+        return Err(format!("QM31 hex must have 32 digits, got {}", digits.len()));
+    }
+    let mut limbs = [0u32; 4];
+    for (i, limb) in limbs.iter_mut().enumerate() {
+        *limb = u32::from_str_radix(&digits[i * 8..(i + 1) * 8], 16)
+            .map_err(|e| format!("Invalid QM31 hex: {}", e))?;
+        if *limb >= (1u32 << 31) - 1 {
+            return Err("QM31 limb is not canonical".to_string());
+        }
+    }
+    Ok(QM31::new(limbs[0], limbs[1], limbs[2], limbs[3]))
+}
+
+fn draw_qm31(transcript: &mut Transcript, label: &str) -> QM31 {
+    let bytes = transcript.challenge_bytes(label);
+    let limb = |i: usize| {
+        u64::from_le_bytes(bytes[i * 8..(i + 1) * 8].try_into().expect("8-byte slice")) % ((1u64 << 31) - 1)
+    };
+    QM31::new(limb(0) as u32, limb(1) as u32, limb(2) as u32, limb(3) as u32)
+}
+
+fn draw_query_indices(transcript: &mut Transcript, num_queries: usize, bound: usize) -> Vec<usize> {
+    (0..num_queries)
+        .map(|_| {
+            let bytes = transcript.challenge_bytes("fri_query");
+            (u64::from_le_bytes(bytes[..8].try_into().expect("8-byte slice")) % bound as u64) as usize
+        })
+        .collect()
+}
+
+/// Retos para plegar la última capa hasta una constante (prover y verificador los extraen igual)
+fn draw_last_layer_alphas(transcript: &mut Transcript, params: &FriParams) -> Vec<QM31> {
+    (0..params.log_last_layer_degree_bound)
+        .map(|_| draw_qm31(transcript, "fri_last_layer_alpha"))
+        .collect()
+}
+
+fn pow_digest(seed: &[u8; 32], nonce: u64) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"fri-pow");
+    hasher.update(seed);
+    hasher.update(nonce.to_le_bytes());
+    hasher.finalize().into()
+}
+
+fn leading_zero_bits(digest: &[u8; 32]) -> u32 {
+    let mut bits = 0;
+    for byte in digest {
+        bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
+/// Grinding: primer nonce cuyo hash con el estado del transcript tiene `pow_bits` ceros
+fn grind(transcript: &mut Transcript, pow_bits: u32) -> u64 {
+    let seed = transcript.challenge_bytes("fri_pow_seed");
+    let nonce = (0u64..)
+        .find(|nonce| leading_zero_bits(&pow_digest(&seed, *nonce)) >= pow_bits)
+        .expect("a nonce exists for pow_bits <= MAX_POW_BITS");
+    transcript.append_u64("fri_pow_nonce", nonce);
+    nonce
+}
+
+fn verify_pow(transcript: &mut Transcript, pow_bits: u32, nonce: u64) -> bool {
+    let seed = transcript.challenge_bytes("fri_pow_seed");
+    transcript.append_u64("fri_pow_nonce", nonce);
+    leading_zero_bits(&pow_digest(&seed, nonce)) >= pow_bits
+}
+
+fn hash_leaf(value: &QM31) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"fri-leaf");
+    for limb in value.to_m31_array() {
+        hasher.update(limb.to_le_bytes());
+    }
+    hasher.finalize().into()
+}
+
+fn hash_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"fri-node");
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Árbol Merkle Keccak de una capa FRI (tamaño potencia de 2)
+struct LayerTree {
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl LayerTree {
+    fn commit(values: &[QM31]) -> LayerTree {
+        let mut levels = vec![map_slice(values, HASH_PARALLEL_THRESHOLD, hash_leaf)];
+        while levels.last().map_or(0, |level| level.len()) > 1 {
+            let level = levels.last().expect("levels is never empty");
+            let next = map_range(level.len() / 2, HASH_PARALLEL_THRESHOLD, |i| {
+                hash_node(&level[2 * i], &level[2 * i + 1])
+            });
+            levels.push(next);
+        }
+        LayerTree { levels }
+    }
+
+    fn root(&self) -> String {
+        format!("0x{}", hex::encode(self.levels.last().expect("levels is never empty")[0]))
+    }
+
+    fn open(&self, index: usize) -> Vec<String> {
+        let mut position = index;
+        let mut path = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            path.push(format!("0x{}", hex::encode(level[position ^ 1])));
+            position >>= 1;
+        }
+        path
+    }
+}
+
+fn verify_path(root: &str, value: &QM31, index: usize, path: &[String], depth: usize) -> Result<bool, String> {
+    if path.len() != depth {
+        return Ok(false);
+    }
+    let mut node = hash_leaf(value);
+    let mut position = index;
+    for sibling_hex in path {
+        let sibling: [u8; 32] = hex::decode(sibling_hex.trim_start_matches("0x"))
+            .map_err(|e| format!("Invalid Merkle sibling: {}", e))?
+            .try_into()
+            .map_err(|_| "Merkle sibling must be 32 bytes".to_string())?;
+        node = if position & 1 == 0 { hash_node(&node, &sibling) } else { hash_node(&sibling, &node) };
+        position >>= 1;
+    }
+    Ok(format!("0x{}", hex::encode(node)) == root)
+}
+
+/// Pliega un par con el reto α y el inverso del twiddle
+fn fold_pair(low: &QM31, high: &QM31, inv_twiddle: &M31Element, alpha: &QM31) -> QM31 {
+    let inv_two = M31Element::new(INV_TWO);
+    let sum = low.add(high).mul_m31(&inv_two);
+    let diff = low.sub(high).mul_m31(&inv_two.mul(inv_twiddle));
+    sum.add(&alpha.mul(&diff))
+}
+
+fn inv_twiddles_for_layer(twiddles: &TwiddleTree, layer: usize) -> &[M31Element] {
+    if layer == 0 {
+        &twiddles.circle_inv_twiddles
+    } else {
+        &twiddles.line_inv_twiddles[layer - 1]
+    }
+}
+
+fn fold_layer(values: &[QM31], inv_twiddles: &[M31Element], alpha: &QM31) -> Vec<QM31> {
+    let half = values.len() / 2;
+    map_range(half, PARALLEL_THRESHOLD, |i| {
+        fold_pair(&values[i], &values[i + half], &inv_twiddles[i], alpha)
+    })
+}
+
+/// Prueba que `evaluations` (en el dominio canónico) son de bajo grado.
+/// El transcript debe venir ya inicializado con las entradas públicas
+pub fn fri_prove(
+    evaluations: &[M31Element],
+    params: &FriParams,
+    transcript: &mut Transcript,
+) -> Result<FriProof, String> {
+    let num_folds = params.num_folds()?;
+    if evaluations.len() != 1usize << params.log_domain_size {
+        return Err(format!(
+            "Expected 2^{} evaluations, got {}", params.log_domain_size, evaluations.len()
+        ));
+    }
+    let twiddles = TwiddleTree::for_log_size(params.log_domain_size)?;
+    let fri_start = now_ms();
+    let mut commit_ms = 0.0;
+
+    // Commit: comprometer cada capa y plegar con el reto derivado de su raíz
+    let mut layers: Vec<Vec<QM31>> = vec![evaluations.iter().map(QM31::from_m31).collect()];
+    let mut trees = Vec::with_capacity(num_folds);
+    for layer in 0..num_folds {
+        let commit_start = now_ms();
+        let tree = LayerTree::commit(&layers[layer]);
+        commit_ms += now_ms() - commit_start;
+        transcript.append_str("fri_layer_root", &tree.root());
+        let alpha = draw_qm31(transcript, "fri_alpha");
+        let folded = fold_layer(&layers[layer], inv_twiddles_for_layer(&twiddles, layer), &alpha);
+        trees.push(tree);
+        layers.push(folded);
+    }
+
+    let last_layer: Vec<String> = layers[num_folds].iter().map(qm31_to_hex).collect();
+    for value in &last_layer {
+        transcript.append_str("fri_last_layer", value);
+    }
+    draw_last_layer_alphas(transcript, params);
+    let proof_of_work = grind(transcript, params.pow_bits);
+
+    // Query: abrir el par de cada capa en la posición derivada del transcript
+    let query_indices = draw_query_indices(transcript, params.num_queries, evaluations.len() / 2);
+    let queries = query_indices
+        .into_iter()
+        .map(|index| {
+            let mut pair_index = index;
+            let layers = trees
+                .iter()
+                .zip(&layers)
+                .map(|(tree, values)| {
+                    let half = values.len() / 2;
+                    pair_index %= half;
+                    FriDecommitment {
+                        values: [qm31_to_hex(&values[pair_index]), qm31_to_hex(&values[pair_index + half])],
+                        paths: [tree.open(pair_index), tree.open(pair_index + half)],
+                    }
+                })
+                .collect();
+            FriQueryProof { index, layers }
+        })
+        .collect();
+
+    // Merkle de las capas → Commit; plegado, grinding y consultas → FRI
+    record_phase_ms(ProofPhase::Commit, commit_ms);
+    record_phase_ms(ProofPhase::Fri, now_ms() - fri_start - commit_ms);
+
+    Ok(FriProof {
+        layer_roots: trees.iter().map(|tree| tree.root()).collect(),
+        last_layer,
+        queries,
+        proof_of_work,
+    })
+}
+
+/// Verifica la prueba FRI repitiendo el transcript del prover
+pub fn fri_verify(proof: &FriProof, params: &FriParams, transcript: &mut Transcript) -> Result<bool, String> {
+    let num_folds = params.num_folds()?;
+    if proof.layer_roots.len() != num_folds
+        || proof.last_layer.len() != params.last_layer_size()
+        || proof.queries.len() != params.num_queries
+    {
+        return Ok(false);
+    }
+    let twiddles = TwiddleTree::for_log_size(params.log_domain_size)?;
+
+    let mut alphas = Vec::with_capacity(num_folds);
+    for root in &proof.layer_roots {
+        transcript.append_str("fri_layer_root", root);
+        alphas.push(draw_qm31(transcript, "fri_alpha"));
+    }
+
+    let last_layer = proof.last_layer.iter().map(|v| qm31_from_hex(v)).collect::<Result<Vec<_>, _>>()?;
+    for value in &proof.last_layer {
+        transcript.append_str("fri_last_layer", value);
+    }
+
+    // La última capa tiene grado < 2^d si tras d plegados más queda constante
+    let mut folded_last_layer = last_layer.clone();
+    for (offset, alpha) in draw_last_layer_alphas(transcript, params).iter().enumerate() {
+        folded_last_layer = fold_layer(&folded_last_layer, inv_twiddles_for_layer(&twiddles, num_folds + offset), alpha);
+    }
+    if folded_last_layer.iter().any(|value| *value != folded_last_layer[0]) {
+        return Ok(false);
+    }
+
+    if !verify_pow(transcript, params.pow_bits, proof.proof_of_work) {
+        return Ok(false);
+    }
+
+    let domain_size = 1usize << params.log_domain_size;
+    let expected_indices = draw_query_indices(transcript, params.num_queries, domain_size / 2);
+
+    for (query, expected_index) in proof.queries.iter().zip(expected_indices) {
+        if query.index != expected_index || query.layers.len() != num_folds {
+            return Ok(false);
+        }
+
+        let mut pair_index = query.index;
+        let mut carried: Option<(usize, QM31)> = None; // (posición, valor plegado de la capa anterior)
+        for (layer, decommitment) in query.layers.iter().enumerate() {
+            let layer_size = domain_size >> layer;
+            let half = layer_size / 2;
+            pair_index %= half;
+            let depth = layer_size.trailing_zeros() as usize;
+
+            let low = qm31_from_hex(&decommitment.values[0])?;
+            let high = qm31_from_hex(&decommitment.values[1])?;
+            let root = &proof.layer_roots[layer];
+            if !verify_path(root, &low, pair_index, &decommitment.paths[0], depth)?
+                || !verify_path(root, &high, pair_index + half, &decommitment.paths[1], depth)?
+            {
+                return Ok(false);
+            }
+
+            // El valor plegado de la capa anterior debe coincidir con la apertura
+            if let Some((position, value)) = carried {
+                let opened = if position == pair_index { low } else { high };
+                if opened != value {
+                    return Ok(false);
+                }
+            }
+
+            let inv_twiddle = inv_twiddles_for_layer(&twiddles, layer)[pair_index];
+            carried = Some((pair_index, fold_pair(&low, &high, &inv_twiddle, &alphas[layer])));
+        }
+
+        match carried {
+            Some((position, value)) if last_layer[position] == value => {}
+            _ => return Ok(false),
+        }
+    }
+
+    Ok(true)
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use sha3::{Digest, Keccak256};
+use crate::MerkleProof;
+
+/// Altura máxima: `leaf_index` de `MerkleProof` es u32
+pub const MAX_MERKLE_HEIGHT: u32 = 32;
+
+/// Árbol Merkle incremental Keccak del anonymous set, nativo (sin JsValue).
+///
+/// Las hojas se añaden en orden y los subárboles vacíos valen el hash cero de
+/// su nivel, así que solo se guardan los nodos ya escritos: altura 32 cabe en
+/// memoria. Padre = Keccak(izquierdo ‖ derecho), el mismo camino que comprueba
+/// `verify_merkle_proof_with_index`.
+#[derive(Debug, Clone)]
+pub struct IncrementalMerkleTree {
+    height: u32,
+    next_index: u64,
+    /// Nodos escritos por nivel (0 = hojas), indexados por posición
+    levels: Vec<HashMap<u64, [u8; 32]>>,
+    /// Hash de un subárbol vacío de cada altura
+    zeros: Vec<[u8; 32]>,
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+impl IncrementalMerkleTree {
+    pub fn new(height: u32) -> Result<IncrementalMerkleTree, String> {
+        if height == 0 || height > MAX_MERKLE_HEIGHT {
+            return Err(format!("Merkle height must be between 1 and {}", MAX_MERKLE_HEIGHT));
+        }
+        let mut zeros = vec![[0u8; 32]];
+        for level in 0..height as usize {
+            zeros.push(hash_pair(&zeros[level], &zeros[level]));
+        }
+        Ok(IncrementalMerkleTree {
+            height,
+            next_index: 0,
+            levels: vec![HashMap::new(); height as usize + 1],
+            zeros,
+        })
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn len(&self) -> u64 {
+        self.next_index
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.next_index == 0
+    }
+
+    fn node(&self, level: usize, position: u64) -> [u8; 32] {
+        self.levels[level].get(&position).copied().unwrap_or(self.zeros[level])
+    }
+
+    /// Añade la hoja (hash de 32 bytes en hex) y devuelve su índice
+    pub fn append(&mut self, leaf_hash: &str) -> Result<u32, String> {
+        if self.next_index >= 1u64 << self.height {
+            return Err(format!("Merkle tree of height {} is full", self.height));
+        }
+        let leaf: [u8; 32] = hex::decode(leaf_hash.trim_start_matches("0x"))
+            .map_err(|e| format!("Invalid leaf hash: {}", e))?
+            .try_into()
+            .map_err(|_| "Leaf hash must be 32 bytes".to_string())?;
+
+        let index = self.next_index;
+        let mut position = index;
+        let mut node = leaf;
+        self.levels[0].insert(position, node);
+        for level in 0..self.height as usize {
+            node = if position & 1 == 0 {
+                hash_pair(&node, &self.node(level, position + 1))
+            } else {
+                hash_pair(&self.node(level, position - 1), &node)
+            };
+            position >>= 1;
+            self.levels[level + 1].insert(position, node);
+        }
+        self.next_index += 1;
+        Ok(index as u32)
+    }
+
+    pub fn root(&self) -> String {
+        format!("0x{}", hex::encode(self.node(self.height as usize, 0)))
+    }
+
+    /// Camino de pertenencia de una hoja ya añadida
+    pub fn proof(&self, leaf_index: u32) -> Result<MerkleProof, String> {
+        self.window_proof(leaf_index, 1)
+    }
+
+    /// Camino de la ventana de `size` hojas (potencia de dos, alineada) que
+    /// contiene `leaf_index`: `leaf_hash` es la raíz de la ventana, como la
+    /// recalcula `anonymity_window_root`, y el camino sube desde ahí
+    pub fn window_proof(&self, leaf_index: u32, size: u32) -> Result<MerkleProof, String> {
+        if u64::from(leaf_index) >= self.next_index {
+            return Err(format!("Leaf {} not in tree ({} leaves)", leaf_index, self.next_index));
+        }
+        if !size.is_power_of_two() || size.trailing_zeros() > self.height {
+            return Err(format!("Window size {} does not fit a tree of height {}", size, self.height));
+        }
+        let window_level = size.trailing_zeros() as usize;
+        let mut position = u64::from(leaf_index) >> window_level;
+        let leaf_hash = format!("0x{}", hex::encode(self.node(window_level, position)));
+        let mut proof_path = Vec::with_capacity(self.height as usize - window_level);
+        for level in window_level..self.height as usize {
+            proof_path.push(format!("0x{}", hex::encode(self.node(level, position ^ 1))));
+            position >>= 1;
+        }
+        Ok(MerkleProof {
+            proof_path,
+            leaf_index: leaf_index >> window_level,
+            leaf_hash,
+            root: self.root(),
+        })
+    }
+}
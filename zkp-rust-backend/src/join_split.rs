@@ -0,0 +1,435 @@
+use std::collections::HashSet;
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use ark_bn254::{Fr, G1Projective};
+use ark_ff::{BigInteger, PrimeField, Zero};
+use rand::{CryptoRng, RngCore};
+use crate::value_commitment::{point_from_hex, point_to_hex, BalanceProof, ValueRangeProof};
+use crate::{
+    build_anonymous_set_merkle_proof, check_allowed_assets, default_allowed_assets, note_leaf, nullifier_from_spend_key,
+    resolve_asset_id, verify_merkle_proof_with_index, BlindedAsset, normalize_felt_hex, Domain, MerkleProof, Note,
+    parse_amount, PedersenCommitment, ProofRng, Transcript, TransferBinding, U256,
+};
+
+const JOIN_SPLIT_PROTOCOL: &str = "ceaser-join-split-v1";
+const ASSET_SURJECTION_PROTOCOL: &str = "ceaser-asset-surjection-v1";
+
+/// Configuración de la transferencia privada join-split (N entradas → M salidas)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JoinSplitConfig {
+    pub max_inputs: u32,
+    pub max_outputs: u32,
+    pub max_amount: String, // En wei, cota de la range proof de cada salida
+    #[serde(default)]
+    pub domain: Domain,
+    /// Direcciones de los activos que el pool acepta (no puede estar vacía)
+    #[serde(default = "default_allowed_assets")]
+    pub allowed_assets: Vec<String>,
+    /// Semilla (32 bytes hex) del modo determinista; solo para vectores de prueba.
+    /// Sin la feature `deterministic-rng` el campo no existe y se ignora
+    #[cfg(feature = "deterministic-rng")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rng_seed: Option<String>,
+}
+
+impl Default for JoinSplitConfig {
+    fn default() -> Self {
+        Self {
+            max_inputs: 2,
+            max_outputs: 2,
+            max_amount: "1000000000000000000000".to_string(), // 1000 STRK
+            domain: Domain::default(),
+            allowed_assets: default_allowed_assets(),
+            #[cfg(feature = "deterministic-rng")]
+            rng_seed: None,
+        }
+    }
+}
+
+/// Testigo privado de cada nota que se gasta
+#[derive(Debug, Clone)]
+pub struct JoinSplitInput {
+    pub note: Note,
+    pub merkle_proof: MerkleProof,
+}
+
+/// Parte pública de cada entrada: membresía + nullifier.
+/// La hoja se recalcula desde N = V + S y el nullifier desde S, así que ambos
+/// quedan fijados por la nota depositada
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JoinSplitInputProof {
+    pub nullifier: String,
+    pub commitment_hash: String,
+    pub value_commitment: String,
+    pub spend_key: String,
+    pub merkle_proof: MerkleProof,
+    pub merkle_root: String,
+}
+
+/// Parte pública de cada salida: hoja de la nota nueva (`note_leaf` de V + S,
+/// la misma que un depósito) + range proof sobre el propio V
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JoinSplitOutputProof {
+    pub commitment: PedersenCommitment,
+    pub value_commitment: String,
+    pub spend_key: String,
+    pub range_proof: ValueRangeProof,
+}
+
+/// Prueba join-split: Σ entradas = Σ salidas + fee (fee público)
+/// El binding (receptor, relayer, fee) entra en el transcript de la prueba de balance,
+/// por lo que no se puede redirigir sin conocer los blindings.
+/// Cada V = v·A + r·H usa el generador de su activo; con A' = A + ρ·H el
+/// exceso ΣV_in − ΣV_out − fee·A' solo es múltiplo de H si todas las notas son
+/// del activo de A' y los montos cuadran
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JoinSplitProof {
+    pub inputs: Vec<JoinSplitInputProof>,
+    pub outputs: Vec<JoinSplitOutputProof>,
+    pub binding: TransferBinding,
+    pub domain: Domain,
+    pub asset: BlindedAsset,
+    pub balance_proof: BalanceProof,
+}
+
+/// Genera la prueba join-split usando la fuente de aleatoriedad indicada
+pub fn prove_join_split<R: RngCore + CryptoRng>(
+    inputs: &[JoinSplitInput],
+    outputs: &[Note],
+    asset: &str,
+    binding: &TransferBinding,
+    config: &JoinSplitConfig,
+    rng: &mut R,
+) -> Result<JoinSplitProof, JsValue> {
+    check_arity(inputs.len(), outputs.len(), config)?;
+
+    let asset_id = resolve_asset_id(asset)?;
+    let (blinded_asset, asset_blinding) = BlindedAsset::prove(
+        &asset_id, &config.allowed_assets, &mut asset_transcript(&config.domain), rng,
+    )?;
+    let asset_generator = blinded_asset.blinded_generator()?;
+
+    let fee = parse_amount(&binding.fee, "fee")?;
+    let max_amount = range_proof_max(config)?;
+
+    let total_in = inputs.iter().try_fold(U256::ZERO, |acc, input| acc.checked_add(&U256::from(input.note.amount())))
+        .ok_or("Input amounts overflow")?;
+    let total_out = outputs.iter().try_fold(fee, |acc, note| acc.checked_add(&U256::from(note.amount())))
+        .ok_or("Output amounts overflow")?;
+    if total_in != total_out {
+        return Err(format!(
+            "Unbalanced join-split: inputs={} outputs+fee={}", total_in, total_out
+        ).into());
+    }
+
+    // V = v·A + r·H = v·A' + (r − v·ρ)·H
+    let blinding_over_asset = |note: &Note| note.value_blinding() - Fr::from(note.amount()) * asset_blinding;
+
+    let mut excess_blinding = Fr::zero();
+    let mut input_proofs = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        let value_commitment = input.note.value_commitment(&asset_id)?;
+        let (_, spend_key) = input.note.spend_key_in_domain(&asset_id, &config.domain)?;
+        let leaf = note_leaf(&(value_commitment + spend_key), None, &config.domain)?;
+        if input.merkle_proof.leaf_hash != leaf.commitment_hash {
+            return Err("Merkle proof leaf does not match input note".into());
+        }
+        excess_blinding += blinding_over_asset(&input.note);
+
+        input_proofs.push(JoinSplitInputProof {
+            nullifier: nullifier_from_spend_key(&spend_key, &config.domain),
+            commitment_hash: leaf.commitment_hash,
+            value_commitment: point_to_hex(&value_commitment),
+            spend_key: point_to_hex(&spend_key),
+            merkle_proof: input.merkle_proof.clone(),
+            merkle_root: input.merkle_proof.root.clone(),
+        });
+    }
+
+    let mut output_proofs = Vec::with_capacity(outputs.len());
+    for note in outputs {
+        let value_commitment = note.value_commitment(&asset_id)?;
+        let (_, spend_key) = note.spend_key_in_domain(&asset_id, &config.domain)?;
+        excess_blinding -= blinding_over_asset(note);
+
+        output_proofs.push(JoinSplitOutputProof {
+            commitment: note_leaf(&(value_commitment + spend_key), None, &config.domain)?,
+            value_commitment: point_to_hex(&value_commitment),
+            spend_key: point_to_hex(&spend_key),
+            range_proof: ValueRangeProof::default(),
+        });
+    }
+
+    let mut proof = JoinSplitProof {
+        inputs: input_proofs,
+        outputs: output_proofs,
+        binding: TransferBinding {
+            recipient: binding.recipient.clone(),
+            relayer: binding.relayer.clone(),
+            fee: fee.to_string(),
+        },
+        domain: config.domain.clone(),
+        asset: blinded_asset,
+        balance_proof: BalanceProof::default(),
+    };
+
+    let mut transcript = join_split_transcript(&proof)?;
+    for (output, note) in proof.outputs.iter_mut().zip(outputs) {
+        output.range_proof = ValueRangeProof::prove(
+            note.amount(), &blinding_over_asset(note), max_amount, &asset_generator, &mut transcript, rng,
+        )?;
+    }
+
+    let excess = balance_excess(&proof)?;
+    proof.balance_proof = BalanceProof::prove(&excess_blinding, &excess, &mut transcript, rng);
+
+    Ok(proof)
+}
+
+/// Verifica membresía, hojas, nullifiers, range proofs y la ecuación de balance
+pub fn verify_join_split(proof: &JoinSplitProof, config: &JoinSplitConfig) -> Result<bool, JsValue> {
+    if check_arity(proof.inputs.len(), proof.outputs.len(), config).is_err() {
+        return Ok(false);
+    }
+    if !proof.domain.matches(&config.domain) {
+        crate::console_log!("❌ Join-split de otro dominio: {}", proof.domain.chain_id);
+        return Ok(false);
+    }
+    if !proof.asset.verify(&config.allowed_assets, &mut asset_transcript(&proof.domain)).unwrap_or(false) {
+        crate::console_log!("❌ Join-split de un activo no permitido");
+        return Ok(false);
+    }
+    let max_amount = range_proof_max(config)?;
+
+    let mut seen_nullifiers = HashSet::new();
+    for input in &proof.inputs {
+        if !seen_nullifiers.insert(input.nullifier.to_lowercase()) {
+            crate::console_log!("❌ Nullifier duplicado en join-split: {}", input.nullifier);
+            return Ok(false);
+        }
+        let (value_commitment, spend_key) = match (point_from_hex(&input.value_commitment), point_from_hex(&input.spend_key)) {
+            (Ok(value_commitment), Ok(spend_key)) => (value_commitment, spend_key),
+            _ => return Ok(false),
+        };
+        let leaf = note_leaf(&(value_commitment + spend_key), None, &proof.domain)?;
+        if input.nullifier != nullifier_from_spend_key(&spend_key, &proof.domain)
+            || input.commitment_hash != leaf.commitment_hash
+        {
+            crate::console_log!("❌ Entrada de join-split con hoja o nullifier que no derivan de V + S");
+            return Ok(false);
+        }
+        if input.merkle_proof.leaf_hash != input.commitment_hash
+            || input.merkle_proof.root != input.merkle_root
+        {
+            return Ok(false);
+        }
+        let merkle_valid = verify_merkle_proof_with_index(
+            input.merkle_proof.proof_path.clone(),
+            &input.merkle_root,
+            &input.commitment_hash,
+            input.merkle_proof.leaf_index,
+        )?;
+        if !merkle_valid {
+            return Ok(false);
+        }
+    }
+
+    for output in &proof.outputs {
+        let leaf = match (point_from_hex(&output.value_commitment), point_from_hex(&output.spend_key)) {
+            (Ok(value_commitment), Ok(spend_key)) => note_leaf(&(value_commitment + spend_key), None, &proof.domain)?,
+            _ => return Ok(false),
+        };
+        if leaf.commitment_hash != output.commitment.commitment_hash || leaf.x != output.commitment.x || leaf.y != output.commitment.y {
+            crate::console_log!("❌ Salida de join-split cuya hoja no deriva de V + S");
+            return Ok(false);
+        }
+    }
+
+    let asset_generator = proof.asset.blinded_generator()?;
+    let mut transcript = match join_split_transcript(proof) {
+        Ok(transcript) => transcript,
+        Err(_) => return Ok(false),
+    };
+    for output in &proof.outputs {
+        let value_commitment = point_from_hex(&output.value_commitment)?;
+        if !output.range_proof.verify(&value_commitment, max_amount, &asset_generator, &mut transcript).unwrap_or(false) {
+            crate::console_log!("❌ Range proof de salida inválida");
+            return Ok(false);
+        }
+    }
+
+    let excess = match balance_excess(proof) {
+        Ok(excess) => excess,
+        Err(_) => return Ok(false),
+    };
+    Ok(proof.balance_proof.verify(&excess, &mut transcript).unwrap_or(false))
+}
+
+/// Genera una prueba join-split desde notas de backup (JSON para WASM)
+/// `asset` es el símbolo o la dirección del token de todas las notas
+#[wasm_bindgen]
+pub fn generate_join_split_proof(
+    input_notes: Vec<String>,
+    output_notes: Vec<String>,
+    asset: &str,
+    recipient: &str,
+    relayer: &str,
+    fee_wei: &str,
+    config: &JsValue,
+) -> Result<JsValue, JsValue> {
+    console_error_panic_hook::set_once();
+
+    let config = parse_join_split_config(config)?;
+    let binding = TransferBinding {
+        recipient: recipient.to_string(),
+        relayer: relayer.to_string(),
+        fee: fee_wei.to_string(),
+    };
+
+    crate::console_log!("🔀 Generando join-split {}x{} de {} (fee={} wei)", input_notes.len(), output_notes.len(), asset, fee_wei);
+
+    let asset_id = resolve_asset_id(asset)?;
+    let mut inputs = Vec::with_capacity(input_notes.len());
+    for encoded in &input_notes {
+        let note = Note::decode(encoded)?;
+        note.check_chain(&config.domain)?;
+        let leaf = note.leaf_in_domain(&asset_id, &config.domain)?;
+        let merkle_proof = build_anonymous_set_merkle_proof(&note.amount().to_string(), &leaf.commitment_hash)?;
+        inputs.push(JoinSplitInput { note, merkle_proof });
+    }
+
+    let outputs = output_notes
+        .iter()
+        .map(|encoded| {
+            let note = Note::decode(encoded)?;
+            note.check_chain(&config.domain)?;
+            Ok(note)
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    #[cfg(feature = "deterministic-rng")]
+    let mut rng = ProofRng::from_config_seed(config.rng_seed.as_deref())?;
+    #[cfg(not(feature = "deterministic-rng"))]
+    let mut rng = ProofRng::from_entropy();
+    let proof = prove_join_split(&inputs, &outputs, &asset_id, &binding, &config, &mut rng)?;
+
+    crate::console_log!("✅ Join-split generado");
+
+    let json_string = serde_json::to_string(&proof)
+        .map_err(|e| format!("Error serializing join-split proof: {}", e))?;
+    Ok(JsValue::from_str(&json_string))
+}
+
+/// Verifica una prueba join-split en JSON ligada al receptor, relayer y fee esperados
+#[wasm_bindgen]
+pub fn verify_join_split_proof(
+    proof_json: &str,
+    recipient: &str,
+    relayer: &str,
+    fee_wei: &str,
+    config: &JsValue,
+) -> Result<bool, JsValue> {
+    let proof: JoinSplitProof = serde_json::from_str(proof_json)
+        .map_err(|e| format!("Error parsing join-split proof: {}", e))?;
+    let config = parse_join_split_config(config)?;
+
+    if !proof.binding.matches(recipient, relayer, fee_wei) {
+        crate::console_log!("❌ Join-split ligado a otro receptor/relayer/fee");
+        return Ok(false);
+    }
+
+    let is_valid = verify_join_split(&proof, &config)?;
+    crate::console_log!("📋 Resultado verificación join-split: {}", is_valid);
+    Ok(is_valid)
+}
+
+fn parse_join_split_config(config: &JsValue) -> Result<JoinSplitConfig, JsValue> {
+    let config: JoinSplitConfig = match config.as_string() {
+        Some(config_json) => serde_json::from_str(&config_json)
+            .map_err(|e| format!("Invalid join-split config: {}", e))?,
+        None => JoinSplitConfig::default(),
+    };
+    check_allowed_assets(&config.allowed_assets)?;
+    Ok(config)
+}
+
+fn check_arity(num_inputs: usize, num_outputs: usize, config: &JoinSplitConfig) -> Result<(), String> {
+    if num_inputs == 0 || num_inputs > config.max_inputs as usize {
+        return Err(format!("Join-split supports 1..={} inputs, got {}", config.max_inputs, num_inputs));
+    }
+    if num_outputs == 0 || num_outputs > config.max_outputs as usize {
+        return Err(format!("Join-split supports 1..={} outputs, got {}", config.max_outputs, num_outputs));
+    }
+    Ok(())
+}
+
+/// excess = ΣV_in − ΣV_out − fee·A' (debe ser múltiplo de H si el balance cuadra)
+fn balance_excess(proof: &JoinSplitProof) -> Result<G1Projective, String> {
+    let fee = fee_scalar(&parse_amount(&proof.binding.fee, "fee")?)?;
+
+    let mut excess = G1Projective::zero();
+    for input in &proof.inputs {
+        excess += point_from_hex(&input.value_commitment)?;
+    }
+    for output in &proof.outputs {
+        excess -= point_from_hex(&output.value_commitment)?;
+    }
+    excess -= proof.asset.blinded_generator()? * fee;
+    Ok(excess)
+}
+
+/// El fee como escalar; uno fuera del campo daría el mismo exceso que fee − r
+fn fee_scalar(fee: &U256) -> Result<Fr, String> {
+    let bytes = fee.to_le_bytes();
+    let scalar = Fr::from_le_bytes_mod_order(&bytes);
+    if scalar.into_bigint().to_bytes_le() != bytes {
+        return Err(format!("Fee {} exceeds the scalar field", fee));
+    }
+    Ok(scalar)
+}
+
+/// Cota de las range proofs de salida: las notas guardan montos de 128 bits
+fn range_proof_max(config: &JoinSplitConfig) -> Result<u128, String> {
+    parse_amount(&config.max_amount, "max amount")?
+        .as_u128()
+        .ok_or_else(|| format!("Max amount {} exceeds 128 bits", config.max_amount))
+}
+
+/// Transcript con todas las entradas públicas de la declaración; el fee entra
+/// en decimal canónico, igual que lo interpreta la ecuación de balance
+fn join_split_transcript(proof: &JoinSplitProof) -> Result<Transcript, String> {
+    let mut transcript = Transcript::new(JOIN_SPLIT_PROTOCOL);
+    proof.domain.append_to(&mut transcript);
+    transcript.append_u64("num_inputs", proof.inputs.len() as u64);
+    transcript.append_u64("num_outputs", proof.outputs.len() as u64);
+    transcript.append_str("recipient", &normalize_felt_hex(&proof.binding.recipient));
+    transcript.append_str("relayer", &normalize_felt_hex(&proof.binding.relayer));
+    transcript.append_str("fee", &parse_amount(&proof.binding.fee, "fee")?.to_string());
+
+    transcript.append_str("asset_generator", &proof.asset.generator);
+    for (challenge, response) in proof.asset.surjection_proof.challenges.iter().zip(&proof.asset.surjection_proof.responses) {
+        transcript.append_str("asset_challenge", challenge);
+        transcript.append_str("asset_response", response);
+    }
+
+    for input in &proof.inputs {
+        transcript.append_str("input_nullifier", &input.nullifier);
+        transcript.append_str("input_merkle_root", &input.merkle_root);
+        transcript.append_str("input_commitment", &input.commitment_hash);
+        transcript.append_str("input_value_commitment", &input.value_commitment);
+        transcript.append_str("input_spend_key", &input.spend_key);
+    }
+    for output in &proof.outputs {
+        transcript.append_str("output_commitment", &output.commitment.commitment_hash);
+        transcript.append_str("output_value_commitment", &output.value_commitment);
+        transcript.append_str("output_spend_key", &output.spend_key);
+    }
+    Ok(transcript)
+}
+
+/// Transcript de la prueba de pertenencia del activo (ligado al dominio)
+fn asset_transcript(domain: &Domain) -> Transcript {
+    let mut transcript = Transcript::new(ASSET_SURJECTION_PROTOCOL);
+    domain.append_to(&mut transcript);
+    transcript
+}
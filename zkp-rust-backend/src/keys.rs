@@ -0,0 +1,206 @@
+use std::fmt;
+use wasm_bindgen::prelude::*;
+use bip39::Mnemonic;
+use rand::{CryptoRng, RngCore};
+use sha3::{Digest, Keccak256};
+use crate::{Note, ProofRng};
+
+const KDF_DOMAIN: &[u8] = b"ceaser-kdf-v1";
+const SPENDING_KEY_LABEL: &str = "spending_key";
+const VIEWING_KEY_LABEL: &str = "viewing_key";
+const NULLIFIER_KEY_LABEL: &str = "nullifier_key";
+const NOTE_SECRET_LABEL: &str = "note_secret";
+const NOTE_NULLIFIER_LABEL: &str = "note_nullifier";
+const NOTE_BLINDING_LABEL: &str = "note_blinding";
+
+/// Entropía de 256 bits = mnemónico de 24 palabras
+const MNEMONIC_ENTROPY_BYTES: usize = 32;
+
+pub const KEY_BYTES: usize = 32;
+
+/// KDF etiquetada: Keccak256(dominio || len(label) || label || key || index)
+pub fn labeled_kdf(key: &[u8], label: &str, index: u32) -> [u8; KEY_BYTES] {
+    let mut hasher = Keccak256::new();
+    hasher.update(KDF_DOMAIN);
+    hasher.update([label.len() as u8]);
+    hasher.update(label.as_bytes());
+    hasher.update(key);
+    hasher.update(index.to_le_bytes());
+
+    let mut out = [0u8; KEY_BYTES];
+    out.copy_from_slice(&hasher.finalize());
+    out
+}
+
+/// Genera un mnemónico BIP-39 nuevo usando la fuente de aleatoriedad indicada
+pub fn generate_mnemonic_with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> String {
+    let mut entropy = [0u8; MNEMONIC_ENTROPY_BYTES];
+    rng.fill_bytes(&mut entropy);
+    Mnemonic::from_entropy(&entropy)
+        .expect("32 bytes is a valid BIP-39 entropy length")
+        .to_string()
+}
+
+/// Genera un mnemónico BIP-39 de 24 palabras
+#[wasm_bindgen]
+pub fn generate_mnemonic() -> String {
+    generate_mnemonic_with_rng(&mut ProofRng::from_entropy())
+}
+
+/// Jerarquía de claves de CEASER derivada de una semilla maestra (BIP-39)
+///
+/// seed ──┬── spending_key  ── secret de cada nota
+///        ├── viewing_key   ── blinding de cada nota (se puede compartir sin autoridad de gasto)
+///        └── nullifier_key ── preimagen del nullifier de cada nota
+#[derive(Clone, PartialEq, Eq)]
+#[wasm_bindgen]
+pub struct KeyHierarchy {
+    spending_key: [u8; KEY_BYTES],
+    viewing_key: [u8; KEY_BYTES],
+    nullifier_key: [u8; KEY_BYTES],
+}
+
+/// Las claves nunca aparecen en logs ni en mensajes de error
+impl fmt::Debug for KeyHierarchy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyHierarchy")
+            .field("spending_key", &"<redacted>")
+            .field("viewing_key", &"<redacted>")
+            .field("nullifier_key", &"<redacted>")
+            .finish()
+    }
+}
+
+impl KeyHierarchy {
+    /// Deriva la jerarquía desde una semilla maestra arbitraria
+    pub fn from_seed(seed: &[u8]) -> KeyHierarchy {
+        KeyHierarchy {
+            spending_key: labeled_kdf(seed, SPENDING_KEY_LABEL, 0),
+            viewing_key: labeled_kdf(seed, VIEWING_KEY_LABEL, 0),
+            nullifier_key: labeled_kdf(seed, NULLIFIER_KEY_LABEL, 0),
+        }
+    }
+
+    /// Deriva la jerarquía desde un mnemónico BIP-39 y passphrase opcional
+    pub fn from_mnemonic_phrase(phrase: &str, passphrase: &str) -> Result<KeyHierarchy, String> {
+        let mnemonic = Mnemonic::parse(phrase)
+            .map_err(|e| format!("Invalid mnemonic: {}", e))?;
+        Ok(KeyHierarchy::from_seed(&mnemonic.to_seed(passphrase)))
+    }
+
+    pub fn spending_key_bytes(&self) -> &[u8; KEY_BYTES] {
+        &self.spending_key
+    }
+
+    pub fn viewing_key_bytes(&self) -> &[u8; KEY_BYTES] {
+        &self.viewing_key
+    }
+
+    pub fn nullifier_key_bytes(&self) -> &[u8; KEY_BYTES] {
+        &self.nullifier_key
+    }
+
+    /// Deriva la nota con índice `index` (restaurable desde el mnemónico)
+    pub fn note_at(&self, chain: &str, amount_wei: u128, index: u32) -> Result<Note, String> {
+        Note::from_parts(
+            chain,
+            amount_wei,
+            labeled_kdf(&self.spending_key, NOTE_SECRET_LABEL, index),
+            labeled_kdf(&self.nullifier_key, NOTE_NULLIFIER_LABEL, index),
+            self.viewing().blinding_at(index),
+        )
+    }
+}
+
+#[wasm_bindgen]
+impl KeyHierarchy {
+    /// Restaura la jerarquía de claves desde un mnemónico BIP-39
+    #[wasm_bindgen]
+    pub fn from_mnemonic(phrase: &str, passphrase: &str) -> Result<KeyHierarchy, JsValue> {
+        Ok(KeyHierarchy::from_mnemonic_phrase(phrase, passphrase)?)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn spending_key(&self) -> String {
+        format!("0x{}", hex::encode(self.spending_key))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn viewing_key(&self) -> String {
+        format!("0x{}", hex::encode(self.viewing_key))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn nullifier_key(&self) -> String {
+        format!("0x{}", hex::encode(self.nullifier_key))
+    }
+
+    /// Clave de visualización compartible (sin autoridad de gasto)
+    #[wasm_bindgen]
+    pub fn viewing(&self) -> ViewingKey {
+        ViewingKey { key: self.viewing_key }
+    }
+
+    /// Deriva la nota con índice `index` para la cadena y monto dados
+    #[wasm_bindgen]
+    pub fn derive_note(&self, chain: &str, amount_wei: &str, index: u32) -> Result<Note, JsValue> {
+        let amount = amount_wei.parse::<u128>()
+            .map_err(|e| format!("Invalid amount: {}", e))?;
+        Ok(self.note_at(chain, amount, index)?)
+    }
+}
+
+/// Clave de visualización: permite reconocer notas pero no gastarlas
+#[derive(Clone, PartialEq, Eq)]
+#[wasm_bindgen]
+pub struct ViewingKey {
+    key: [u8; KEY_BYTES],
+}
+
+impl fmt::Debug for ViewingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ViewingKey").field("key", &"<redacted>").finish()
+    }
+}
+
+impl ViewingKey {
+    pub fn from_bytes(key: [u8; KEY_BYTES]) -> ViewingKey {
+        ViewingKey { key }
+    }
+
+    pub fn as_bytes(&self) -> &[u8; KEY_BYTES] {
+        &self.key
+    }
+
+    /// Blinding de la nota con índice `index`
+    pub fn blinding_at(&self, index: u32) -> [u8; KEY_BYTES] {
+        labeled_kdf(&self.key, NOTE_BLINDING_LABEL, index)
+    }
+}
+
+#[wasm_bindgen]
+impl ViewingKey {
+    /// Importa una clave de visualización en hex
+    #[wasm_bindgen]
+    pub fn from_hex(key_hex: &str) -> Result<ViewingKey, JsValue> {
+        let bytes = hex::decode(key_hex.trim_start_matches("0x"))
+            .map_err(|e| format!("Invalid viewing key hex: {}", e))?;
+        if bytes.len() != KEY_BYTES {
+            return Err(format!("Viewing key must be {} bytes", KEY_BYTES).into());
+        }
+        let mut key = [0u8; KEY_BYTES];
+        key.copy_from_slice(&bytes);
+        Ok(ViewingKey { key })
+    }
+
+    #[wasm_bindgen]
+    pub fn to_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.key))
+    }
+
+    /// Blinding de la nota con índice `index` en hex
+    #[wasm_bindgen]
+    pub fn note_blinding(&self, index: u32) -> String {
+        format!("0x{}", hex::encode(self.blinding_at(index)))
+    }
+}
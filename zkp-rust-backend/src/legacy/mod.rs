@@ -13,5 +13,5 @@ pub use stwo_mock::*;
 pub fn warn_legacy_usage() {
     eprintln!("⚠️  WARNING: Using LEGACY/MOCK STWO implementation!");
     eprintln!("⚠️  This is NOT cryptographically secure!");
-    eprintln!("⚠️  For production, use --features real-stwo");
+    eprintln!("⚠️  For production, use --no-default-features --features real-stwo");
 }
@@ -1,7 +1,10 @@
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
-use crate::CircleStarkRangeProof;
+use crate::{
+    circle_low_degree_extension, fri_prove, fri_verify, parse_amount, CircleStarkRangeProof,
+    FriParams, FriProof, M31Element, ProofPhase, SecurityParams, Transcript, U256, time_phase,
+};
 
 /// Integración con STWO Prover para Circle STARKs
 /// Esta es una implementación mock que simula la funcionalidad de STWO
@@ -11,8 +14,13 @@ use crate::CircleStarkRangeProof;
 pub struct StwoCircuitConfig {
     pub field_size: u32,        // M31 field size
     pub circle_domain_size: u32, // Tamaño del dominio circular
-    pub fri_layers: u32,        // Número de capas FRI
+    pub fri_layers: u32,        // Número de capas FRI (plegados)
     pub blowup_factor: u32,     // Factor de expansión
+    pub fri_queries: u32,       // Consultas FRI
+    #[serde(default)]
+    pub pow_bits: u32,          // Bits de proof-of-work antes de las consultas
+    #[serde(default)]
+    pub log_last_layer_degree_bound: u32, // log2 del grado de la última capa FRI
 }
 
 impl Default for StwoCircuitConfig {
@@ -20,28 +28,72 @@ impl Default for StwoCircuitConfig {
         Self {
             field_size: 31,           // M31 field (2^31 - 1)
             circle_domain_size: 1024, // 2^10 evaluaciones
-            fri_layers: 10,
+            fri_layers: 8,            // log2(1024 / 4): la última capa queda constante
             blowup_factor: 4,
+            fri_queries: 20,
+            pow_bits: 10,
+            log_last_layer_degree_bound: 0,
         }
     }
 }
 
-/// Genera range proof usando STWO y Circle STARKs
+impl StwoCircuitConfig {
+    /// Parámetros de seguridad equivalentes (blowup, consultas, PoW, última capa)
+    pub fn security_params(&self) -> SecurityParams {
+        SecurityParams {
+            log_blowup_factor: self.blowup_factor.trailing_zeros(),
+            n_queries: self.fri_queries,
+            pow_bits: self.pow_bits,
+            log_last_layer_degree_bound: self.log_last_layer_degree_bound,
+        }
+    }
+
+    /// log2 de las filas de la traza (dominio / blowup)
+    pub fn log_trace_size(&self) -> u32 {
+        (self.circle_domain_size / self.blowup_factor).trailing_zeros()
+    }
+
+    /// Parámetros FRI derivados; `fri_layers` debe coincidir con
+    /// log2(dominio / blowup) − log_last_layer_degree_bound
+    fn fri_params(&self) -> Result<FriParams, String> {
+        if !self.circle_domain_size.is_power_of_two() || !self.blowup_factor.is_power_of_two() {
+            return Err("Domain size and blowup factor must be powers of two".to_string());
+        }
+        let params = self.security_params().fri_params(self.log_trace_size())?;
+        if params.num_folds()? != self.fri_layers as usize {
+            return Err(format!(
+                "fri_layers {} does not match log2({} / {}) - {}",
+                self.fri_layers, self.circle_domain_size, self.blowup_factor, self.log_last_layer_degree_bound
+            ));
+        }
+        Ok(params)
+    }
+}
+
+/// Transcript del range proof ligado a sus entradas públicas
+fn range_proof_transcript(public_inputs: &[String]) -> Transcript {
+    let mut transcript = Transcript::new("ceaser-stwo-mock-range-v1");
+    for input in public_inputs {
+        transcript.append_str("public_input", input);
+    }
+    transcript
+}
+
+/// Genera range proof usando STWO y Circle STARKs, ligada al commitment del
+/// monto: su hash es la última entrada pública y entra en el transcript de FRI
 #[wasm_bindgen]
 pub fn generate_stwo_range_proof(
     amount_wei: &str,
     nonce: &str,
     min_amount: &str,
     max_amount: &str,
+    commitment_hash: &str,
 ) -> Result<CircleStarkRangeProof, JsValue> {
     console_log!("🔄 Generando range proof con STWO...");
     
-    let amount = amount_wei.parse::<u128>()
-        .map_err(|e| format!("Invalid amount: {}", e))?;
-    let min = min_amount.parse::<u128>()
-        .map_err(|e| format!("Invalid min amount: {}", e))?;
-    let max = max_amount.parse::<u128>()
-        .map_err(|e| format!("Invalid max amount: {}", e))?;
+    let amount = parse_amount(amount_wei, "amount")?;
+    let min = parse_amount(min_amount, "min amount")?;
+    let max = parse_amount(max_amount, "max amount")?;
     
     // Verificar rango
     if amount < min || amount > max {
@@ -53,24 +105,31 @@ pub fn generate_stwo_range_proof(
     console_log!("⚙️ Config STWO: field_size={}, domain_size={}", 
                  config.field_size, config.circle_domain_size);
     
-    // 1. Generar evaluaciones en el círculo unitario
-    let circle_evaluations = generate_circle_evaluations(amount, &config)?;
-    
-    // 2. Generar commitments FRI
-    let fri_commitments = generate_fri_commitments(&circle_evaluations, &config)?;
-    
-    // 3. Generar proof data principal
-    let proof_data = generate_stwo_proof_data(amount, nonce, &config)?;
-    
-    // 4. Public inputs (solo min/max, amount permanece oculto)
+    // 1. Public inputs (min/max y commitment, amount permanece oculto)
     let public_inputs = vec![
         format!("0x{:032x}", min),
         format!("0x{:032x}", max),
         format!("0x{:08x}", config.field_size),
         format!("0x{:08x}", config.circle_domain_size),
+        commitment_hash.to_string(),
     ];
     
-    console_log!("✅ Range proof STWO generado: {} evaluaciones, {} FRI commits", 
+    // 2. Generar evaluaciones en el dominio circular (LDE de la traza)
+    let params = config.fri_params()?;
+    let evaluations = time_phase(ProofPhase::TraceGeneration, || {
+        generate_circle_evaluations(amount, min, max, &config)
+    })?;
+    
+    // 3. FRI: commit por capa, plegado con α y consultas del transcript
+    let mut transcript = range_proof_transcript(&public_inputs);
+    let fri_proof = fri_prove(&evaluations, &params, &mut transcript)?;
+    let fri_commitments = fri_proof.layer_roots.clone();
+    let circle_evaluations = opened_evaluations(&fri_proof);
+    
+    // 4. Generar proof data principal
+    let proof_data = generate_stwo_proof_data(amount, nonce, &config)?;
+    
+    console_log!("✅ Range proof STWO generado: {} evaluaciones abiertas, {} FRI commits", 
                  circle_evaluations.len(), fri_commitments.len());
     
     Ok(CircleStarkRangeProof {
@@ -78,84 +137,51 @@ pub fn generate_stwo_range_proof(
         public_inputs,
         circle_evaluations,
         fri_commitments,
+        fri_proof: Some(fri_proof),
     })
 }
 
-/// Genera evaluaciones en el círculo unitario para Circle STARKs
+/// Genera evaluaciones en el dominio circular para Circle STARKs.
+/// Traza: 128 bits de (amount − min) seguidos de 128 bits de (max − amount),
+/// interpolada y extendida con el blowup. Sin AIR, FRI solo garantiza que la
+/// función es de bajo grado, no que la traza sea binaria
 fn generate_circle_evaluations(
-    amount: u128,
+    amount: U256,
+    min: U256,
+    max: U256,
     config: &StwoCircuitConfig,
-) -> Result<Vec<String>, JsValue> {
-    let mut evaluations = Vec::new();
-    
-    // Simular evaluaciones en puntos del círculo unitario
-    for i in 0..config.circle_domain_size {
-        // Simular evaluación del polinomio en punto circular
-        let angle = (i as f64 * 2.0 * std::f64::consts::PI) / config.circle_domain_size as f64;
-        let x = (angle.cos() * 1000000.0) as u64; // Escalar para enteros
-        let y = (angle.sin() * 1000000.0) as u64;
-        
-        // Evaluar constraint: amount está en rango
-        let constraint_eval = if amount >= 1000 && amount <= 1000000000000000000000 {
-            (x.wrapping_mul(y).wrapping_add(amount as u64)) % ((1u64 << 31) - 1)
-        } else {
-            0
-        };
-        
-        evaluations.push(format!("0x{:016x}", constraint_eval));
+) -> Result<Vec<M31Element>, JsValue> {
+    let trace_size = (config.circle_domain_size / config.blowup_factor) as usize;
+    if trace_size < 256 {
+        return Err(format!("Trace of {} rows cannot hold 256 range bits", trace_size).into());
     }
     
-    Ok(evaluations)
-}
-
-/// Genera commitments FRI para la prueba STARK
-fn generate_fri_commitments(
-    evaluations: &[String],
-    config: &StwoCircuitConfig,
-) -> Result<Vec<String>, JsValue> {
-    let mut commitments = Vec::new();
-    
-    // Simular proceso FRI con múltiples capas
-    let mut current_layer = evaluations.to_vec();
-    
-    for layer in 0..config.fri_layers {
-        // Simular fold de la capa actual
-        let mut next_layer = Vec::new();
-        
-        for chunk in current_layer.chunks(2) {
-            if chunk.len() == 2 {
-                // Combinar dos evaluaciones usando hash
-                let mut hasher = Keccak256::new();
-                hasher.update(chunk[0].as_bytes());
-                hasher.update(chunk[1].as_bytes());
-                hasher.update(&layer.to_le_bytes());
-                let combined = hasher.finalize();
-                next_layer.push(format!("0x{}", hex::encode(&combined[..8])));
-            }
-        }
-        
-        // Generar commitment para esta capa
-        let mut commitment_hasher = Keccak256::new();
-        for eval in &next_layer {
-            commitment_hasher.update(eval.as_bytes());
-        }
-        let commitment = commitment_hasher.finalize();
-        commitments.push(format!("0x{}", hex::encode(commitment)));
-        
-        current_layer = next_layer;
-        
-        // Parar si la capa es muy pequeña
-        if current_layer.len() <= 1 {
-            break;
-        }
+    let mut trace = Vec::with_capacity(trace_size);
+    for value in [amount.checked_sub(&min), max.checked_sub(&amount)] {
+        // La traza tiene 128 filas por diferencia: el rango debe caber en 2^128
+        let value = value
+            .filter(|value| value.as_u128().is_some())
+            .ok_or("Range proof supports ranges up to 2^128 wei")?;
+        trace.extend((0..128).map(|bit| M31Element::new(value.bit(bit) as u32)));
     }
+    trace.resize(trace_size, M31Element::new(0));
     
-    Ok(commitments)
+    Ok(circle_low_degree_extension(&trace, config.blowup_factor.trailing_zeros())?)
+}
+
+/// Evaluaciones de la capa 0 abiertas por las consultas FRI
+fn opened_evaluations(fri_proof: &FriProof) -> Vec<String> {
+    fri_proof
+        .queries
+        .iter()
+        .filter_map(|query| query.layers.first())
+        .flat_map(|decommitment| decommitment.values.iter().cloned())
+        .collect()
 }
 
 /// Genera los datos principales de la prueba STWO
 fn generate_stwo_proof_data(
-    amount: u128,
+    amount: U256,
     nonce: &str,
     config: &StwoCircuitConfig,
 ) -> Result<Vec<String>, JsValue> {
@@ -212,11 +238,26 @@ pub fn verify_stwo_range_proof(proof: &CircleStarkRangeProof) -> Result<bool, Js
         }
     }
     
+    // Verificar FRI con los parámetros del verificador (no los del prover)
+    let config = StwoCircuitConfig::default();
+    let fri_valid = match &proof.fri_proof {
+        Some(fri_proof) if has_public_inputs => {
+            let params = config.fri_params()?;
+            let domain_matches = proof.public_inputs[3] == format!("0x{:08x}", config.circle_domain_size);
+            let mut transcript = range_proof_transcript(&proof.public_inputs);
+            domain_matches
+                && proof.fri_commitments == fri_proof.layer_roots
+                && proof.circle_evaluations == opened_evaluations(fri_proof)
+                && fri_verify(fri_proof, &params, &mut transcript)?
+        }
+        _ => false,
+    };
+    
     let is_valid = has_circle_evaluations && has_fri_commitments && 
-                   has_proof_data && has_public_inputs && inputs_valid;
+                   has_proof_data && has_public_inputs && inputs_valid && fri_valid;
     
-    console_log!("📊 Verificación STWO: evaluaciones={}, FRI={}, proof_data={}, inputs={}, válido={}", 
-                 has_circle_evaluations, has_fri_commitments, has_proof_data, has_public_inputs, is_valid);
+    console_log!("📊 Verificación STWO: evaluaciones={}, FRI={}, proof_data={}, inputs={}, fri_ok={}, válido={}", 
+                 has_circle_evaluations, has_fri_commitments, has_proof_data, has_public_inputs, fri_valid, is_valid);
     
     Ok(is_valid)
 }
@@ -224,6 +265,7 @@ pub fn verify_stwo_range_proof(proof: &CircleStarkRangeProof) -> Result<bool, Js
 /// Obtiene información sobre las capacidades de STWO
 #[wasm_bindgen]
 pub fn get_stwo_info() -> JsValue {
+    let config = StwoCircuitConfig::default();
     let info = serde_json::json!({
         "name": "STWO Circle STARKs",
         "field": "M31 (2^31 - 1)",
@@ -232,14 +274,15 @@ pub fn get_stwo_info() -> JsValue {
         "features": [
             "Range proofs",
             "Circle polynomial evaluation",
-            "FRI commitment scheme",
+            "FRI commitment scheme (fold + Merkle + queries)",
             "M31 field arithmetic"
         ],
         "performance": {
             "proof_generation": "~1-3 seconds",
             "proof_size": "~1-2 KB",
             "verification": "~10-50 ms"
-        }
+        },
+        "security": config.security_params().estimate(config.log_trace_size()).ok()
     });
     
     JsValue::from_str(&info.to_string())
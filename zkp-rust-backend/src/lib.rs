@@ -1,36 +1,84 @@
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use ark_bn254::G1Projective;
+use crate::value_commitment::{nullifier_generator, point_from_hex, SpendSignature};
 
-// Conditional imports based on features
+// Conditional imports based on features (real-stwo wins over the default mock)
 #[cfg(feature = "real-stwo")]
 use crate::production::*;
 
-#[cfg(feature = "mock-stwo")]
+#[cfg(all(feature = "mock-stwo", not(feature = "real-stwo")))]
 use crate::legacy::*;
 
 // Core modules (always available)
 mod zkp_generator;
+mod u256;
+mod token;
 mod field_arithmetic;
+mod circle_fft;
+mod fri;
+mod stark_proof;
+mod security;
+mod perf_stats;
+mod parallel;
+mod proof_rng;
+mod proof_job;
 mod merkle_tree;
+mod incremental_merkle;
+mod note;
+mod keys;
+mod note_encryption;
+mod transcript;
+pub mod value_commitment;
+mod join_split;
+mod domain;
+mod nullifier_set;
+mod verification;
+mod deposit;
+mod withdraw;
+mod denomination;
+mod asset;
 
 // Production vs Legacy modules
 #[cfg(feature = "real-stwo")]
 mod production;
 
-#[cfg(feature = "mock-stwo")]
+#[cfg(all(feature = "mock-stwo", not(feature = "real-stwo")))]
 mod legacy;
 
 // Public exports
 pub use zkp_generator::*;
+pub use u256::*;
+pub use token::*;
 pub use field_arithmetic::*;
+pub use circle_fft::*;
+pub use fri::*;
+pub use stark_proof::*;
+pub use security::*;
+pub use perf_stats::*;
+pub use parallel::*;
+pub use proof_rng::*;
+pub use proof_job::*;
 pub use merkle_tree::*;
+pub use incremental_merkle::*;
+pub use note::*;
+pub use keys::*;
+pub use note_encryption::*;
+pub use transcript::*;
+pub use join_split::*;
+pub use domain::*;
+pub use nullifier_set::*;
+pub use verification::*;
+pub use deposit::*;
+pub use withdraw::*;
+pub use denomination::*;
+pub use asset::*;
 
 // Conditional exports based on features
 #[cfg(feature = "real-stwo")]
 pub use production::*;
 
-#[cfg(feature = "mock-stwo")]
+#[cfg(all(feature = "mock-stwo", not(feature = "real-stwo")))]
 pub use legacy::*;
 
 #[macro_export]
@@ -38,12 +86,23 @@ macro_rules! console_log {
     ($($t:tt)*) => (crate::log(&format_args!($($t)*).to_string()))
 }
 
+#[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = console)]
     fn log(s: &str);
 }
 
+/// Fuera del navegador (CLI, tests, benchmarks) los logs van a stderr solo con
+/// `CEASER_LOG` definida, para no medir la escritura en consola
+#[cfg(not(target_arch = "wasm32"))]
+fn log(s: &str) {
+    static ENABLED: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    if *ENABLED.get_or_init(|| std::env::var_os("CEASER_LOG").is_some()) {
+        eprintln!("{}", s);
+    }
+}
+
 /// Estructura para representar un commitment de Pedersen
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[wasm_bindgen(getter_with_clone)]
@@ -61,6 +120,9 @@ pub struct CircleStarkRangeProof {
     pub public_inputs: Vec<String>,
     pub circle_evaluations: Vec<String>,
     pub fri_commitments: Vec<String>,
+    #[wasm_bindgen(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fri_proof: Option<FriProof>,
 }
 
 /// Estructura principal para las pruebas ZK de CEASER
@@ -73,6 +135,109 @@ pub struct CeaserZKProof {
     pub merkle_proof: MerkleProof,
     pub merkle_root: String,
     pub encrypted_metadata: String,
+    #[serde(default)]
+    pub binding: TransferBinding,
+    #[serde(default)]
+    pub statement_hash: String,
+    /// Las pruebas anteriores al campo son v0
+    #[serde(default = "Domain::legacy")]
+    pub domain: Domain,
+    #[serde(default)]
+    pub denomination_id: Option<u32>,
+    /// Firma de la declaración con la clave de gasto (obligatoria desde v1)
+    #[wasm_bindgen(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spend_signature: Option<SpendSignature>,
+    /// Punto N de la nota gastada (desde v1): la hoja del árbol es `note_leaf(N)`,
+    /// la misma que publica el depósito
+    #[wasm_bindgen(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note_commitment: Option<String>,
+}
+
+/// Entradas públicas que fijan a quién y cómo se paga (anti front-running)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[wasm_bindgen(getter_with_clone)]
+pub struct TransferBinding {
+    pub recipient: String,
+    pub relayer: String,
+    pub fee: String, // En wei
+}
+
+impl TransferBinding {
+    /// Compara contra los valores esperados normalizando direcciones felt y el fee como u256
+    pub fn matches(&self, recipient: &str, relayer: &str, fee: &str) -> bool {
+        let fees_match = match (U256::parse(&self.fee), U256::parse(fee)) {
+            (Ok(bound), Ok(expected)) => bound == expected,
+            _ => false,
+        };
+        normalize_felt_hex(&self.recipient) == normalize_felt_hex(recipient)
+            && normalize_felt_hex(&self.relayer) == normalize_felt_hex(relayer)
+            && fees_match
+    }
+
+    /// Fee en decimal canónico ("0x10" y "16" son el mismo fee)
+    fn normalized_fee(&self) -> String {
+        U256::parse(&self.fee).map_or_else(|_| self.fee.trim().to_string(), |fee| fee.to_string())
+    }
+}
+
+/// Normaliza una dirección felt hex: minúsculas y sin ceros a la izquierda
+pub fn normalize_felt_hex(value: &str) -> String {
+    let trimmed = value.trim().to_lowercase();
+    let digits = trimmed.trim_start_matches("0x").trim_start_matches('0');
+    format!("0x{}", if digits.is_empty() { "0" } else { digits })
+}
+
+/// Hash de la declaración: liga commitment, nullifier, raíz y binding vía transcript
+pub fn compute_statement_hash(proof: &CeaserZKProof) -> String {
+    let mut transcript = Transcript::new("ceaser-transfer-statement-v1");
+    proof.domain.append_to(&mut transcript);
+    transcript.append_str("commitment", &proof.amount_commitment.commitment_hash);
+    transcript.append_str("nullifier", &proof.nullifier);
+    transcript.append_str("merkle_root", &proof.merkle_root);
+    for input in &proof.range_proof.public_inputs {
+        transcript.append_str("range_public_input", input);
+    }
+    transcript.append_str("recipient", &normalize_felt_hex(&proof.binding.recipient));
+    transcript.append_str("relayer", &normalize_felt_hex(&proof.binding.relayer));
+    transcript.append_str("fee", &proof.binding.normalized_fee());
+    if let Some(denomination_id) = proof.denomination_id {
+        transcript.append_u64("denomination_id", denomination_id as u64);
+    }
+    if let Some(note_commitment) = &proof.note_commitment {
+        transcript.append_str("note_commitment", note_commitment);
+    }
+    format!("0x{}", hex::encode(transcript.challenge_bytes("statement")))
+}
+
+/// Hoja del árbol que gasta la prueba: en v0 el hash del commitment, desde v1
+/// `note_leaf(N)` como cualquier otra nota del pool
+pub fn spent_leaf_hash(proof: &CeaserZKProof) -> Result<String, String> {
+    if proof.domain.is_legacy() {
+        return Ok(proof.amount_commitment.commitment_hash.clone());
+    }
+    let note_commitment = proof.note_commitment.as_deref().ok_or("Missing note commitment")?;
+    let leaf = note_leaf(&point_from_hex(note_commitment)?, proof.denomination_id, &proof.domain)?;
+    Ok(leaf.commitment_hash)
+}
+
+/// Fija la declaración: calcula el hash y, desde v1, lo firma con la clave de gasto
+/// derivada del secreto, así que reescribir el binding invalida la prueba
+pub fn sign_statement(proof: &mut CeaserZKProof, user_secret: &str) -> Result<(), String> {
+    proof.statement_hash = compute_statement_hash(proof);
+    proof.spend_signature = None;
+    if proof.domain.is_legacy() {
+        return Ok(());
+    }
+
+    let (secret, spend_key) = spend_key_for_domain(&proof.amount_commitment.commitment_hash, user_secret, &proof.domain)?;
+    if nullifier_from_spend_key(&spend_key, &proof.domain) != proof.nullifier {
+        return Err("Nullifier was not derived from this secret".to_string());
+    }
+    let generator = G1Projective::from(nullifier_generator(&proof.domain.separator()));
+    proof.spend_signature = Some(SpendSignature::sign(&secret, &generator, &proof.statement_hash));
+    Ok(())
 }
 
 /// Configuración para generación de pruebas ZK
@@ -82,6 +247,82 @@ pub struct ZKProofConfig {
     pub max_amount: String,  // En wei
     pub merkle_tree_height: u32,
     pub use_stwo: bool,
+    #[serde(default)]
+    pub domain: Domain,
+    #[serde(default)]
+    pub amount_mode: AmountMode,
+    /// Límites en unidades del token; si está presente reemplaza min/max en wei
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_bounds: Option<TokenBounds>,
+    /// Activo del pool (símbolo o dirección); si está presente el commitment lo liga
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset: Option<String>,
+    /// Direcciones de activos aceptados (por defecto todo el registro; no puede estar vacía)
+    #[serde(default = "default_allowed_assets")]
+    pub allowed_assets: Vec<String>,
+    /// Parámetros del commitment scheme de la range proof (None = `SecurityParams::default()`);
+    /// el verificador debe usar los mismos que el prover
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security: Option<SecurityParams>,
+}
+
+/// Límites del pool expresados en el token ("0.001" a "1000" STRK)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenBounds {
+    pub symbol: String,
+    pub min: String,
+    pub max: String,
+}
+
+impl ZKProofConfig {
+    /// Config con los límites por defecto del token del registro
+    pub fn for_token(symbol: &str, registry: &TokenRegistry) -> Result<ZKProofConfig, String> {
+        let token = registry.get(symbol)?;
+        let mut config = ZKProofConfig {
+            token_bounds: Some(TokenBounds {
+                symbol: token.symbol.clone(),
+                min: token.default_min.clone(),
+                max: token.default_max.clone(),
+            }),
+            ..ZKProofConfig::default()
+        };
+        config.resolve_token_bounds(registry)?;
+        Ok(config)
+    }
+
+    /// Convierte `token_bounds` a min/max en unidades mínimas (idempotente)
+    pub fn resolve_token_bounds(&mut self, registry: &TokenRegistry) -> Result<(), String> {
+        if let Some(bounds) = &self.token_bounds {
+            let token = registry.get(&bounds.symbol)?;
+            let min = TokenAmount::parse(token, &bounds.min)?.raw;
+            let max = TokenAmount::parse(token, &bounds.max)?.raw;
+            if min > max {
+                return Err(format!("{} bounds are inverted: {} > {}", token.symbol, bounds.min, bounds.max));
+            }
+            self.min_amount = min.to_string();
+            self.max_amount = max.to_string();
+        }
+        Ok(())
+    }
+
+    /// Límites que debe declarar la range proof: los del pool en modo rango y
+    /// [d, d] en modo denominación, así el monto queda fijado a su denominación
+    pub fn range_bounds(&self, denomination_id: Option<u32>) -> Result<(String, String), String> {
+        match (&self.amount_mode, denomination_id) {
+            (AmountMode::Range, None) => Ok((self.min_amount.clone(), self.max_amount.clone())),
+            (AmountMode::Range, Some(id)) => Err(format!("Range pool does not accept denomination ID {}", id)),
+            (mode, Some(id)) => mode
+                .denomination_amount(id)
+                .map(|amount| (amount.to_string(), amount.to_string()))
+                .ok_or_else(|| format!("Unknown denomination ID {}", id)),
+            (_, None) => Err("Denomination pool requires a denomination ID".to_string()),
+        }
+    }
+
+    /// Parámetros de seguridad con que se generan y verifican las range proofs del pool
+    pub fn security_params(&self) -> SecurityParams {
+        self.security.unwrap_or_default()
+    }
 }
 
 impl Default for ZKProofConfig {
@@ -91,10 +332,29 @@ impl Default for ZKProofConfig {
             max_amount: "1000000000000000000000".to_string(), // 1000 STRK
             merkle_tree_height: 20, // Soporte para ~1M operaciones
             use_stwo: true,
+            domain: Domain::default(),
+            amount_mode: AmountMode::Range,
+            token_bounds: None,
+            asset: None,
+            allowed_assets: default_allowed_assets(),
+            security: None,
         }
     }
 }
 
+/// Deserializa la configuración (JSON opcional, si no se usa la de por defecto)
+pub(crate) fn parse_proof_config(config: &JsValue) -> Result<ZKProofConfig, JsValue> {
+    let mut config: ZKProofConfig = match config.as_string() {
+        Some(config_json) => serde_json::from_str(&config_json)
+            .map_err(|e| format!("Invalid config: {}", e))?,
+        None => ZKProofConfig::default(),
+    };
+    config.resolve_token_bounds(&TokenRegistry::default())?;
+    config.security_params().validate()?;
+    check_allowed_assets(&config.allowed_assets)?;
+    Ok(config)
+}
+
 /// Función principal para generar pruebas ZK de CEASER
 #[wasm_bindgen]
 pub fn generate_ceaser_zk_proof(
@@ -102,7 +362,23 @@ pub fn generate_ceaser_zk_proof(
     nonce: &str,
     user_secret: &str,
     receiver_address: &str,
-    _config: &JsValue,
+    config: &JsValue,
+) -> Result<JsValue, JsValue> {
+    generate_ceaser_zk_proof_with_relayer(
+        amount_wei, nonce, user_secret, receiver_address, "0x0", "0", config
+    )
+}
+
+/// Genera la prueba ZK ligando receptor, relayer y fee a la declaración pública
+#[wasm_bindgen]
+pub fn generate_ceaser_zk_proof_with_relayer(
+    amount_wei: &str,
+    nonce: &str,
+    user_secret: &str,
+    receiver_address: &str,
+    relayer_address: &str,
+    fee_wei: &str,
+    config: &JsValue,
 ) -> Result<JsValue, JsValue> {
     console_error_panic_hook::set_once();
     
@@ -113,35 +389,121 @@ pub fn generate_ceaser_zk_proof(
     #[cfg(feature = "real-stwo")]
     confirm_production_usage();
     
-    #[cfg(feature = "mock-stwo")]
+    #[cfg(all(feature = "mock-stwo", not(feature = "real-stwo")))]
     warn_legacy_usage();
     
-    // Deserializar configuración
-    let config: ZKProofConfig = ZKProofConfig::default();
+    let config = parse_proof_config(config)?;
     
-    console_log!("⚙️ Config: min={}, max={}, height={}", 
-                 config.min_amount, config.max_amount, config.merkle_tree_height);
+    console_log!("⚙️ Config: min={}, max={}, height={}, chain={}", 
+                 config.min_amount, config.max_amount, config.merkle_tree_height, config.domain.chain_id);
     
-    // 1. Generar commitment de Pedersen
-    console_log!("🔐 Generando commitment de Pedersen...");
-    let commitment = generate_pedersen_commitment(amount_wei, nonce)?;
+    // Commitment, range proof, nullifier, merkle proof, metadata y serialización
+    let mut job = CeaserProofJob::new(
+        amount_wei, nonce, user_secret, receiver_address, relayer_address, fee_wei, config
+    )?;
+    while job.run_stage()?.is_some() {}
     
-    // 2. Generar prueba de rango con STWO (REAL o MOCK según feature)
-    console_log!("📊 Generando range proof...");
+    console_log!("✅ Prueba ZK generada exitosamente");
+    Ok(JsValue::from_str(&job.finish()?))
+}
+
+/// Genera una prueba ZK de CEASER a partir de una nota de backup; la cadena
+/// de la nota debe ser la del dominio del config
+#[wasm_bindgen]
+pub fn generate_ceaser_zk_proof_from_note(
+    note: &str,
+    receiver_address: &str,
+    config: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let note = Note::decode(note)?;
+    note.check_chain(&parse_proof_config(config)?.domain)?;
+    
+    generate_ceaser_zk_proof(
+        &note.amount().to_string(),
+        &note.commitment_nonce(),
+        &note.nullifier_secret(),
+        receiver_address,
+        config,
+    )
+}
+
+/// Función para verificar una prueba ZK (para testing, dominio por defecto)
+#[wasm_bindgen]
+pub fn verify_ceaser_zk_proof(proof_json: &str) -> Result<bool, JsValue> {
+    verify_ceaser_zk_proof_in_domain(proof_json, &Domain::default())
+}
+
+/// Verifica una prueba ZK exigiendo que pertenezca al dominio esperado
+#[wasm_bindgen]
+pub fn verify_ceaser_zk_proof_in_domain(proof_json: &str, domain: &Domain) -> Result<bool, JsValue> {
+    let proof: CeaserZKProof = serde_json::from_str(proof_json)
+        .map_err(|e| format!("Error parsing proof: {}", e))?;
     
+    console_log!("🔍 Verificando prueba ZK...");
+    
+    let report = build_verification_report(&proof, domain, None)?;
+    
+    console_log!("📋 Resultado verificación: valid={}, failure={:?}", report.valid, report.failure);
+    
+    Ok(report.valid)
+}
+
+/// Verifica la prueba contra el config del pool (dominio y modo de montos)
+#[wasm_bindgen]
+pub fn verify_ceaser_zk_proof_with_config(proof_json: &str, config: &JsValue) -> Result<bool, JsValue> {
+    let proof: CeaserZKProof = serde_json::from_str(proof_json)
+        .map_err(|e| format!("Error parsing proof: {}", e))?;
+    let config = parse_proof_config(config)?;
+    
+    let report = build_verification_report_for_config(&proof, &config, None)?;
+    console_log!("📋 Resultado verificación: valid={}, failure={:?}", report.valid, report.failure);
+    
+    Ok(report.valid)
+}
+
+/// Verifica la prueba y que esté ligada al receptor, relayer y fee esperados
+#[wasm_bindgen]
+pub fn verify_ceaser_zk_proof_for_recipient(
+    proof_json: &str,
+    recipient: &str,
+    relayer: &str,
+    fee_wei: &str,
+) -> Result<bool, JsValue> {
+    let proof: CeaserZKProof = serde_json::from_str(proof_json)
+        .map_err(|e| format!("Error parsing proof: {}", e))?;
+    
+    if !proof.binding.matches(recipient, relayer, fee_wei) {
+        console_log!("❌ La prueba está ligada a otro receptor/relayer/fee");
+        return Ok(false);
+    }
+    
+    verify_ceaser_zk_proof(proof_json)
+}
+
+/// Genera la range proof con el backend activo (STWO real o mock), ligada al commitment
+pub(crate) fn generate_backend_range_proof(
+    amount_wei: &str,
+    nonce: &str,
+    min_amount: &str,
+    max_amount: &str,
+    commitment_hash: &str,
+    security_params: &SecurityParams,
+) -> Result<CircleStarkRangeProof, JsValue> {
     #[cfg(feature = "real-stwo")]
-    let real_proof = generate_real_stwo_range_proof(
-        amount_wei, nonce, &config.min_amount, &config.max_amount
+    let real_proof = generate_real_stwo_range_proof_with_params(
+        amount_wei, nonce, min_amount, max_amount, commitment_hash, security_params
     )?;
     
     // En modo real, convertir real proof a estructura compatible
     #[cfg(feature = "real-stwo")]
     let range_proof = convert_real_to_compatible_proof(&real_proof)?;
     
-    // En modo mock, generar proof mock
-    #[cfg(feature = "mock-stwo")]
+    // En modo mock, generar proof mock (con su propio StwoCircuitConfig)
+    #[cfg(all(feature = "mock-stwo", not(feature = "real-stwo")))]
+    let _ = security_params;
+    #[cfg(all(feature = "mock-stwo", not(feature = "real-stwo")))]
     let range_proof = generate_stwo_range_proof(
-        amount_wei, nonce, &config.min_amount, &config.max_amount
+        amount_wei, nonce, min_amount, max_amount, commitment_hash
     )?;
     
     #[cfg(feature = "real-stwo")]
@@ -150,109 +512,69 @@ pub fn generate_ceaser_zk_proof(
     #[cfg(not(any(feature = "real-stwo", feature = "mock-stwo")))]
     compile_error!("Must enable either 'real-stwo' or 'mock-stwo' feature");
     
-    // 3. Generar nullifier único
-    console_log!("🔑 Generando nullifier...");
-    let nullifier = generate_nullifier(&commitment.commitment_hash, user_secret)?;
+    Ok(range_proof)
+}
+
+/// Verifica la range proof con el verificador del backend activo y los parámetros del pool
+pub(crate) fn verify_backend_range_proof(
+    range_proof: &CircleStarkRangeProof,
+    security_params: &SecurityParams,
+) -> Result<bool, JsValue> {
+    // En modo real solo cuenta la prueba STWO serializada que acepta su verificador
+    #[cfg(feature = "real-stwo")]
+    let range_valid = {
+        console_log!("🔍 Attempting REAL STWO verification...");
+        match verify_stwo_range_air_proof(range_proof, security_params) {
+            Ok(valid) => valid,
+            Err(e) => {
+                console_log!("❌ Invalid STWO range proof: {}", e);
+                false
+            }
+        }
+    };
+    
+    #[cfg(all(feature = "mock-stwo", not(feature = "real-stwo")))]
+    let range_valid = {
+        let _ = security_params;
+        verify_stwo_range_proof(range_proof)?
+    };
     
-    // 4. Generar anonymous set real y merkle proof
+    Ok(range_valid)
+}
+
+/// Construye la merkle proof del commitment dentro del anonymous set
+pub(crate) fn build_anonymous_set_merkle_proof(
+    amount_wei: &str,
+    commitment_hash: &str,
+) -> Result<MerkleProof, JsValue> {
+    time_phase(ProofPhase::Merkle, || anonymous_set_merkle_proof(amount_wei, commitment_hash))
+}
+
+fn anonymous_set_merkle_proof(amount_wei: &str, commitment_hash: &str) -> Result<MerkleProof, JsValue> {
     console_log!("🌳 Generando anonymous set de {} usuarios...", 1024);
     let anonymous_set_size = 1024; // 2^10 = 1024 usuarios en el conjunto anónimo
     // Convertir amount a u32 para posicionamiento en anonymous set
     let amount_parsed: u64 = amount_wei.parse().unwrap_or(1);
     let user_index = (amount_parsed % anonymous_set_size as u64) as u32; // Posición pseudoaleatoria basada en amount
     console_log!("👤 Usuario posicionado en índice {} del anonymous set", user_index);
-    let merkle_data_js = generate_real_anonymous_set_proof(user_index, anonymous_set_size, &commitment.commitment_hash)?;
+    let merkle_data_js = generate_real_anonymous_set_proof(user_index, anonymous_set_size, commitment_hash)?;
     let merkle_data_str = merkle_data_js.as_string().ok_or("Failed to get merkle data as string")?;
     let merkle_data: serde_json::Value = serde_json::from_str(&merkle_data_str)
         .map_err(|e| format!("Failed to parse merkle data: {}", e))?;
     
-    let merkle_proof = MerkleProof {
+    Ok(MerkleProof {
         proof_path: merkle_data["proof"]["proof_path"].as_array()
             .unwrap_or(&vec![])
             .iter()
             .map(|v| v.as_str().unwrap_or("").to_string())
             .collect(),
         leaf_index: merkle_data["proof"]["leaf_index"].as_u64().unwrap_or(0) as u32,
-        leaf_hash: commitment.commitment_hash.clone(),
+        leaf_hash: commitment_hash.to_string(),
         root: merkle_data["root"].as_str().unwrap_or("").to_string(),
-    };
-    let merkle_root = merkle_data["root"].as_str().unwrap_or("").to_string();
-    
-    // 5. Encriptar metadata del receiver
-    console_log!("🔐 Encriptando metadata...");
-    let encrypted_metadata = encrypt_receiver_metadata(receiver_address, user_secret)?;
-    
-    // 6. Construir la prueba ZK completa
-    let zk_proof = CeaserZKProof {
-        amount_commitment: commitment,
-        range_proof,
-        nullifier,
-        merkle_proof,
-        merkle_root,
-        encrypted_metadata,
-    };
-    
-    console_log!("✅ Prueba ZK generada exitosamente");
-    
-    // Convertir a JSON string para WASM
-    let json_string = serde_json::to_string(&zk_proof)
-        .map_err(|e| format!("Error serializing proof: {}", e))?;
-    Ok(JsValue::from_str(&json_string))
-}
-
-/// Función para verificar una prueba ZK (para testing)
-#[wasm_bindgen]
-pub fn verify_ceaser_zk_proof(proof_json: &str) -> Result<bool, JsValue> {
-    let proof: CeaserZKProof = serde_json::from_str(proof_json)
-        .map_err(|e| format!("Error parsing proof: {}", e))?;
-    
-    console_log!("🔍 Verificando prueba ZK...");
-    
-    // Confirmar modo de verificación
-    #[cfg(feature = "real-stwo")]
-    console_log!("✅ Usando verificador STWO REAL");
-    
-    #[cfg(feature = "mock-stwo")]
-    console_log!("⚠️ Usando verificador MOCK (solo para testing)");
-    
-    // Verificaciones básicas
-    let commitment_valid = verify_pedersen_commitment(&proof.amount_commitment)?;
-    
-    // Verificación de range proof - usar verificador apropiado según el modo
-    #[cfg(feature = "real-stwo")]
-    let range_valid = {
-        console_log!("🔍 Attempting REAL STWO verification...");
-        // Intentar reconstruir el real proof desde la estructura compatible
-        if proof.range_proof.proof_data.len() > 0 && proof.range_proof.proof_data[0].contains("stwo_circle_stark_proof_v2") {
-            console_log!("✅ Detected REAL STWO proof structure");
-            // En producción real, aquí usaríamos verify_real_stwo_range_proof
-            // Por ahora, validamos la estructura como real
-            true
-        } else {
-            console_log!("⚠️ Fallback to compatible verification");
-            verify_stwo_range_proof(&proof.range_proof)?
-        }
-    };
-    
-    #[cfg(feature = "mock-stwo")]
-    let range_valid = verify_stwo_range_proof(&proof.range_proof)?;
-    
-    let merkle_valid = verify_merkle_proof_with_index(
-        proof.merkle_proof.proof_path.clone(), 
-        &proof.merkle_root, 
-        &proof.amount_commitment.commitment_hash,
-        proof.merkle_proof.leaf_index
-    )?;
-    
-    let is_valid = commitment_valid && range_valid && merkle_valid;
-    
-    console_log!("📋 Resultado verificación: commitment={}, range={}, merkle={}, total={}", 
-                 commitment_valid, range_valid, merkle_valid, is_valid);
-    
-    Ok(is_valid)
+    })
 }
 
-/// Función para obtener estadísticas de rendimiento
+/// Estadísticas medidas (registro de ejecuciones por operación) y seguridad del backend
 #[wasm_bindgen]
 pub fn get_zkp_performance_stats() -> JsValue {
     #[cfg(feature = "real-stwo")]
@@ -262,19 +584,17 @@ pub fn get_zkp_performance_stats() -> JsValue {
     
     #[cfg(not(feature = "real-stwo"))]
     {
-        let stats = HashMap::from([
-            ("proof_generation_time_ms", "~100 (MOCK - NOT REAL)"),
-            ("proof_size_bytes", "~1024 (MOCK - NOT REAL)"),
-            ("verification_time_ms", "~5 (MOCK - NOT REAL)"),
-            ("supported_range", "0.001 - 1000 STRK"),
-            ("merkle_tree_capacity", "~1M operations (MOCK)"),
-            ("library_used", "MOCK IMPLEMENTATION - NOT SECURE"),
-            ("warning", "⚠️ THIS IS NOT CRYPTOGRAPHICALLY SECURE"),
-            ("recommendation", "Use --features real-stwo for production"),
-        ]);
+        let config = StwoCircuitConfig::default();
+        let stats = serde_json::json!({
+            "library_used": "MOCK IMPLEMENTATION - NOT SECURE",
+            "warning": "⚠️ THIS IS NOT CRYPTOGRAPHICALLY SECURE",
+            "recommendation": "Use --no-default-features --features real-stwo for production",
+            "supported_range": "0.001 - 1000 STRK",
+            "security": config.security_params().estimate(config.log_trace_size()).ok(),
+            "measured": performance_stats(),
+        });
         
-        let json_string = serde_json::to_string(&stats).unwrap();
-        JsValue::from_str(&json_string)
+        JsValue::from_str(&stats.to_string())
     }
 }
 
@@ -287,7 +607,7 @@ pub fn main() {
     #[cfg(feature = "real-stwo")]
     console_log!("✅ Modo: PRODUCCIÓN (STWO Real)");
     
-    #[cfg(feature = "mock-stwo")]
+    #[cfg(all(feature = "mock-stwo", not(feature = "real-stwo")))]
     console_log!("⚠️ Modo: DESARROLLO (Mock)");
 }
 
@@ -1,3 +1,40 @@
+//! CLI de medición: genera pruebas de depósito nativas y muestra el registro de
+//! estadísticas (tiempos por fase, tamaño de prueba y pico de memoria).
+//!
+//! Uso: `cargo run --release -- [ejecuciones] [--history]`
+use zkp_ceaser::{
+    build_deposit_proof, performance_history, performance_stats, time_phase, ProofPhase,
+    ProofRecorder, TrackingAllocator, ZKProofConfig,
+};
+
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
 fn main() {
-    println!("Hello, world!");
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let runs = args
+        .iter()
+        .find_map(|arg| arg.parse::<usize>().ok())
+        .unwrap_or(5);
+    let show_history = args.iter().any(|arg| arg == "--history");
+
+    let config = ZKProofConfig::default();
+    for run in 0..runs {
+        let recorder = ProofRecorder::start("deposit_proof");
+        let nonce = format!("0x{:064x}", run + 1);
+        let Ok(proof) = build_deposit_proof("1000000000000000000", &nonce, &nonce, &config) else {
+            eprintln!("❌ Error generando la prueba de depósito {}", run);
+            std::process::exit(1);
+        };
+        let json = time_phase(ProofPhase::Serialization, || serde_json::to_string(&proof))
+            .expect("deposit proof serializes to JSON");
+        recorder.finish(json.len());
+    }
+
+    let report = if show_history {
+        serde_json::to_string_pretty(&performance_history())
+    } else {
+        serde_json::to_string_pretty(&performance_stats())
+    };
+    println!("{}", report.expect("stats serialize to JSON"));
 }
@@ -0,0 +1,317 @@
+use std::fmt;
+use wasm_bindgen::prelude::*;
+use rand::{CryptoRng, RngCore};
+use sha3::{Digest, Keccak256};
+use ark_bn254::{Fr, G1Projective};
+use crate::value_commitment::{commit_value_with_generator, value_blinding_from_bytes};
+use crate::{
+    asset_generator, generate_asset_commitment_for_domain, generate_nullifier_for_domain,
+    generate_pedersen_commitment_for_domain, note_leaf, resolve_asset_id, spend_key_for_domain, Domain,
+    PedersenCommitment, ProofRng, SN_MAIN,
+};
+
+const NOTE_PREFIX: &str = "ceaser-note";
+const NOTE_VERSION: &str = "v1";
+const NOTE_FIELD_BYTES: usize = 32;
+const NOTE_CHECKSUM_BYTES: usize = 4;
+
+/// Nota privada de CEASER: todo lo necesario para gastar un depósito
+/// Formato de backup: ceaser-note-v1-<chain>-<amount>-<hex>
+/// donde <hex> = secret || nullifier || blinding || checksum (4 bytes)
+#[derive(Clone, PartialEq, Eq)]
+#[wasm_bindgen]
+pub struct Note {
+    chain: String,
+    amount_wei: u128,
+    secret: [u8; NOTE_FIELD_BYTES],
+    nullifier: [u8; NOTE_FIELD_BYTES],
+    blinding: [u8; NOTE_FIELD_BYTES],
+}
+
+/// Los secretos nunca aparecen en logs ni en mensajes de error
+impl fmt::Debug for Note {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Note")
+            .field("chain", &self.chain)
+            .field("amount_wei", &self.amount_wei)
+            .field("secret", &"<redacted>")
+            .field("nullifier", &"<redacted>")
+            .field("blinding", &"<redacted>")
+            .finish()
+    }
+}
+
+impl Note {
+    /// Genera una nota nueva usando la fuente de aleatoriedad indicada
+    pub fn generate_with_rng<R: RngCore + CryptoRng>(
+        chain: &str,
+        amount_wei: u128,
+        rng: &mut R,
+    ) -> Result<Note, String> {
+        validate_chain(chain)?;
+
+        let mut secret = [0u8; NOTE_FIELD_BYTES];
+        let mut nullifier = [0u8; NOTE_FIELD_BYTES];
+        let mut blinding = [0u8; NOTE_FIELD_BYTES];
+        rng.fill_bytes(&mut secret);
+        rng.fill_bytes(&mut nullifier);
+        rng.fill_bytes(&mut blinding);
+
+        Ok(Note {
+            chain: chain.to_string(),
+            amount_wei,
+            secret,
+            nullifier,
+            blinding,
+        })
+    }
+
+    /// Construye una nota a partir de sus componentes
+    pub fn from_parts(
+        chain: &str,
+        amount_wei: u128,
+        secret: [u8; NOTE_FIELD_BYTES],
+        nullifier: [u8; NOTE_FIELD_BYTES],
+        blinding: [u8; NOTE_FIELD_BYTES],
+    ) -> Result<Note, String> {
+        validate_chain(chain)?;
+        Ok(Note {
+            chain: chain.to_string(),
+            amount_wei,
+            secret,
+            nullifier,
+            blinding,
+        })
+    }
+
+    /// Serializa la nota al formato de backup versionado con checksum
+    pub fn encode(&self) -> String {
+        let header = self.header();
+        let mut payload = Vec::with_capacity(3 * NOTE_FIELD_BYTES + NOTE_CHECKSUM_BYTES);
+        payload.extend_from_slice(&self.secret);
+        payload.extend_from_slice(&self.nullifier);
+        payload.extend_from_slice(&self.blinding);
+        let checksum = note_checksum(&header, &payload);
+        payload.extend_from_slice(&checksum);
+
+        format!("{}{}", header, hex::encode(payload))
+    }
+
+    /// Parsea una nota desde el formato de backup, validando versión y checksum.
+    /// Solo se acepta la codificación canónica (la que produce `encode`)
+    pub fn decode(encoded: &str) -> Result<Note, String> {
+        let encoded = encoded.trim();
+        let parts: Vec<&str> = encoded.split('-').collect();
+        if parts.len() != 6 {
+            return Err(format!("Invalid note format: expected 6 fields, got {}", parts.len()));
+        }
+        if format!("{}-{}", parts[0], parts[1]) != NOTE_PREFIX {
+            return Err("Invalid note prefix".to_string());
+        }
+        if parts[2] != NOTE_VERSION {
+            return Err(format!("Unsupported note version: {}", parts[2]));
+        }
+
+        let chain = parts[3];
+        validate_chain(chain)?;
+        let amount_wei = parts[4].parse::<u128>()
+            .map_err(|e| format!("Invalid note amount: {}", e))?;
+
+        let bytes = hex::decode(parts[5])
+            .map_err(|e| format!("Invalid note hex: {}", e))?;
+        if bytes.len() != 3 * NOTE_FIELD_BYTES + NOTE_CHECKSUM_BYTES {
+            return Err(format!("Invalid note payload length: {} bytes", bytes.len()));
+        }
+
+        let (payload, checksum) = bytes.split_at(3 * NOTE_FIELD_BYTES);
+        let header = format!("{}-{}-{}-{}-", NOTE_PREFIX, NOTE_VERSION, chain, amount_wei);
+        if note_checksum(&header, payload) != checksum {
+            return Err("Note checksum mismatch".to_string());
+        }
+
+        let mut secret = [0u8; NOTE_FIELD_BYTES];
+        let mut nullifier = [0u8; NOTE_FIELD_BYTES];
+        let mut blinding = [0u8; NOTE_FIELD_BYTES];
+        secret.copy_from_slice(&payload[..NOTE_FIELD_BYTES]);
+        nullifier.copy_from_slice(&payload[NOTE_FIELD_BYTES..2 * NOTE_FIELD_BYTES]);
+        blinding.copy_from_slice(&payload[2 * NOTE_FIELD_BYTES..]);
+
+        // "+5", ceros a la izquierda o hex en mayúsculas darían varias codificaciones de la misma nota
+        let note = Note::from_parts(chain, amount_wei, secret, nullifier, blinding)?;
+        if note.encode() != encoded {
+            return Err("Non-canonical note encoding".to_string());
+        }
+        Ok(note)
+    }
+
+    pub fn amount(&self) -> u128 {
+        self.amount_wei
+    }
+
+    pub fn secret_bytes(&self) -> &[u8; NOTE_FIELD_BYTES] {
+        &self.secret
+    }
+
+    pub fn nullifier_bytes(&self) -> &[u8; NOTE_FIELD_BYTES] {
+        &self.nullifier
+    }
+
+    pub fn blinding_bytes(&self) -> &[u8; NOTE_FIELD_BYTES] {
+        &self.blinding
+    }
+
+    /// Blinding r del commitment de valor, derivado de `commitment_nonce` igual
+    /// que en el depósito: la nota depositada y la gastada son el mismo N
+    pub fn value_blinding(&self) -> Fr {
+        value_blinding_from_bytes(&[self.secret, self.blinding].concat())
+    }
+
+    /// Commitment de valor con el generador del activo: V = v·A + r·H
+    pub fn value_commitment(&self, asset_id: &str) -> Result<G1Projective, String> {
+        let generator = asset_generator(&resolve_asset_id(asset_id)?);
+        Ok(commit_value_with_generator(self.amount_wei, &generator, &self.value_blinding()))
+    }
+
+    /// Clave de gasto (s, S = s·K_d) de la nota de un activo; su hash es el nullifier
+    pub fn spend_key_in_domain(&self, asset_id: &str, domain: &Domain) -> Result<(Fr, G1Projective), JsValue> {
+        let commitment = self.asset_commitment_in_domain(asset_id, domain)?;
+        Ok(spend_key_for_domain(&commitment.commitment_hash, &self.nullifier_secret(), domain)?)
+    }
+
+    /// Punto de la nota N = V + S, el que se publica al depositarla con
+    /// `commitment_nonce`/`nullifier_secret` en un pool de ese activo
+    pub fn note_commitment_in_domain(&self, asset_id: &str, domain: &Domain) -> Result<G1Projective, JsValue> {
+        let (_, spend_key) = self.spend_key_in_domain(asset_id, domain)?;
+        Ok(self.value_commitment(asset_id)? + spend_key)
+    }
+
+    /// La nota solo se gasta en su cadena: "sepolia" (o "sn_sepolia") es
+    /// SN_SEPOLIA y "mainnet" (o "main") SN_MAIN
+    pub fn check_chain(&self, domain: &Domain) -> Result<(), String> {
+        let chain_id = match self.chain.as_str() {
+            "mainnet" => SN_MAIN.to_string(),
+            chain if chain.starts_with("sn_") => chain.to_uppercase(),
+            chain => format!("SN_{}", chain.to_uppercase()),
+        };
+        if chain_id != domain.chain_id.trim().to_uppercase() {
+            return Err(format!("Note is for chain {} but the pool is on {}", self.chain, domain.chain_id));
+        }
+        Ok(())
+    }
+
+    fn header(&self) -> String {
+        format!("{}-{}-{}-{}-", NOTE_PREFIX, NOTE_VERSION, self.chain, self.amount_wei)
+    }
+}
+
+#[wasm_bindgen]
+impl Note {
+    /// Genera una nota nueva con aleatoriedad del sistema (OS / crypto.getRandomValues)
+    #[wasm_bindgen(constructor)]
+    pub fn new(chain: &str, amount_wei: &str) -> Result<Note, JsValue> {
+        let amount = amount_wei.parse::<u128>()
+            .map_err(|e| format!("Invalid amount: {}", e))?;
+        Ok(Note::generate_with_rng(chain, amount, &mut ProofRng::from_entropy())?)
+    }
+
+    /// Parsea una nota desde su string de backup
+    #[wasm_bindgen]
+    pub fn parse(encoded: &str) -> Result<Note, JsValue> {
+        Ok(Note::decode(encoded)?)
+    }
+
+    /// String de backup de la nota
+    #[wasm_bindgen]
+    pub fn to_note_string(&self) -> String {
+        self.encode()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn chain(&self) -> String {
+        self.chain.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn amount_wei(&self) -> String {
+        self.amount_wei.to_string()
+    }
+
+    /// Nonce del commitment (secret || blinding), usado como `nonce` en generate_ceaser_zk_proof
+    #[wasm_bindgen]
+    pub fn commitment_nonce(&self) -> String {
+        format!("0x{}{}", hex::encode(self.secret), hex::encode(self.blinding))
+    }
+
+    /// Preimagen del nullifier, usada como `user_secret` en generate_ceaser_zk_proof
+    #[wasm_bindgen]
+    pub fn nullifier_secret(&self) -> String {
+        format!("0x{}", hex::encode(self.nullifier))
+    }
+
+    /// Commitment del monto derivado de la nota (derivación v0, como `generate_pedersen_commitment`)
+    #[wasm_bindgen]
+    pub fn commitment(&self) -> Result<PedersenCommitment, JsValue> {
+        self.commitment_in_domain(&Domain::legacy())
+    }
+
+    /// Nullifier público que se revela al gastar la nota (derivación v0, como `generate_nullifier`)
+    #[wasm_bindgen]
+    pub fn nullifier_hash(&self) -> Result<String, JsValue> {
+        self.nullifier_hash_in_domain(&Domain::legacy())
+    }
+
+    /// Commitment del monto en el dominio indicado
+    #[wasm_bindgen]
+    pub fn commitment_in_domain(&self, domain: &Domain) -> Result<PedersenCommitment, JsValue> {
+        generate_pedersen_commitment_for_domain(&self.amount_wei.to_string(), &self.commitment_nonce(), domain)
+    }
+
+    /// Nullifier de la nota en el dominio indicado
+    #[wasm_bindgen]
+    pub fn nullifier_hash_in_domain(&self, domain: &Domain) -> Result<String, JsValue> {
+        let commitment = self.commitment_in_domain(domain)?;
+        generate_nullifier_for_domain(&commitment.commitment_hash, &self.nullifier_secret(), domain)
+    }
+
+    /// Commitment de la nota ligado al activo (símbolo o dirección del token)
+    #[wasm_bindgen]
+    pub fn asset_commitment_in_domain(&self, asset_id: &str, domain: &Domain) -> Result<PedersenCommitment, JsValue> {
+        generate_asset_commitment_for_domain(asset_id, &self.amount_wei.to_string(), &self.commitment_nonce(), domain)
+    }
+
+    /// Nullifier de la nota de un activo concreto en el dominio indicado
+    #[wasm_bindgen]
+    pub fn asset_nullifier_hash_in_domain(&self, asset_id: &str, domain: &Domain) -> Result<String, JsValue> {
+        let commitment = self.asset_commitment_in_domain(asset_id, domain)?;
+        generate_nullifier_for_domain(&commitment.commitment_hash, &self.nullifier_secret(), domain)
+    }
+
+    /// Hoja del árbol de la nota de un activo (ver `note_leaf`)
+    #[wasm_bindgen]
+    pub fn leaf_in_domain(&self, asset_id: &str, domain: &Domain) -> Result<PedersenCommitment, JsValue> {
+        Ok(note_leaf(&self.note_commitment_in_domain(asset_id, domain)?, None, domain)?)
+    }
+}
+
+/// Checksum de 4 bytes sobre cabecera + payload
+fn note_checksum(header: &str, payload: &[u8]) -> [u8; NOTE_CHECKSUM_BYTES] {
+    let mut hasher = Keccak256::new();
+    hasher.update(header.as_bytes());
+    hasher.update(payload);
+    let hash = hasher.finalize();
+
+    let mut checksum = [0u8; NOTE_CHECKSUM_BYTES];
+    checksum.copy_from_slice(&hash[..NOTE_CHECKSUM_BYTES]);
+    checksum
+}
+
+/// El identificador de cadena no puede contener '-' (separador del formato)
+fn validate_chain(chain: &str) -> Result<(), String> {
+    let valid = !chain.is_empty()
+        && chain.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid chain identifier: {}", chain))
+    }
+}
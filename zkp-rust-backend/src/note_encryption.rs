@@ -0,0 +1,224 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use ark_bn254::{Fr, G1Affine, G1Projective};
+use ark_ec::{CurveGroup, PrimeGroup};
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand::{CryptoRng, RngCore};
+use sha3::{Digest, Keccak256};
+use subtle::ConstantTimeEq;
+use crate::{ProofRng, ViewingKey};
+
+const POINT_BYTES: usize = 32;
+const TAG_BYTES: usize = 16;
+const AMOUNT_BYTES: usize = 16;
+const BLINDING_BYTES: usize = 32;
+const MAX_MEMO_BYTES: usize = 512;
+
+/// Datos de nota que el receptor recupera al escanear
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScannedNote {
+    pub blob_index: u32,
+    pub amount_wei: String,
+    pub blinding: String,
+    pub memo: String,
+}
+
+impl ViewingKey {
+    /// Escalar BN254 asociado a la clave de visualización
+    fn scalar(&self) -> Fr {
+        Fr::from_le_bytes_mod_order(self.as_bytes())
+    }
+
+    /// Punto público de la clave de visualización
+    pub fn public_key_point(&self) -> G1Affine {
+        (G1Projective::generator() * self.scalar()).into_affine()
+    }
+
+    /// Intenta descifrar un blob; `None` si no va dirigido a esta clave
+    pub fn try_decrypt_note(&self, blob: &[u8]) -> Option<(u128, [u8; BLINDING_BYTES], String)> {
+        if blob.len() < POINT_BYTES + TAG_BYTES + AMOUNT_BYTES + BLINDING_BYTES {
+            return None;
+        }
+
+        let (ephemeral_bytes, rest) = blob.split_at(POINT_BYTES);
+        let (tag, ciphertext) = rest.split_at(TAG_BYTES);
+
+        let ephemeral = G1Affine::deserialize_compressed(ephemeral_bytes).ok()?;
+        let shared = (G1Projective::from(ephemeral) * self.scalar()).into_affine();
+        let key = derive_encryption_key(&shared, ephemeral_bytes);
+
+        // Tiempo constante: no revelar cuántos bytes del tag coinciden
+        if !bool::from(compute_tag(&key, ciphertext)[..].ct_eq(tag)) {
+            return None;
+        }
+
+        let plaintext = apply_keystream(&key, ciphertext);
+        let mut amount_bytes = [0u8; AMOUNT_BYTES];
+        amount_bytes.copy_from_slice(&plaintext[..AMOUNT_BYTES]);
+        let mut blinding = [0u8; BLINDING_BYTES];
+        blinding.copy_from_slice(&plaintext[AMOUNT_BYTES..AMOUNT_BYTES + BLINDING_BYTES]);
+        let memo = String::from_utf8(plaintext[AMOUNT_BYTES + BLINDING_BYTES..].to_vec()).ok()?;
+
+        Some((u128::from_le_bytes(amount_bytes), blinding, memo))
+    }
+
+    /// Devuelve las notas que descifran correctamente con esta clave
+    pub fn scan(&self, encrypted_blobs: &[String]) -> Vec<ScannedNote> {
+        encrypted_blobs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, blob_hex)| {
+                let blob = hex::decode(blob_hex.trim_start_matches("0x")).ok()?;
+                let (amount, blinding, memo) = self.try_decrypt_note(&blob)?;
+                Some(ScannedNote {
+                    blob_index: i as u32,
+                    amount_wei: amount.to_string(),
+                    blinding: format!("0x{}", hex::encode(blinding)),
+                    memo,
+                })
+            })
+            .collect()
+    }
+}
+
+#[wasm_bindgen]
+impl ViewingKey {
+    /// Clave pública de visualización (punto comprimido en hex) para compartir con emisores
+    #[wasm_bindgen]
+    pub fn public_key(&self) -> String {
+        let mut bytes = Vec::with_capacity(POINT_BYTES);
+        self.public_key_point()
+            .serialize_compressed(&mut bytes)
+            .expect("serializing into a Vec cannot fail");
+        format!("0x{}", hex::encode(bytes))
+    }
+}
+
+/// Cifra (amount, blinding, memo) para el receptor usando la fuente de aleatoriedad indicada
+///
+/// ECDH efímero sobre BN254 G1 + keystream Keccak + MAC Keccak (encrypt-then-MAC)
+/// blob = R (32 bytes, comprimido) || tag (16 bytes) || ciphertext
+/// plaintext = amount (16 bytes LE) || blinding (32 bytes) || memo (UTF-8)
+pub fn encrypt_note_for_recipient_with_rng<R: RngCore + CryptoRng>(
+    recipient_public_key: &str,
+    amount_wei: u128,
+    blinding: &[u8; BLINDING_BYTES],
+    memo: &str,
+    rng: &mut R,
+) -> Result<String, String> {
+    if memo.len() > MAX_MEMO_BYTES {
+        return Err(format!("Memo too long: {} bytes (max {})", memo.len(), MAX_MEMO_BYTES));
+    }
+
+    let recipient_bytes = hex::decode(recipient_public_key.trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid recipient public key hex: {}", e))?;
+    let recipient = G1Affine::deserialize_compressed(&recipient_bytes[..])
+        .map_err(|e| format!("Invalid recipient public key: {}", e))?;
+
+    // Escalar efímero (64 bytes reducidos módulo r para evitar sesgo)
+    let mut ephemeral_seed = [0u8; 64];
+    rng.fill_bytes(&mut ephemeral_seed);
+    let ephemeral_secret = Fr::from_le_bytes_mod_order(&ephemeral_seed);
+
+    let ephemeral_public = (G1Projective::generator() * ephemeral_secret).into_affine();
+    let mut ephemeral_bytes = Vec::with_capacity(POINT_BYTES);
+    ephemeral_public
+        .serialize_compressed(&mut ephemeral_bytes)
+        .map_err(|e| format!("Error serializing ephemeral key: {}", e))?;
+
+    let shared = (G1Projective::from(recipient) * ephemeral_secret).into_affine();
+    let key = derive_encryption_key(&shared, &ephemeral_bytes);
+
+    let mut plaintext = Vec::with_capacity(AMOUNT_BYTES + BLINDING_BYTES + memo.len());
+    plaintext.extend_from_slice(&amount_wei.to_le_bytes());
+    plaintext.extend_from_slice(blinding);
+    plaintext.extend_from_slice(memo.as_bytes());
+
+    let ciphertext = apply_keystream(&key, &plaintext);
+    let tag = compute_tag(&key, &ciphertext);
+
+    let mut blob = ephemeral_bytes;
+    blob.extend_from_slice(&tag);
+    blob.extend_from_slice(&ciphertext);
+    Ok(format!("0x{}", hex::encode(blob)))
+}
+
+/// Cifra los datos de una nota para la clave pública de visualización del receptor
+#[wasm_bindgen]
+pub fn encrypt_note_for_recipient(
+    recipient_public_key: &str,
+    amount_wei: &str,
+    blinding: &str,
+    memo: &str,
+) -> Result<String, JsValue> {
+    let amount = amount_wei.parse::<u128>()
+        .map_err(|e| format!("Invalid amount: {}", e))?;
+    let blinding_bytes = hex::decode(blinding.trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid blinding hex: {}", e))?;
+    if blinding_bytes.len() != BLINDING_BYTES {
+        return Err(format!("Blinding must be {} bytes", BLINDING_BYTES).into());
+    }
+    let mut blinding_arr = [0u8; BLINDING_BYTES];
+    blinding_arr.copy_from_slice(&blinding_bytes);
+
+    Ok(encrypt_note_for_recipient_with_rng(
+        recipient_public_key, amount, &blinding_arr, memo, &mut ProofRng::from_entropy(),
+    )?)
+}
+
+/// Escanea blobs cifrados y devuelve (JSON) las notas dirigidas a la clave de visualización
+#[wasm_bindgen]
+pub fn scan_notes(viewing_key: &str, encrypted_blobs: Vec<String>) -> Result<JsValue, JsValue> {
+    let viewing_key = ViewingKey::from_hex(viewing_key)?;
+
+    let notes = viewing_key.scan(&encrypted_blobs);
+    crate::console_log!("🔎 Escaneo completado: {}/{} notas propias", notes.len(), encrypted_blobs.len());
+
+    let json_string = serde_json::to_string(&notes)
+        .map_err(|e| format!("Error serializing scanned notes: {}", e))?;
+    Ok(JsValue::from_str(&json_string))
+}
+
+/// Clave simétrica = Keccak256(dominio || punto compartido || clave efímera)
+fn derive_encryption_key(shared: &G1Affine, ephemeral_bytes: &[u8]) -> [u8; 32] {
+    let mut shared_bytes = Vec::with_capacity(POINT_BYTES);
+    shared
+        .serialize_compressed(&mut shared_bytes)
+        .expect("serializing into a Vec cannot fail");
+
+    let mut hasher = Keccak256::new();
+    hasher.update(b"ceaser-note-encryption-v1");
+    hasher.update(&shared_bytes);
+    hasher.update(ephemeral_bytes);
+
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&hasher.finalize());
+    key
+}
+
+/// XOR con keystream Keccak256(key || "stream" || counter)
+fn apply_keystream(key: &[u8; 32], data: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(data.len());
+    for (counter, chunk) in data.chunks(32).enumerate() {
+        let mut hasher = Keccak256::new();
+        hasher.update(key);
+        hasher.update(b"stream");
+        hasher.update((counter as u32).to_le_bytes());
+        let block = hasher.finalize();
+        output.extend(chunk.iter().zip(block.iter()).map(|(d, k)| d ^ k));
+    }
+    output
+}
+
+/// Tag de autenticación sobre el ciphertext
+fn compute_tag(key: &[u8; 32], ciphertext: &[u8]) -> [u8; TAG_BYTES] {
+    let mut hasher = Keccak256::new();
+    hasher.update(key);
+    hasher.update(b"mac");
+    hasher.update(ciphertext);
+
+    let mut tag = [0u8; TAG_BYTES];
+    tag.copy_from_slice(&hasher.finalize()[..TAG_BYTES]);
+    tag
+}
@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+use wasm_bindgen::prelude::*;
+use serde::Deserialize;
+use crate::normalize_felt_hex;
+
+/// Registro de nullifiers gastados (detección de doble gasto off-chain)
+pub trait NullifierSet {
+    /// ¿Ya se gastó este nullifier?
+    fn contains(&self, nullifier: &str) -> bool;
+
+    /// Inserta el nullifier si no existe; devuelve `false` si ya estaba gastado
+    fn insert_if_absent(&mut self, nullifier: &str) -> Result<bool, String>;
+
+    /// Número de nullifiers registrados
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Formatos aceptados de export: `["0x..", ...]` o `{"nullifiers": ["0x..", ...]}`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NullifierExport {
+    List(Vec<String>),
+    Object { nullifiers: Vec<String> },
+}
+
+/// Implementación en memoria
+#[derive(Debug, Clone, Default)]
+#[wasm_bindgen]
+pub struct InMemoryNullifierSet {
+    spent: HashSet<String>,
+}
+
+impl InMemoryNullifierSet {
+    /// Carga nullifiers conocidos desde un export JSON
+    pub fn from_json_export(json: &str) -> Result<InMemoryNullifierSet, String> {
+        let export: NullifierExport = serde_json::from_str(json)
+            .map_err(|e| format!("Invalid nullifier export: {}", e))?;
+        let nullifiers = match export {
+            NullifierExport::List(nullifiers) => nullifiers,
+            NullifierExport::Object { nullifiers } => nullifiers,
+        };
+
+        let mut set = InMemoryNullifierSet::default();
+        for nullifier in &nullifiers {
+            set.spent.insert(normalize_felt_hex(nullifier));
+        }
+        Ok(set)
+    }
+
+    /// Export JSON ordenado (mismo formato que acepta `from_json_export`)
+    pub fn to_json_export(&self) -> String {
+        let mut nullifiers: Vec<&String> = self.spent.iter().collect();
+        nullifiers.sort();
+        serde_json::json!({ "nullifiers": nullifiers }).to_string()
+    }
+}
+
+impl NullifierSet for InMemoryNullifierSet {
+    fn contains(&self, nullifier: &str) -> bool {
+        self.spent.contains(&normalize_felt_hex(nullifier))
+    }
+
+    fn insert_if_absent(&mut self, nullifier: &str) -> Result<bool, String> {
+        Ok(self.spent.insert(normalize_felt_hex(nullifier)))
+    }
+
+    fn len(&self) -> usize {
+        self.spent.len()
+    }
+}
+
+#[wasm_bindgen]
+impl InMemoryNullifierSet {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> InMemoryNullifierSet {
+        InMemoryNullifierSet::default()
+    }
+
+    /// Crea el registro desde un export JSON de nullifiers conocidos
+    #[wasm_bindgen]
+    pub fn from_json(json: &str) -> Result<InMemoryNullifierSet, JsValue> {
+        Ok(InMemoryNullifierSet::from_json_export(json)?)
+    }
+
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.to_json_export()
+    }
+
+    #[wasm_bindgen]
+    pub fn is_spent(&self, nullifier: &str) -> bool {
+        self.contains(nullifier)
+    }
+
+    /// Marca como gastado; devuelve `false` si ya lo estaba
+    #[wasm_bindgen]
+    pub fn mark_spent(&mut self, nullifier: &str) -> bool {
+        self.spent.insert(normalize_felt_hex(nullifier))
+    }
+
+    #[wasm_bindgen]
+    pub fn size(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Implementación persistida en un archivo JSON (solo nativo, p.ej. el relayer)
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+pub struct FileNullifierSet {
+    path: std::path::PathBuf,
+    inner: InMemoryNullifierSet,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileNullifierSet {
+    /// Abre (o crea vacío) el registro en `path`
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> Result<FileNullifierSet, String> {
+        let path = path.as_ref().to_path_buf();
+        let inner = if path.exists() {
+            let json = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Cannot read nullifier file {}: {}", path.display(), e))?;
+            InMemoryNullifierSet::from_json_export(&json)?
+        } else {
+            InMemoryNullifierSet::default()
+        };
+        Ok(FileNullifierSet { path, inner })
+    }
+
+    /// Escribe a un archivo temporal y renombra para no dejar el registro a medias
+    fn persist(&self) -> Result<(), String> {
+        let tmp_path = self.path.with_extension("tmp");
+        std::fs::write(&tmp_path, self.inner.to_json_export())
+            .map_err(|e| format!("Cannot write nullifier file {}: {}", tmp_path.display(), e))?;
+        std::fs::rename(&tmp_path, &self.path)
+            .map_err(|e| format!("Cannot replace nullifier file {}: {}", self.path.display(), e))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl NullifierSet for FileNullifierSet {
+    fn contains(&self, nullifier: &str) -> bool {
+        self.inner.contains(nullifier)
+    }
+
+    fn insert_if_absent(&mut self, nullifier: &str) -> Result<bool, String> {
+        let inserted = self.inner.insert_if_absent(nullifier)?;
+        if inserted {
+            if let Err(e) = self.persist() {
+                self.inner.spent.remove(&normalize_felt_hex(nullifier));
+                return Err(e);
+            }
+        }
+        Ok(inserted)
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
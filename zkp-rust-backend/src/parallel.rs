@@ -0,0 +1,119 @@
+use wasm_bindgen::prelude::*;
+use serde::Serialize;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[cfg(all(feature = "parallel", target_arch = "wasm32"))]
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Paralelismo opcional (feature `parallel`): rayon nativo o, en el navegador,
+// un pool de Web Workers sobre SharedArrayBuffer con wasm-bindgen-rayon.
+// Todas las operaciones conservan el orden, así que la salida es la misma
+// con uno o con N hilos
+
+/// A partir de este número de hojas/nodos el hash Keccak se reparte entre hilos
+pub const HASH_PARALLEL_THRESHOLD: usize = 1 << 8;
+
+/// Necesario para que el glue JS de wasm-bindgen-rayon pueda arrancar los workers
+#[cfg(all(feature = "parallel", target_arch = "wasm32"))]
+pub use wasm_bindgen_rayon::init_thread_pool;
+
+#[cfg(all(feature = "parallel", target_arch = "wasm32"))]
+static THREAD_POOL_READY: AtomicBool = AtomicBool::new(false);
+
+/// Arranca el pool de workers del navegador. Hasta que la promesa se resuelve
+/// todo se ejecuta en el hilo actual
+#[cfg(all(feature = "parallel", target_arch = "wasm32"))]
+#[wasm_bindgen(js_name = initParallelProving)]
+pub async fn init_parallel_proving(num_threads: usize) -> Result<(), JsValue> {
+    wasm_bindgen_futures::JsFuture::from(init_thread_pool(num_threads)).await?;
+    THREAD_POOL_READY.store(true, Ordering::Release);
+    crate::console_log!("🧵 Pool de {} workers listo para generar pruebas", num_threads);
+    Ok(())
+}
+
+/// Indica si las operaciones grandes se reparten entre hilos
+pub fn parallel_enabled() -> bool {
+    #[cfg(all(feature = "parallel", target_arch = "wasm32"))]
+    {
+        THREAD_POOL_READY.load(Ordering::Acquire)
+    }
+
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    {
+        true
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    {
+        false
+    }
+}
+
+/// Hilos disponibles para la generación de pruebas
+pub fn parallel_threads() -> usize {
+    #[cfg(feature = "parallel")]
+    if parallel_enabled() {
+        return rayon::current_num_threads();
+    }
+    1
+}
+
+/// `f(0..len)` en orden, en paralelo si `len >= min_parallel_len`
+pub(crate) fn map_range<T, F>(len: usize, min_parallel_len: usize, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    if len >= min_parallel_len && parallel_enabled() {
+        return (0..len).into_par_iter().map(f).collect();
+    }
+    let _ = min_parallel_len;
+    (0..len).map(f).collect()
+}
+
+/// `f` sobre cada elemento, en orden, en paralelo si hay al menos `min_parallel_len`
+pub(crate) fn map_slice<T, U, F>(items: &[T], min_parallel_len: usize, f: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> U + Sync + Send,
+{
+    map_range(items.len(), min_parallel_len, |i| f(&items[i]))
+}
+
+/// Ejecuta las dos mitades de una recursión a la vez si `parallel` es cierto
+pub(crate) fn join<A, B, FA, FB>(parallel: bool, a: FA, b: FB) -> (A, B)
+where
+    A: Send,
+    B: Send,
+    FA: FnOnce() -> A + Send,
+    FB: FnOnce() -> B + Send,
+{
+    #[cfg(feature = "parallel")]
+    if parallel && parallel_enabled() {
+        return rayon::join(a, b);
+    }
+    let _ = parallel;
+    (a(), b())
+}
+
+#[derive(Serialize)]
+struct ParallelInfo {
+    feature_enabled: bool,
+    pool_ready: bool,
+    threads: usize,
+}
+
+/// Estado del paralelismo (JSON)
+#[wasm_bindgen]
+pub fn get_parallel_info() -> String {
+    serde_json::to_string(&ParallelInfo {
+        feature_enabled: cfg!(feature = "parallel"),
+        pool_ready: parallel_enabled(),
+        threads: parallel_threads(),
+    })
+    .unwrap_or_default()
+}
@@ -0,0 +1,410 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Ejecuciones que guarda el registro (las más antiguas se descartan)
+pub const STATS_HISTORY_SIZE: usize = 128;
+
+/// Fases medidas durante la generación de una prueba
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProofPhase {
+    Commitment,
+    TraceGeneration,
+    Commit,
+    Fri,
+    Merkle,
+    Serialization,
+}
+
+/// Medidas de una ejecución
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProofRunStats {
+    pub operation: String,
+    pub crate_version: String,
+    pub phase_ms: BTreeMap<ProofPhase, f64>,
+    pub total_ms: f64,
+    pub proof_size_bytes: usize,
+    /// Pico de memoria de esta ejecución con `TrackingAllocator` (None sin él o
+    /// si otra ejecución medida se solapó y los contadores globales se mezclan)
+    pub peak_allocation_bytes: Option<u64>,
+    /// Tamaño de la memoria lineal del módulo WASM al terminar (solo crece)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wasm_memory_bytes: Option<u64>,
+}
+
+/// Resumen de una métrica sobre las ejecuciones del registro
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetricSummary {
+    pub count: usize,
+    pub last: f64,
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl MetricSummary {
+    fn from_values(values: impl Iterator<Item = f64>) -> MetricSummary {
+        let mut summary = MetricSummary { min: f64::INFINITY, max: f64::NEG_INFINITY, ..Default::default() };
+        let mut sum = 0.0;
+        for value in values {
+            summary.count += 1;
+            summary.last = value;
+            summary.min = summary.min.min(value);
+            summary.max = summary.max.max(value);
+            sum += value;
+        }
+        if summary.count == 0 {
+            return MetricSummary::default();
+        }
+        summary.mean = sum / summary.count as f64;
+        summary
+    }
+}
+
+/// Estadísticas agregadas por operación
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationStats {
+    pub runs: usize,
+    pub total_ms: MetricSummary,
+    pub phase_ms: BTreeMap<ProofPhase, MetricSummary>,
+    pub proof_size_bytes: MetricSummary,
+    pub peak_allocation_bytes: MetricSummary,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PerformanceStats {
+    pub crate_version: String,
+    pub history_size: usize,
+    pub operations: BTreeMap<String, OperationStats>,
+}
+
+static STATS_REGISTRY: OnceLock<Mutex<VecDeque<ProofRunStats>>> = OnceLock::new();
+
+thread_local! {
+    /// Fases de la ejecución en curso en este hilo
+    static ACTIVE_PHASES: RefCell<Option<BTreeMap<ProofPhase, f64>>> = const { RefCell::new(None) };
+}
+
+fn registry() -> &'static Mutex<VecDeque<ProofRunStats>> {
+    STATS_REGISTRY.get_or_init(|| Mutex::new(VecDeque::with_capacity(STATS_HISTORY_SIZE)))
+}
+
+/// Milisegundos monótonos (performance.now() en el navegador)
+pub fn now_ms() -> f64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        web_sys::window()
+            .and_then(|window| window.performance())
+            .map(|performance| performance.now())
+            .unwrap_or_else(js_sys::Date::now)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        static EPOCH: OnceLock<std::time::Instant> = OnceLock::new();
+        EPOCH.get_or_init(std::time::Instant::now).elapsed().as_secs_f64() * 1000.0
+    }
+}
+
+/// Suma `ms` a la fase indicada de la ejecución en curso (si la hay)
+pub fn record_phase_ms(phase: ProofPhase, ms: f64) {
+    ACTIVE_PHASES.with(|phases| {
+        if let Some(phases) = phases.borrow_mut().as_mut() {
+            *phases.entry(phase).or_insert(0.0) += ms;
+        }
+    });
+}
+
+/// Ejecuta `f` y atribuye su duración a la fase
+pub fn time_phase<T>(phase: ProofPhase, f: impl FnOnce() -> T) -> T {
+    let start = now_ms();
+    let result = f();
+    record_phase_ms(phase, now_ms() - start);
+    result
+}
+
+/// Mide una ejecución completa. Si ya hay otra en curso en el hilo (p. ej. una
+/// prueba que genera otra), la interna no se registra por separado
+pub struct ProofRecorder {
+    operation: String,
+    started_ms: f64,
+    allocation_baseline: u64,
+    /// Ejecuciones iniciadas hasta esta; None si ya había otra en curso
+    run_generation: Option<u64>,
+    owner: bool,
+    /// Ejecución por etapas (`start_detached`): sus fases viven aquí y solo
+    /// ocupan el hilo entre `resume` y `suspend`
+    detached: bool,
+    phases: Option<BTreeMap<ProofPhase, f64>>,
+    /// Fases que había en el hilo antes de `resume`
+    outer_phases: Option<BTreeMap<ProofPhase, f64>>,
+}
+
+impl ProofRecorder {
+    pub fn start(operation: &str) -> ProofRecorder {
+        let owner = ACTIVE_PHASES.with(|phases| {
+            let mut phases = phases.borrow_mut();
+            if phases.is_some() {
+                return false;
+            }
+            *phases = Some(BTreeMap::new());
+            true
+        });
+        ProofRecorder::new(operation, owner, false)
+    }
+
+    /// Ejecución que cede el hilo entre etapas (p. ej. una prueba asíncrona).
+    /// Se registra siempre, aunque otra esté en curso, y sus fases no se mezclan
+    /// con las de otras ejecuciones intercaladas en el mismo hilo
+    pub fn start_detached(operation: &str) -> ProofRecorder {
+        ProofRecorder::new(operation, true, true)
+    }
+
+    fn new(operation: &str, owner: bool, detached: bool) -> ProofRecorder {
+        let run_generation = if owner { begin_allocation_run() } else { None };
+        ProofRecorder {
+            operation: operation.to_string(),
+            started_ms: now_ms(),
+            allocation_baseline: if run_generation.is_some() { reset_peak_allocation() } else { 0 },
+            run_generation,
+            owner,
+            detached,
+            phases: detached.then(BTreeMap::new),
+            outer_phases: None,
+        }
+    }
+
+    /// Las fases medidas en el hilo pasan a esta ejecución hasta `suspend`
+    pub fn resume(&mut self) {
+        if let Some(phases) = self.phases.take() {
+            self.outer_phases = ACTIVE_PHASES.with(|active| active.replace(Some(phases)));
+        }
+    }
+
+    /// Recupera las fases de la ejecución y devuelve el hilo como estaba
+    pub fn suspend(&mut self) {
+        if self.detached && self.phases.is_none() {
+            let outer = self.outer_phases.take();
+            self.phases = ACTIVE_PHASES.with(|active| active.replace(outer));
+        }
+    }
+
+    /// Cierra la ejecución y la añade al registro
+    pub fn finish(mut self, proof_size_bytes: usize) -> Option<ProofRunStats> {
+        if !self.owner {
+            return None;
+        }
+        self.owner = false;
+        self.suspend();
+        let peak_allocation_bytes = end_allocation_run(self.run_generation)
+            .then(|| peak_allocation_since(self.allocation_baseline))
+            .flatten();
+        let phase_ms = if self.detached {
+            self.phases.take()
+        } else {
+            ACTIVE_PHASES.with(|phases| phases.borrow_mut().take())
+        }
+        .unwrap_or_default();
+        let stats = ProofRunStats {
+            operation: std::mem::take(&mut self.operation),
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            phase_ms,
+            total_ms: now_ms() - self.started_ms,
+            proof_size_bytes,
+            peak_allocation_bytes,
+            wasm_memory_bytes: wasm_memory_bytes(),
+        };
+
+        if let Ok(mut runs) = registry().lock() {
+            if runs.len() == STATS_HISTORY_SIZE {
+                runs.pop_front();
+            }
+            runs.push_back(stats.clone());
+        }
+        Some(stats)
+    }
+}
+
+impl Drop for ProofRecorder {
+    /// Una ejecución abortada (error) no se registra pero libera el hilo
+    fn drop(&mut self) {
+        if self.owner {
+            end_allocation_run(self.run_generation);
+            if self.detached {
+                self.suspend();
+            } else {
+                ACTIVE_PHASES.with(|phases| phases.borrow_mut().take());
+            }
+        }
+    }
+}
+
+/// Ejecuciones registradas, de la más antigua a la más reciente
+pub fn performance_history() -> Vec<ProofRunStats> {
+    registry().lock().map(|runs| runs.iter().cloned().collect()).unwrap_or_default()
+}
+
+/// Agrega el registro por operación
+pub fn performance_stats() -> PerformanceStats {
+    let history = performance_history();
+    let mut by_operation: BTreeMap<String, Vec<&ProofRunStats>> = BTreeMap::new();
+    for run in &history {
+        by_operation.entry(run.operation.clone()).or_default().push(run);
+    }
+
+    let operations = by_operation
+        .into_iter()
+        .map(|(operation, runs)| {
+            let mut phases: Vec<ProofPhase> = runs.iter().flat_map(|run| run.phase_ms.keys().copied()).collect();
+            phases.sort();
+            phases.dedup();
+            let phase_ms = phases
+                .into_iter()
+                .map(|phase| {
+                    let values = runs.iter().filter_map(|run| run.phase_ms.get(&phase).copied());
+                    (phase, MetricSummary::from_values(values))
+                })
+                .collect();
+
+            let stats = OperationStats {
+                runs: runs.len(),
+                total_ms: MetricSummary::from_values(runs.iter().map(|run| run.total_ms)),
+                phase_ms,
+                proof_size_bytes: MetricSummary::from_values(runs.iter().map(|run| run.proof_size_bytes as f64)),
+                peak_allocation_bytes: MetricSummary::from_values(
+                    runs.iter().filter_map(|run| run.peak_allocation_bytes.map(|bytes| bytes as f64)),
+                ),
+            };
+            (operation, stats)
+        })
+        .collect();
+
+    PerformanceStats {
+        crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        history_size: STATS_HISTORY_SIZE,
+        operations,
+    }
+}
+
+pub fn clear_performance_stats() {
+    if let Ok(mut runs) = registry().lock() {
+        runs.clear();
+    }
+}
+
+/// Historial de ejecuciones medidas (JSON)
+#[wasm_bindgen]
+pub fn get_performance_history() -> String {
+    serde_json::to_string(&performance_history()).unwrap_or_default()
+}
+
+/// Vacía el registro de estadísticas
+#[wasm_bindgen]
+pub fn reset_performance_stats() {
+    clear_performance_stats();
+}
+
+static CURRENT_ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static PEAK_ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static TRACKING_ENABLED: AtomicBool = AtomicBool::new(false);
+/// Ejecuciones medidas en curso (en cualquier hilo) y total de iniciadas
+static ALLOCATION_RUNS: Mutex<(u64, u64)> = Mutex::new((0, 0));
+
+/// Allocator que cuenta bytes vivos y el pico. Lo instala el binario que quiera
+/// medir memoria: `#[global_allocator] static A: TrackingAllocator = TrackingAllocator;`
+pub struct TrackingAllocator;
+
+impl TrackingAllocator {
+    fn on_alloc(size: usize) {
+        TRACKING_ENABLED.store(true, Ordering::Relaxed);
+        let current = CURRENT_ALLOCATED.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+        PEAK_ALLOCATED.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn on_dealloc(size: usize) {
+        CURRENT_ALLOCATED.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::on_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::on_dealloc(layout.size());
+            Self::on_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Registra el inicio de una ejecución. Los contadores del allocator son del
+/// proceso: solo una ejecución sin otras en curso puede atribuirse el pico
+fn begin_allocation_run() -> Option<u64> {
+    let mut runs = ALLOCATION_RUNS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let (active, started) = &mut *runs;
+    *started += 1;
+    *active += 1;
+    (*active == 1).then_some(*started)
+}
+
+/// Cierra la ejecución; `true` si ninguna otra empezó mientras tanto
+fn end_allocation_run(run_generation: Option<u64>) -> bool {
+    let mut runs = ALLOCATION_RUNS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let (active, started) = &mut *runs;
+    *active -= 1;
+    run_generation == Some(*started)
+}
+
+/// Reinicia el pico al uso actual y devuelve ese uso como línea base
+fn reset_peak_allocation() -> u64 {
+    let current = CURRENT_ALLOCATED.load(Ordering::Relaxed);
+    PEAK_ALLOCATED.store(current, Ordering::Relaxed);
+    current
+}
+
+/// Pico por encima de la línea base; solo con `TrackingAllocator` instalado
+fn peak_allocation_since(baseline: u64) -> Option<u64> {
+    TRACKING_ENABLED
+        .load(Ordering::Relaxed)
+        .then(|| PEAK_ALLOCATED.load(Ordering::Relaxed).saturating_sub(baseline))
+}
+
+/// Memoria lineal del módulo WASM: el pico de todo el módulo desde que se
+/// cargó, no el de una ejecución
+fn wasm_memory_bytes() -> Option<u64> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        Some(core::arch::wasm32::memory_size(0) as u64 * 65536)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        None
+    }
+}
@@ -2,6 +2,9 @@
 // Este es el código que se usa en producción
 // Integración real con StarkWare STWO
 
+#[cfg(feature = "real-stwo")]
+pub mod stwo_range_air;
+
 #[cfg(feature = "real-stwo")]
 pub mod stwo_real;
 
@@ -0,0 +1,226 @@
+use num_traits::{One, Zero};
+use stwo::constraint_framework::preprocessed_columns::PreProcessedColumnId;
+use stwo::constraint_framework::{
+    EvalAtRow, FrameworkComponent, FrameworkEval, TraceLocationAllocator, ORIGINAL_TRACE_IDX,
+};
+use stwo::core::air::Component;
+use stwo::core::backend::simd::column::BaseColumn;
+use stwo::core::backend::simd::SimdBackend;
+use stwo::core::backend::Column;
+use stwo::core::channel::{Blake2sChannel, Channel};
+use stwo::core::fields::m31::BaseField;
+use stwo::core::fields::qm31::SecureField;
+use stwo::core::pcs::{CommitmentSchemeProver, CommitmentSchemeVerifier, PcsConfig};
+use stwo::core::poly::circle::{CanonicCoset, CircleEvaluation, PolyOps};
+use stwo::core::poly::BitReversedOrder;
+use stwo::core::prover::{prove, verify, StarkProof};
+use stwo::core::utils::{bit_reverse_index, coset_index_to_circle_domain_index};
+use stwo::core::vcs::blake2_merkle::{Blake2sMerkleChannel, Blake2sMerkleHasher};
+use crate::U256;
+
+/// log2 de las filas de la traza: una fila por bit de las diferencias (rangos de hasta 2^128)
+pub const RANGE_AIR_LOG_ROWS: u32 = 7;
+const RANGE_AIR_BITS: usize = 1 << RANGE_AIR_LOG_ROWS;
+
+/// Árboles del commitment scheme: preprocesado, traza y composición
+const RANGE_AIR_TREES: usize = 3;
+
+type RangeTraceColumn = CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>;
+
+/// AIR del range proof. Con a = amount − min y b = max − amount en binario
+/// (a_i, b_i ∈ {0,1}) la suma con acarreo c_i reproduce los bits públicos d_i
+/// de D = max − min: a_i + b_i + c_i = d_i + 2·c_{i+1}, con c_0 = 0. La traza es
+/// cíclica, así que en la última fila c_{i+1} es c_0 y el acarreo final es 0:
+/// a + b = D sin desbordar, luego min ≤ amount ≤ max.
+///
+/// `d_i` es una columna preprocesada que el verificador recalcula desde min/max.
+/// STWO no enmascara la traza: la prueba no es de conocimiento cero
+#[derive(Debug, Clone)]
+pub struct RangeCheckEval {
+    pub log_n_rows: u32,
+}
+
+impl FrameworkEval for RangeCheckEval {
+    fn log_size(&self) -> u32 {
+        self.log_n_rows
+    }
+
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        self.log_n_rows + 1
+    }
+
+    fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+        let is_first = eval.get_preprocessed_column(is_first_column_id());
+        let range_bit = eval.get_preprocessed_column(range_bit_column_id());
+        let a = eval.next_trace_mask();
+        let b = eval.next_trace_mask();
+        let [carry, next_carry] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+
+        for bit in [a.clone(), b.clone(), carry.clone()] {
+            eval.add_constraint(bit.clone() * (bit - E::F::from(BaseField::one())));
+        }
+        eval.add_constraint(is_first * carry.clone());
+        eval.add_constraint(a + b + carry - range_bit - next_carry.clone() - next_carry);
+        eval
+    }
+}
+
+pub type RangeCheckComponent = FrameworkComponent<RangeCheckEval>;
+
+fn is_first_column_id() -> PreProcessedColumnId {
+    PreProcessedColumnId { id: "ceaser_range_is_first".to_string() }
+}
+
+fn range_bit_column_id() -> PreProcessedColumnId {
+    PreProcessedColumnId { id: "ceaser_range_bit".to_string() }
+}
+
+fn range_component() -> RangeCheckComponent {
+    FrameworkComponent::new(
+        &mut TraceLocationAllocator::new_with_preproccessed_columns(&[is_first_column_id(), range_bit_column_id()]),
+        RangeCheckEval { log_n_rows: RANGE_AIR_LOG_ROWS },
+        SecureField::zero(),
+    )
+}
+
+/// D = max − min; debe caber en las 128 filas de la traza
+fn range_width(min: U256, max: U256) -> Result<u128, String> {
+    max.checked_sub(&min)
+        .and_then(|width| width.as_u128())
+        .ok_or_else(|| format!("Range [{}, {}] must be non-empty and narrower than 2^128", min, max))
+}
+
+/// Columna con el valor de la fila i en la posición que STWO usa para el punto i del coset
+fn trace_column(values: &[u32]) -> RangeTraceColumn {
+    let mut column = BaseColumn::zeros(1 << RANGE_AIR_LOG_ROWS);
+    for (row, value) in values.iter().enumerate() {
+        let index = bit_reverse_index(coset_index_to_circle_domain_index(row, RANGE_AIR_LOG_ROWS), RANGE_AIR_LOG_ROWS);
+        column.set(index, BaseField::from(*value));
+    }
+    CircleEvaluation::new(CanonicCoset::new(RANGE_AIR_LOG_ROWS).circle_domain(), column)
+}
+
+/// Traza preprocesada: selector de la primera fila y bits de D (dependen solo de min/max)
+fn preprocessed_trace(width: u128) -> Vec<RangeTraceColumn> {
+    let is_first: Vec<u32> = (0..RANGE_AIR_BITS).map(|row| (row == 0) as u32).collect();
+    let range_bits: Vec<u32> = (0..RANGE_AIR_BITS).map(|bit| (width >> bit & 1) as u32).collect();
+    vec![trace_column(&is_first), trace_column(&range_bits)]
+}
+
+/// Traza del prover: bits de a, bits de b y acarreos
+fn range_trace(a: u128, b: u128) -> Vec<RangeTraceColumn> {
+    let a_bits: Vec<u32> = (0..RANGE_AIR_BITS).map(|bit| (a >> bit & 1) as u32).collect();
+    let b_bits: Vec<u32> = (0..RANGE_AIR_BITS).map(|bit| (b >> bit & 1) as u32).collect();
+    let mut carries = vec![0u32; RANGE_AIR_BITS];
+    for bit in 1..RANGE_AIR_BITS {
+        carries[bit] = (a_bits[bit - 1] + b_bits[bit - 1] + carries[bit - 1]) >> 1;
+    }
+    vec![trace_column(&a_bits), trace_column(&b_bits), trace_column(&carries)]
+}
+
+/// Las entradas públicas (límites y hash del commitment del monto) entran en
+/// el canal antes de cualquier commitment
+fn mix_public_inputs(channel: &mut Blake2sChannel, min: U256, max: U256, commitment_hash: &[u8; 32]) {
+    for bound in [min, max] {
+        for word in bound.to_le_bytes().chunks(8) {
+            channel.mix_u64(u64::from_le_bytes(word.try_into().expect("8-byte chunk")));
+        }
+    }
+    for word in commitment_hash.chunks(8) {
+        channel.mix_u64(u64::from_le_bytes(word.try_into().expect("8-byte chunk")));
+    }
+}
+
+fn twiddle_log_size(config: &PcsConfig) -> u32 {
+    RANGE_AIR_LOG_ROWS + 1 + config.fri_config.log_blowup_factor
+}
+
+/// Genera la prueba STWO de que min ≤ amount ≤ max para el commitment dado
+pub fn prove_range_air(
+    amount: U256,
+    min: U256,
+    max: U256,
+    commitment_hash: &[u8; 32],
+    config: PcsConfig,
+) -> Result<StarkProof<Blake2sMerkleHasher>, String> {
+    let width = range_width(min, max)?;
+    let (a, b) = match (amount.checked_sub(&min), max.checked_sub(&amount)) {
+        (Some(a), Some(b)) => (a.low(), b.low()),
+        _ => return Err(format!("Amount {} is outside valid range [{}, {}]", amount, min, max)),
+    };
+
+    let twiddles = SimdBackend::precompute_twiddles(
+        CanonicCoset::new(twiddle_log_size(&config)).circle_domain().half_coset,
+    );
+    let channel = &mut Blake2sChannel::default();
+    mix_public_inputs(channel, min, max, commitment_hash);
+    let mut commitment_scheme = CommitmentSchemeProver::<SimdBackend, Blake2sMerkleChannel>::new(config, &twiddles);
+
+    let mut tree_builder = commitment_scheme.tree_builder();
+    tree_builder.extend_evals(preprocessed_trace(width));
+    tree_builder.commit(channel);
+
+    let mut tree_builder = commitment_scheme.tree_builder();
+    tree_builder.extend_evals(range_trace(a, b));
+    tree_builder.commit(channel);
+
+    let component = range_component();
+    prove::<SimdBackend, Blake2sMerkleChannel>(&[&component], channel, commitment_scheme)
+        .map_err(|e| format!("STWO proving failed: {:?}", e))
+}
+
+/// Raíz del árbol preprocesado que corresponde a min/max (el verificador no
+/// acepta la que trae la prueba sin recalcularla)
+fn preprocessed_root(width: u128, config: PcsConfig) -> stwo::core::vcs::blake2_hash::Blake2sHash {
+    let twiddles = SimdBackend::precompute_twiddles(
+        CanonicCoset::new(twiddle_log_size(&config)).circle_domain().half_coset,
+    );
+    let mut commitment_scheme = CommitmentSchemeProver::<SimdBackend, Blake2sMerkleChannel>::new(config, &twiddles);
+    let mut tree_builder = commitment_scheme.tree_builder();
+    tree_builder.extend_evals(preprocessed_trace(width));
+    tree_builder.commit(&mut Blake2sChannel::default());
+    commitment_scheme.roots()[0]
+}
+
+/// Ejecuta el verificador de STWO sobre la prueba reconstruida
+pub fn verify_range_air(
+    proof: StarkProof<Blake2sMerkleHasher>,
+    min: U256,
+    max: U256,
+    commitment_hash: &[u8; 32],
+    config: PcsConfig,
+) -> Result<(), String> {
+    let width = range_width(min, max)?;
+    let proof_config = &proof.0.config;
+    if proof_config.pow_bits != config.pow_bits
+        || proof_config.fri_config.log_blowup_factor != config.fri_config.log_blowup_factor
+        || proof_config.fri_config.log_last_layer_degree_bound != config.fri_config.log_last_layer_degree_bound
+        || proof_config.fri_config.n_queries != config.fri_config.n_queries
+    {
+        return Err("STARK proof uses different security parameters".to_string());
+    }
+
+    let component = range_component();
+    let sizes = component.trace_log_degree_bounds();
+    let commitments = proof.0.commitments.0.clone();
+    if commitments.len() != RANGE_AIR_TREES || proof.0.sampled_values.len() != RANGE_AIR_TREES {
+        return Err(format!("STARK proof must have {} trees, got {}", RANGE_AIR_TREES, commitments.len()));
+    }
+    for (tree, tree_sizes) in sizes.iter().enumerate() {
+        if proof.0.sampled_values[tree].len() != tree_sizes.len() {
+            return Err(format!("STARK proof tree {} has the wrong number of columns", tree));
+        }
+    }
+    if commitments[0] != preprocessed_root(width, config) {
+        return Err("Preprocessed trace does not match the public range".to_string());
+    }
+
+    let channel = &mut Blake2sChannel::default();
+    mix_public_inputs(channel, min, max, commitment_hash);
+    let commitment_scheme = &mut CommitmentSchemeVerifier::<Blake2sMerkleChannel>::new(config);
+    commitment_scheme.commit(commitments[0], &sizes[0], channel);
+    commitment_scheme.commit(commitments[1], &sizes[1], channel);
+
+    verify(&[&component], channel, commitment_scheme, proof)
+        .map_err(|e| format!("STWO verification failed: {}", e))
+}
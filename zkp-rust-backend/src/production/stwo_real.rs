@@ -1,13 +1,8 @@
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
-// Importaciones condicionales de STWO
 #[cfg(feature = "real-stwo")]
-use stwo::core::{
-    fields::m31::M31,
-    vcs::blake2_hash::Blake2sHasher,
-};
+use super::stwo_range_air::{prove_range_air, verify_range_air, RANGE_AIR_LOG_ROWS};
 
 /// Estructura para range proof usando STWO real
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +16,10 @@ pub struct RealStwoRangeProof {
     pub generation_time_ms: u32,
     pub m31_field_elements: Vec<String>,
     pub circle_points: Vec<String>,
+    /// Prueba STWO completa (commitments, muestras, capas FRI, PoW) si el prover la produjo
+    #[wasm_bindgen(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stark_proof: Option<crate::VersionedStarkProof>,
 }
 
 #[wasm_bindgen]
@@ -45,18 +44,40 @@ impl RealStwoRangeProof {
             generation_time_ms,
             m31_field_elements,
             circle_points,
+            stark_proof: None,
         }
     }
 }
 
-/// Genera un range proof usando STWO real
+/// Genera un range proof usando STWO real (parámetros de seguridad por defecto)
 #[cfg(feature = "real-stwo")]
 pub fn generate_real_stwo_range_proof(
     amount_wei: &str,
     nonce: &str,
     min_amount: &str,
     max_amount: &str,
+    commitment_hash: &str,
 ) -> Result<RealStwoRangeProof, JsValue> {
+    generate_real_stwo_range_proof_with_params(
+        amount_wei, nonce, min_amount, max_amount, commitment_hash, &crate::SecurityParams::default()
+    )
+}
+
+/// Genera un range proof usando STWO real con blowup, consultas, PoW y última capa configurables.
+/// Las entradas públicas son `[min, max, commitment_hash]`: el monto queda en la traza del
+/// prover y el commitment en el canal, así la prueba no sirve para otro commitment
+#[cfg(feature = "real-stwo")]
+pub fn generate_real_stwo_range_proof_with_params(
+    amount_wei: &str,
+    _nonce: &str,
+    min_amount: &str,
+    max_amount: &str,
+    commitment_hash: &str,
+    security_params: &crate::SecurityParams,
+) -> Result<RealStwoRangeProof, JsValue> {
+    security_params.validate()?;
+    let pcs_config = security_params.pcs_config();
+    let security = security_params.estimate(RANGE_AIR_LOG_ROWS)?;
     // Use WASM-compatible time measurement
     #[cfg(target_arch = "wasm32")]
     let start_time = web_sys::window()
@@ -68,54 +89,24 @@ pub fn generate_real_stwo_range_proof(
     let start_time = std::time::Instant::now();
     
     crate::console_log!("🚀 Iniciando generación STWO REAL");
-    crate::console_log!("🔢 Amount: {} wei", amount_wei);
-    crate::console_log!("🔑 Nonce: {}", nonce);
+    crate::console_log!("🛡️ PCS: blowup=2^{}, queries={}, pow={} bits → {} bits conjeturados, {} probados",
+                        pcs_config.fri_config.log_blowup_factor, pcs_config.fri_config.n_queries,
+                        pcs_config.pow_bits, security.conjectured_bits, security.proven_bits);
     
-    // Parse amounts with better error handling for large numbers
-    let amount = amount_wei.parse::<u128>().map_err(|e| format!("Invalid amount: {}", e))?;
-    let min = min_amount.parse::<u128>().map_err(|e| format!("Invalid min_amount: {}", e))?;
-    let max = max_amount.parse::<u128>().map_err(|e| format!("Invalid max_amount: {}", e))?;
+    let amount = crate::parse_amount(amount_wei, "amount")?;
+    let min = crate::parse_amount(min_amount, "min_amount")?;
+    let max = crate::parse_amount(max_amount, "max_amount")?;
     
     // Validate range
-        if amount < min || amount > max {
+    if amount < min || amount > max {
         return Err(format!("Amount {} is outside valid range [{}, {}]", amount, min, max).into());
     }
     
-    // Convert to u32 for M31 field operations (M31 uses 31-bit values)
-    let amount_m31 = (amount % (1u128 << 31)) as u32;
-    
-    // Generar elementos M31 reales usando STWO
-    let mut m31_elements = Vec::new();
-    let mut circle_points = Vec::new();
-    
-    // Crear elementos M31 basados en el amount y nonce
-    let base_element = M31::from(amount_m31);
-    let nonce_element = M31::from(nonce.len() as u32);
-    
-    // Generar secuencia de elementos M31 para el proof
-    for i in 0..8 {
-        let element = base_element + M31::from(i as u32) * nonce_element;
-        m31_elements.push(format!("M31({})", element.0));
-    }
-    
-    // Generar puntos del círculo usando M31 directamente
-    for i in 0..4 {
-        let point_x = base_element + M31::from(i as u32);
-        let point_y = base_element * M31::from(i as u32 + 1);
-        circle_points.push(format!("(M31({}), M31({}))", point_x.0, point_y.0));
-    }
-    
-    // Generar evaluaciones del círculo (simuladas pero usando estructura real)
-    let mut circle_evaluations = Vec::new();
-    for i in 0..16 {
-        let eval = base_element + M31::from(i as u32);
-        circle_evaluations.push(format!("eval_{}", eval.0));
-        
-        // Placeholder para hashing real (en implementación completa usaríamos Blake2s)
-        let _hasher = Blake2sHasher::new();
-        let _hash_input = format!("eval_{}_{}", eval.0, i);
-        // En producción real: hasher.update(_hash_input.as_bytes());
-    }
+    // Traza, commitments, composición y FRI los hace el prover de STWO con esta configuración
+    let prove_start = crate::now_ms();
+    let proof = prove_range_air(amount, min, max, &commitment_hash_bytes(commitment_hash)?, pcs_config)?;
+    crate::record_phase_ms(crate::ProofPhase::Fri, crate::now_ms() - prove_start);
+    let stark_proof = crate::VersionedStarkProof::from_stwo(&proof);
     
     // Calculate generation time in WASM-compatible way
     #[cfg(target_arch = "wasm32")]
@@ -130,154 +121,133 @@ pub fn generate_real_stwo_range_proof(
     #[cfg(not(target_arch = "wasm32"))]
     let generation_time = start_time.elapsed().as_millis() as u32;
     
-    // Crear el proof data con formato de STWO real
-    let proof_data = format!(
-        "stwo_circle_stark_proof_v2:amount_{}_nonce_{}_elements_{}_time_{}ms",
-        amount_m31, nonce, m31_elements.len(), generation_time
-    );
-    
-        let public_inputs = vec![
-        amount_wei.to_string(),
-        min_amount.to_string(),
-        max_amount.to_string(),
-        format!("range_valid_{}", amount >= min && amount <= max),
-    ];
-    
-    let proof_size = proof_data.len() + 
-                    public_inputs.iter().map(|s| s.len()).sum::<usize>() +
-                    circle_evaluations.iter().map(|s| s.len()).sum::<usize>();
+    let public_inputs = vec![min.to_string(), max.to_string(), commitment_hash.to_string()];
+    let wire = stark_proof.to_range_proof(public_inputs.clone())?;
     
     let real_proof = RealStwoRangeProof {
-            proof_data,
-            public_inputs,
-            circle_evaluations,
-        stark_config: "STWO v1.0.0 Circle STARK Configuration".to_string(),
-        proof_size_bytes: proof_size as u32,
+        proof_data: wire.proof_data[0].clone(),
+        public_inputs,
+        circle_evaluations: wire.circle_evaluations,
+        stark_config: serde_json::to_string(security_params)
+            .map_err(|e| format!("Error serializing security params: {}", e))?,
+        proof_size_bytes: stark_proof.size_bytes()? as u32,
         generation_time_ms: generation_time,
-        m31_field_elements: m31_elements,
-        circle_points,
+        m31_field_elements: stark_proof.queried_values.iter().flatten()
+            .map(|value| format!("M31({})", value))
+            .collect(),
+        circle_points: Vec::new(),
+        stark_proof: Some(stark_proof),
     };
     
     crate::console_log!("✅ STWO REAL proof generado en {}ms", generation_time);
-    crate::console_log!("📊 Proof size: {} bytes", proof_size);
-    crate::console_log!("🔢 M31 elements: {}", real_proof.m31_field_elements.len());
-    crate::console_log!("⭕ Circle points: {}", real_proof.circle_points.len());
+    crate::console_log!("📊 Proof size: {} bytes", real_proof.proof_size_bytes);
+    crate::console_log!("🔢 Queried M31 values: {}", real_proof.m31_field_elements.len());
     
     Ok(real_proof)
 }
 
-/// Verifica un range proof usando STWO real
+/// Verifica un range proof usando STWO real con los parámetros de seguridad por defecto
 #[cfg(feature = "real-stwo")]
 pub fn verify_real_stwo_range_proof(proof: &RealStwoRangeProof) -> Result<bool, JsValue> {
+    verify_real_stwo_range_proof_with_params(proof, &crate::SecurityParams::default())
+}
+
+/// Verifica un range proof ejecutando el verificador de STWO. Los parámetros los
+/// fija el verificador: `stark_config` lo escribe el prover y no se usa
+#[cfg(feature = "real-stwo")]
+pub fn verify_real_stwo_range_proof_with_params(
+    proof: &RealStwoRangeProof,
+    security_params: &crate::SecurityParams,
+) -> Result<bool, JsValue> {
     crate::console_log!("🔍 Verificando STWO REAL proof...");
-    
-    // Verificar formato del proof data
-    if !proof.proof_data.starts_with("stwo_circle_stark_proof_v2:") {
-        crate::console_log!("❌ Invalid proof format");
-        return Ok(false);
-    }
-    
-    // Verificar que tenemos los elementos necesarios
-    if proof.m31_field_elements.is_empty() || proof.circle_points.is_empty() {
-        crate::console_log!("❌ Missing M31 elements or circle points");
-        return Ok(false);
-    }
-    
-    // Verificar formato de elementos M31
-    for element in &proof.m31_field_elements {
-        if !element.starts_with("M31(") || !element.ends_with(")") {
-            crate::console_log!("❌ Invalid M31 element format: {}", element);
+    let stark_proof = match &proof.stark_proof {
+        Some(stark_proof) => stark_proof,
+        None => {
+            crate::console_log!("❌ Missing STARK proof");
             return Ok(false);
         }
-    }
-    
-    // Verificar public inputs
-    if proof.public_inputs.len() < 3 {
-        crate::console_log!("❌ Insufficient public inputs");
-        return Ok(false);
-    }
-    
-    // En una implementación completa, aquí verificaríamos:
-    // 1. Las evaluaciones del polinomio en el círculo
-    // 2. Los commitments FRI
-    // 3. La validez criptográfica del STARK
-    
-    crate::console_log!("✅ STWO REAL proof verificado");
-    crate::console_log!("🔢 Verified {} circle evaluations", proof.circle_evaluations.len());
-    crate::console_log!("⭕ Verified {} M31 elements", proof.m31_field_elements.len());
-    crate::console_log!("🎯 Verified {} circle points", proof.circle_points.len());
-    
-    Ok(true)
-}
-
-// Fallbacks para cuando no está habilitado real-stwo
-#[cfg(not(feature = "real-stwo"))]
-pub fn generate_real_stwo_range_proof(
-    _amount_wei: &str,
-    _nonce: &str,
-    _min_amount: &str,
-    _max_amount: &str,
-) -> Result<RealStwoRangeProof, JsValue> {
-    Err("real-stwo feature not enabled".into())
+    };
+    let range_proof = stark_proof.to_range_proof(proof.public_inputs.clone())?;
+    Ok(verify_stwo_range_air_proof(&range_proof, security_params)?)
 }
 
-#[cfg(not(feature = "real-stwo"))]
-pub fn verify_real_stwo_range_proof(_proof: &RealStwoRangeProof) -> Result<bool, JsValue> {
-    Err("real-stwo feature not enabled".into())
+/// Reconstruye la prueba STWO del formato de cable y la pasa por el verificador de STWO
+#[cfg(feature = "real-stwo")]
+pub fn verify_stwo_range_air_proof(
+    range_proof: &crate::CircleStarkRangeProof,
+    security_params: &crate::SecurityParams,
+) -> Result<bool, String> {
+    security_params.validate()?;
+    let (min, max, commitment_hash) = match range_proof.public_inputs.as_slice() {
+        [min, max, commitment_hash] => (
+            crate::parse_amount(min, "min_amount")?,
+            crate::parse_amount(max, "max_amount")?,
+            commitment_hash_bytes(commitment_hash)?,
+        ),
+        _ => {
+            crate::console_log!("❌ Range proof public inputs must be [min, max, commitment_hash]");
+            return Ok(false);
+        }
+    };
+    let proof = crate::VersionedStarkProof::from_range_proof(range_proof)?.to_stwo()?;
+    match verify_range_air(proof, min, max, &commitment_hash, security_params.pcs_config()) {
+        Ok(()) => {
+            crate::console_log!("✅ STWO REAL proof verificado");
+            Ok(true)
+        }
+        Err(e) => {
+            crate::console_log!("❌ {}", e);
+            Ok(false)
+        }
+    }
 }
 
-/// Obtiene información de rendimiento de STWO real
+/// Hash del commitment (32 bytes hex) que se mezcla en el canal
 #[cfg(feature = "real-stwo")]
-pub fn get_real_stwo_info() -> JsValue {
-    let stats = HashMap::from([
-        ("proof_generation_time_ms", "~2000-5000 (REAL STWO)"),
-        ("proof_size_bytes", "~8192-16384 (REAL STWO)"),
-        ("verification_time_ms", "~100-500 (REAL STWO)"),
-        ("supported_range", "0.001 - 1000 STRK"),
-        ("merkle_tree_capacity", "~1M operations"),
-        ("library_used", "STWO v1.0.0 - StarkWare Official"),
-        ("field_arithmetic", "M31 (2^31 - 1) with QM31 extension"),
-        ("stark_type", "Circle STARKs with FRI"),
-        ("security_level", "128-bit cryptographic security"),
-        ("status", "✅ PRODUCTION READY - CRYPTOGRAPHICALLY SECURE"),
-        ("features", "Real Circle STARKs, M31 Field, Blake2s Hash"),
-    ]);
-    
-    let json_string = serde_json::to_string(&stats).unwrap();
-    JsValue::from_str(&json_string)
+fn commitment_hash_bytes(commitment_hash: &str) -> Result<[u8; 32], String> {
+    hex::decode(commitment_hash.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| format!("Invalid commitment hash: {}", commitment_hash))
 }
 
-#[cfg(not(feature = "real-stwo"))]
+/// Obtiene información de STWO real con las medidas del registro de estadísticas
+#[cfg(feature = "real-stwo")]
 pub fn get_real_stwo_info() -> JsValue {
-    let error = HashMap::from([
-        ("error", "real-stwo feature not enabled"),
-        ("solution", "Compile with --features real-stwo"),
-    ]);
+    let security_params = crate::SecurityParams::default();
+    let stats = serde_json::json!({
+        "supported_range": "0.001 - 1000 STRK",
+        "merkle_tree_capacity": "~1M operations",
+        "library_used": "STWO - StarkWare Official",
+        "field_arithmetic": "M31 (2^31 - 1) with QM31 extension",
+        "stark_type": "Circle STARKs with FRI",
+        "features": "Real Circle STARKs, M31 Field, Blake2s Hash",
+        "security_params": security_params,
+        "security": security_params.estimate(RANGE_AIR_LOG_ROWS).ok(),
+        "measured": crate::performance_stats(),
+    });
     
-    let json_string = serde_json::to_string(&error).unwrap();
-    JsValue::from_str(&json_string)
+    JsValue::from_str(&stats.to_string())
 }
 
 /// Confirma que estamos usando producción real
 #[cfg(feature = "real-stwo")]
 pub fn confirm_production_usage() {
     crate::console_log!("✅ CONFIRMADO: Usando STWO REAL para producción");
-    crate::console_log!("🔐 Seguridad criptográfica: 128-bit");
+    if let Ok(security) = crate::SecurityParams::default().estimate(RANGE_AIR_LOG_ROWS) {
+        crate::console_log!("🔐 Seguridad criptográfica: {} bits conjeturados, {} bits probados",
+                            security.conjectured_bits, security.proven_bits);
+    }
     crate::console_log!("⚡ Field: M31 (2^31 - 1)");
     crate::console_log!("🌀 Circle STARKs activados");
 }
 
-#[cfg(not(feature = "real-stwo"))]
-pub fn confirm_production_usage() {
-    crate::console_log!("❌ ERROR: STWO real no está habilitado");
-}
-
 /// Advierte sobre uso de legacy
 #[cfg(feature = "mock-stwo")]
 pub fn warn_legacy_usage() {
     crate::console_log!("⚠️ ADVERTENCIA: Usando implementación MOCK");
     crate::console_log!("🚨 NO ES SEGURO para producción");
-    crate::console_log!("💡 Use --features real-stwo para producción");
+    crate::console_log!("💡 Use --no-default-features --features real-stwo para producción");
 }
 
 #[cfg(not(feature = "mock-stwo"))]
@@ -286,22 +256,13 @@ pub fn warn_legacy_usage() {
 }
 
 /// Convierte un RealStwoRangeProof a CircleStarkRangeProof para compatibilidad
+/// mediante la serialización versionada (ida y vuelta con `to_stwo`)
 #[cfg(feature = "real-stwo")]
 pub fn convert_real_to_compatible_proof(real_proof: &RealStwoRangeProof) -> Result<crate::CircleStarkRangeProof, JsValue> {
-    use crate::CircleStarkRangeProof;
-    
-    // Extraer datos del proof real para crear estructura compatible
-    let compatible_proof = CircleStarkRangeProof {
-        proof_data: vec![real_proof.proof_data.clone()],
-        public_inputs: real_proof.public_inputs.clone(),
-        circle_evaluations: real_proof.circle_evaluations.clone(),
-        fri_commitments: real_proof.m31_field_elements.clone(),
-    };
-    
-    crate::console_log!("🔄 Converted REAL STWO proof to compatible structure");
-    crate::console_log!("📊 Real proof has {} M31 elements and {} circle points", 
-                        real_proof.m31_field_elements.len(), 
-                        real_proof.circle_points.len());
-    
+    let stark_proof = real_proof.stark_proof.as_ref()
+        .ok_or("REAL STWO proof without serialized STARK proof")?;
+    let compatible_proof = stark_proof.to_range_proof(real_proof.public_inputs.clone())?;
+    crate::console_log!("🔄 Serialized REAL STWO proof (format v{}, {} bytes)",
+                        stark_proof.version, stark_proof.size_bytes()?);
     Ok(compatible_proof)
-}
\ No newline at end of file
+}
@@ -0,0 +1,396 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use serde::{Deserialize, Serialize};
+use ark_bn254::G1Projective;
+use crate::value_commitment::point_to_hex;
+use crate::{
+    build_anonymous_set_merkle_proof, commit_amount_for_config, deposit_note_commitment,
+    encrypt_receiver_metadata, generate_backend_range_proof, generate_nullifier_for_domain, note_leaf,
+    parse_amount, parse_proof_config, sign_statement, time_phase, CeaserZKProof, CircleStarkRangeProof, MerkleProof,
+    PedersenCommitment, ProofPhase, ProofRecorder, TransferBinding, ZKProofConfig,
+};
+
+/// Etapas de la generación de una prueba CEASER, en orden de ejecución.
+///
+/// La range proof (traza, commit y FRI) es una sola etapa indivisible: el
+/// progreso salta de 5% a 75% y una cancelación pedida mientras corre se
+/// aplica al terminarla, antes del nullifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProofStage {
+    Commitment,
+    RangeProof,
+    Nullifier,
+    Merkle,
+    Metadata,
+    Serialization,
+}
+
+impl ProofStage {
+    pub const ALL: [ProofStage; 6] = [
+        ProofStage::Commitment,
+        ProofStage::RangeProof,
+        ProofStage::Nullifier,
+        ProofStage::Merkle,
+        ProofStage::Metadata,
+        ProofStage::Serialization,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProofStage::Commitment => "commitment",
+            ProofStage::RangeProof => "range_proof",
+            ProofStage::Nullifier => "nullifier",
+            ProofStage::Merkle => "merkle",
+            ProofStage::Metadata => "metadata",
+            ProofStage::Serialization => "serialization",
+        }
+    }
+
+    /// Porcentaje completado al terminar la etapa (la range proof domina el
+    /// coste y no informa de progreso intermedio)
+    pub fn percent_done(&self) -> u8 {
+        match self {
+            ProofStage::Commitment => 5,
+            ProofStage::RangeProof => 75,
+            ProofStage::Nullifier => 80,
+            ProofStage::Merkle => 90,
+            ProofStage::Metadata => 95,
+            ProofStage::Serialization => 100,
+        }
+    }
+}
+
+/// Token de cancelación compartido entre quien lanza la prueba y quien la
+/// cancela. Se comprueba entre etapas: una etapa en curso no se interrumpe
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+#[wasm_bindgen]
+impl CancellationToken {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Generación de una prueba CEASER por etapas, para poder informar del
+/// progreso, cancelar o ceder el hilo entre una etapa y la siguiente
+pub struct CeaserProofJob {
+    amount_wei: String,
+    nonce: String,
+    user_secret: String,
+    binding: TransferBinding,
+    config: ZKProofConfig,
+    recorder: ProofRecorder,
+    next_stage: usize,
+    cancelled: bool,
+    commitment: Option<(PedersenCommitment, Option<u32>)>,
+    /// Punto N de la nota (solo desde v1, en v0 la hoja es el commitment)
+    note_commitment: Option<G1Projective>,
+    range_proof: Option<CircleStarkRangeProof>,
+    nullifier: Option<String>,
+    merkle_proof: Option<MerkleProof>,
+    encrypted_metadata: Option<String>,
+    proof_json: Option<String>,
+}
+
+impl CeaserProofJob {
+    pub fn new(
+        amount_wei: &str,
+        nonce: &str,
+        user_secret: &str,
+        receiver_address: &str,
+        relayer_address: &str,
+        fee_wei: &str,
+        config: ZKProofConfig,
+    ) -> Result<CeaserProofJob, JsValue> {
+        let fee = parse_amount(fee_wei, "fee")?;
+        let amount = parse_amount(amount_wei, "amount")?;
+        if fee > amount {
+            return Err(format!("Fee {} exceeds amount {}", fee, amount).into());
+        }
+
+        Ok(CeaserProofJob {
+            amount_wei: amount_wei.to_string(),
+            nonce: nonce.to_string(),
+            user_secret: user_secret.to_string(),
+            binding: TransferBinding {
+                recipient: receiver_address.to_string(),
+                relayer: relayer_address.to_string(),
+                fee: fee.to_string(),
+            },
+            config,
+            recorder: ProofRecorder::start_detached("ceaser_zk_proof"),
+            next_stage: 0,
+            cancelled: false,
+            commitment: None,
+            note_commitment: None,
+            range_proof: None,
+            nullifier: None,
+            merkle_proof: None,
+            encrypted_metadata: None,
+            proof_json: None,
+        })
+    }
+
+    /// Próxima etapa a ejecutar (None si la prueba está completa o cancelada)
+    pub fn next_stage(&self) -> Option<ProofStage> {
+        if self.cancelled {
+            return None;
+        }
+        ProofStage::ALL.get(self.next_stage).copied()
+    }
+
+    /// Porcentaje de la última etapa completada (0 antes de la primera)
+    pub fn percent_done(&self) -> u8 {
+        self.next_stage.checked_sub(1).map_or(0, |done| ProofStage::ALL[done].percent_done())
+    }
+
+    /// Estado terminal: no se ejecutan más etapas y se descartan los resultados
+    /// intermedios, que derivan del secreto de la nota
+    pub fn cancel(&mut self) {
+        self.cancelled = true;
+        self.commitment = None;
+        self.note_commitment = None;
+        self.range_proof = None;
+        self.nullifier = None;
+        self.merkle_proof = None;
+        self.encrypted_metadata = None;
+        self.proof_json = None;
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Ejecuta la siguiente etapa y la devuelve (None si ya no quedan). Las
+    /// fases se atribuyen a esta prueba aunque otras se intercalen en el hilo
+    pub fn run_stage(&mut self) -> Result<Option<ProofStage>, JsValue> {
+        let Some(stage) = self.next_stage() else {
+            return Ok(None);
+        };
+
+        self.recorder.resume();
+        let result = self.execute_stage(stage);
+        self.recorder.suspend();
+        result?;
+
+        self.next_stage += 1;
+        Ok(Some(stage))
+    }
+
+    fn execute_stage(&mut self, stage: ProofStage) -> Result<(), JsValue> {
+        match stage {
+            ProofStage::Commitment => {
+                crate::console_log!("🔐 Generando commitment de Pedersen...");
+                let (commitment, note_commitment) = time_phase(ProofPhase::Commitment, || {
+                    let commitment = commit_amount_for_config(&self.amount_wei, &self.nonce, &self.config)?;
+                    let note_commitment = if self.config.domain.is_legacy() {
+                        None
+                    } else {
+                        Some(deposit_note_commitment(&self.amount_wei, &self.nonce, &self.user_secret, &self.config)?.0)
+                    };
+                    Ok::<_, JsValue>((commitment, note_commitment))
+                })?;
+                self.commitment = Some(commitment);
+                self.note_commitment = note_commitment;
+            }
+            ProofStage::RangeProof => {
+                crate::console_log!("📊 Generando range proof...");
+                let (commitment, denomination_id) = self.commitment.as_ref().ok_or("Commitment stage has not run")?;
+                let (min, max) = self.config.range_bounds(*denomination_id)?;
+                self.range_proof = Some(generate_backend_range_proof(
+                    &self.amount_wei, &self.nonce, &min, &max, &commitment.commitment_hash,
+                    &self.config.security_params(),
+                )?);
+            }
+            ProofStage::Nullifier => {
+                crate::console_log!("🔑 Generando nullifier...");
+                let commitment_hash = &self.commitment()?.commitment_hash;
+                let nullifier = time_phase(ProofPhase::Commitment, || {
+                    generate_nullifier_for_domain(commitment_hash, &self.user_secret, &self.config.domain)
+                })?;
+                self.nullifier = Some(nullifier);
+            }
+            ProofStage::Merkle => {
+                let (commitment, denomination_id) = self.commitment.as_ref().ok_or("Commitment stage has not run")?;
+                let leaf_hash = match &self.note_commitment {
+                    Some(note_commitment) => note_leaf(note_commitment, *denomination_id, &self.config.domain)?.commitment_hash,
+                    None => commitment.commitment_hash.clone(),
+                };
+                self.merkle_proof = Some(build_anonymous_set_merkle_proof(&self.amount_wei, &leaf_hash)?);
+            }
+            ProofStage::Metadata => {
+                crate::console_log!("🔐 Encriptando metadata...");
+                self.encrypted_metadata = Some(encrypt_receiver_metadata(&self.binding.recipient, &self.user_secret)?);
+            }
+            ProofStage::Serialization => {
+                let proof = self.assemble_proof()?;
+                let json = time_phase(ProofPhase::Serialization, || serde_json::to_string(&proof))
+                    .map_err(|e| format!("Error serializing proof: {}", e))?;
+                self.proof_json = Some(json);
+            }
+        }
+        Ok(())
+    }
+
+    /// JSON de la prueba completa; registra las estadísticas de la ejecución
+    pub fn finish(self) -> Result<String, JsValue> {
+        let json = self.proof_json.ok_or("Proof generation has not finished")?;
+        if let Some(stats) = self.recorder.finish(json.len()) {
+            crate::console_log!("⏱️ Prueba generada en {:.1}ms ({} bytes)", stats.total_ms, stats.proof_size_bytes);
+        }
+        Ok(json)
+    }
+
+    fn commitment(&self) -> Result<&PedersenCommitment, JsValue> {
+        Ok(&self.commitment.as_ref().ok_or("Commitment stage has not run")?.0)
+    }
+
+    fn assemble_proof(&mut self) -> Result<CeaserZKProof, JsValue> {
+        let missing = |stage: ProofStage| format!("Stage {} has not run", stage.as_str());
+        let (commitment, denomination_id) = self.commitment.take().ok_or_else(|| missing(ProofStage::Commitment))?;
+        let merkle_proof = self.merkle_proof.take().ok_or_else(|| missing(ProofStage::Merkle))?;
+
+        let mut zk_proof = CeaserZKProof {
+            amount_commitment: commitment,
+            range_proof: self.range_proof.take().ok_or_else(|| missing(ProofStage::RangeProof))?,
+            nullifier: self.nullifier.take().ok_or_else(|| missing(ProofStage::Nullifier))?,
+            merkle_root: merkle_proof.root.clone(),
+            merkle_proof,
+            encrypted_metadata: self.encrypted_metadata.take().ok_or_else(|| missing(ProofStage::Metadata))?,
+            binding: self.binding.clone(),
+            statement_hash: String::new(),
+            domain: self.config.domain.clone(),
+            denomination_id,
+            spend_signature: None,
+            note_commitment: self.note_commitment.as_ref().map(point_to_hex),
+        };
+        sign_statement(&mut zk_proof, &self.user_secret)?;
+        crate::console_log!("🔗 Declaración ligada a receptor/relayer/fee: {}", zk_proof.statement_hash);
+        Ok(zk_proof)
+    }
+}
+
+/// Acepta un `CancellationToken`, un `AbortSignal` o cualquier objeto con
+/// `cancelled`/`aborted`; undefined o null significa sin cancelación
+fn is_cancelled(cancel: &JsValue) -> bool {
+    if cancel.is_undefined() || cancel.is_null() {
+        return false;
+    }
+    ["cancelled", "aborted"].iter().any(|property| {
+        js_sys::Reflect::get(cancel, &JsValue::from_str(property))
+            .map(|value| value.is_truthy())
+            .unwrap_or(false)
+    })
+}
+
+/// Comprueba la cancelación antes de la etapa y notifica `(etapa, porcentaje)` al terminarla
+fn run_reported_stage(
+    job: &mut CeaserProofJob,
+    on_progress: Option<&js_sys::Function>,
+    cancel: &JsValue,
+) -> Result<(), JsValue> {
+    if is_cancelled(cancel) {
+        job.cancel();
+        crate::console_log!("🛑 Generación de prueba cancelada");
+        return Err("Proof generation cancelled".into());
+    }
+    if let (Some(stage), Some(callback)) = (job.run_stage()?, on_progress) {
+        callback.call2(
+            &JsValue::NULL,
+            &JsValue::from_str(stage.as_str()),
+            &JsValue::from(stage.percent_done()),
+        )?;
+    }
+    Ok(())
+}
+
+/// Cede el control al event loop (setTimeout 0) para que se procesen mensajes,
+/// pintado o cancelaciones entre etapas. Vale en ventana y en Web Worker
+async fn yield_to_event_loop() -> Result<(), JsValue> {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        let set_timeout = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("setTimeout"))
+            .ok()
+            .and_then(|value| value.dyn_into::<js_sys::Function>().ok());
+        let _ = match set_timeout {
+            Some(set_timeout) => set_timeout.call2(&JsValue::NULL, &resolve, &JsValue::from(0)),
+            None => resolve.call0(&JsValue::NULL),
+        };
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await?;
+    Ok(())
+}
+
+/// Genera la prueba ZK informando del progreso con `on_progress(etapa, porcentaje)`
+/// y comprobando `cancel` entre etapas. Síncrona: en el hilo principal solo
+/// puede cancelarse desde el propio callback
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn generate_ceaser_zk_proof_with_progress(
+    amount_wei: &str,
+    nonce: &str,
+    user_secret: &str,
+    receiver_address: &str,
+    relayer_address: &str,
+    fee_wei: &str,
+    config: &JsValue,
+    on_progress: Option<js_sys::Function>,
+    cancel: &JsValue,
+) -> Result<JsValue, JsValue> {
+    console_error_panic_hook::set_once();
+    let config = parse_proof_config(config)?;
+    let mut job = CeaserProofJob::new(
+        amount_wei, nonce, user_secret, receiver_address, relayer_address, fee_wei, config,
+    )?;
+
+    while job.next_stage().is_some() {
+        run_reported_stage(&mut job, on_progress.as_ref(), cancel)?;
+    }
+    Ok(JsValue::from_str(&job.finish()?))
+}
+
+/// Variante asíncrona (Promise con el JSON de la prueba). Cede el event loop
+/// entre etapas, así que la UI sigue respondiendo y una cancelación recibida
+/// mientras tanto (p. ej. por postMessage en un Web Worker) se aplica en la
+/// siguiente etapa. El tiempo total registrado incluye esas esperas
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub async fn generate_ceaser_zk_proof_async(
+    amount_wei: String,
+    nonce: String,
+    user_secret: String,
+    receiver_address: String,
+    relayer_address: String,
+    fee_wei: String,
+    config: JsValue,
+    on_progress: Option<js_sys::Function>,
+    cancel: JsValue,
+) -> Result<JsValue, JsValue> {
+    console_error_panic_hook::set_once();
+    let config = parse_proof_config(&config)?;
+    let mut job = CeaserProofJob::new(
+        &amount_wei, &nonce, &user_secret, &receiver_address, &relayer_address, &fee_wei, config,
+    )?;
+
+    while job.next_stage().is_some() {
+        yield_to_event_loop().await?;
+        run_reported_stage(&mut job, on_progress.as_ref(), &cancel)?;
+    }
+    Ok(JsValue::from_str(&job.finish()?))
+}
@@ -0,0 +1,81 @@
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+/// Fuente de aleatoriedad de pruebas, notas y claves.
+///
+/// En producción es la entropía del sistema (getrandom, que en el navegador
+/// usa `crypto.getRandomValues`). El modo con semilla usa ChaCha20 para que la
+/// misma semilla produzca pruebas idénticas byte a byte (vectores en `proofs/`).
+/// Una semilla conocida hace los blindings predecibles: solo para tests
+pub enum ProofRng {
+    Entropy(OsRng),
+    Seeded(Box<ChaCha20Rng>),
+}
+
+impl ProofRng {
+    pub fn from_entropy() -> ProofRng {
+        ProofRng::Entropy(OsRng)
+    }
+
+    pub fn from_seed(seed: [u8; 32]) -> ProofRng {
+        ProofRng::Seeded(Box::new(ChaCha20Rng::from_seed(seed)))
+    }
+
+    /// Semilla de 32 bytes en hex (con o sin 0x)
+    pub fn from_seed_hex(seed: &str) -> Result<ProofRng, String> {
+        let seed: [u8; 32] = hex::decode(seed.trim_start_matches("0x"))
+            .map_err(|e| format!("Invalid RNG seed: {}", e))?
+            .try_into()
+            .map_err(|_| "RNG seed must be 32 bytes".to_string())?;
+        Ok(ProofRng::from_seed(seed))
+    }
+
+    /// Con semilla si la configuración la trae, si no entropía del sistema
+    #[cfg(feature = "deterministic-rng")]
+    pub fn from_config_seed(seed: Option<&str>) -> Result<ProofRng, String> {
+        match seed {
+            Some(seed) => {
+                crate::console_log!("⚠️ Modo determinista: aleatoriedad derivada de una semilla fija (solo tests)");
+                ProofRng::from_seed_hex(seed)
+            }
+            None => Ok(ProofRng::from_entropy()),
+        }
+    }
+
+    pub fn is_deterministic(&self) -> bool {
+        matches!(self, ProofRng::Seeded(_))
+    }
+}
+
+impl RngCore for ProofRng {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            ProofRng::Entropy(rng) => rng.next_u32(),
+            ProofRng::Seeded(rng) => rng.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            ProofRng::Entropy(rng) => rng.next_u64(),
+            ProofRng::Seeded(rng) => rng.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            ProofRng::Entropy(rng) => rng.fill_bytes(dest),
+            ProofRng::Seeded(rng) => rng.fill_bytes(dest),
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        match self {
+            ProofRng::Entropy(rng) => rng.try_fill_bytes(dest),
+            ProofRng::Seeded(rng) => rng.try_fill_bytes(dest),
+        }
+    }
+}
+
+impl CryptoRng for ProofRng {}
@@ -0,0 +1,148 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::{FriParams, MAX_POW_BITS};
+
+/// Bits del campo de retos (QM31 ≈ 2^124)
+pub const SECURE_FIELD_BITS: u32 = 124;
+
+/// Dominio de evaluación canónico más grande del círculo de M31
+pub const MAX_LOG_DOMAIN_SIZE: u32 = 30;
+
+/// Parámetros de seguridad del commitment scheme (los de `PcsConfig` en STWO)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecurityParams {
+    pub log_blowup_factor: u32,
+    pub n_queries: u32,
+    pub pow_bits: u32,
+    pub log_last_layer_degree_bound: u32,
+}
+
+impl Default for SecurityParams {
+    fn default() -> Self {
+        Self {
+            log_blowup_factor: 1,           // ρ = 1/2
+            n_queries: 70,                  // 70 bits de consultas (conjeturados)
+            pow_bits: 26,                   // grinding
+            log_last_layer_degree_bound: 0, // última capa constante
+        }
+    }
+}
+
+/// Nivel de seguridad estimado de una configuración
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecurityEstimate {
+    pub conjectured_bits: u32,
+    pub proven_bits: u32,
+    pub query_bits_conjectured: u32,
+    pub query_bits_proven: u32,
+    pub field_bits_conjectured: u32,
+    pub field_bits_proven: u32,
+    pub pow_bits: u32,
+    pub log_domain_size: u32,
+}
+
+impl SecurityParams {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.log_blowup_factor == 0 || self.log_blowup_factor > 16 {
+            return Err(format!("log_blowup_factor must be in 1..=16, got {}", self.log_blowup_factor));
+        }
+        if self.n_queries == 0 {
+            return Err("n_queries must be at least 1".to_string());
+        }
+        if self.pow_bits > MAX_POW_BITS {
+            return Err(format!("pow_bits must be at most {}, got {}", MAX_POW_BITS, self.pow_bits));
+        }
+        Ok(())
+    }
+
+    /// log2 del dominio de evaluación (traza × blowup) sin desbordar
+    pub fn log_domain_size(&self, log_trace_size: u32) -> Result<u32, String> {
+        log_trace_size
+            .checked_add(self.log_blowup_factor)
+            .filter(|size| *size <= MAX_LOG_DOMAIN_SIZE)
+            .ok_or_else(|| format!(
+                "Trace of 2^{} rows with blowup 2^{} exceeds the 2^{} evaluation domain",
+                log_trace_size, self.log_blowup_factor, MAX_LOG_DOMAIN_SIZE
+            ))
+    }
+
+    /// Parámetros FRI para una traza de 2^log_trace_size filas
+    pub fn fri_params(&self, log_trace_size: u32) -> Result<FriParams, String> {
+        self.validate()?;
+        let params = FriParams {
+            log_domain_size: self.log_domain_size(log_trace_size)?,
+            log_blowup: self.log_blowup_factor,
+            num_queries: self.n_queries as usize,
+            pow_bits: self.pow_bits,
+            log_last_layer_degree_bound: self.log_last_layer_degree_bound,
+        };
+        params.num_folds()?;
+        Ok(params)
+    }
+
+    /// Estima la seguridad para una traza de 2^log_trace_size filas.
+    ///
+    /// Conjeturada (ethSTARK): cada consulta aporta log_blowup bits, más el grinding,
+    /// acotado por |F|/|D|. Probada (régimen de Johnson): cada consulta aporta
+    /// ~log_blowup/2 bits, acotado por el error de la fase de commit de BCIKS20
+    /// ≈ (m + ½)^7 · |D|² / (2 · ρ^{3/2} · |F|) con m = 3
+    pub fn estimate(&self, log_trace_size: u32) -> Result<SecurityEstimate, String> {
+        let log_domain_size = self.log_domain_size(log_trace_size)?;
+        let log_blowup = self.log_blowup_factor as f64;
+
+        let query_bits_conjectured = self.n_queries.saturating_mul(self.log_blowup_factor);
+        let field_bits_conjectured = SECURE_FIELD_BITS.saturating_sub(log_domain_size);
+
+        // 1 − δ = √ρ · (1 + 1/20) por la holgura respecto a la cota de Johnson
+        let per_query_proven = log_blowup / 2.0 - (1.05f64).log2();
+        let query_bits_proven = (self.n_queries as f64 * per_query_proven).max(0.0).floor() as u32;
+        let commit_phase_loss = 7.0 * 3.5f64.log2() - 1.0 + 2.0 * log_domain_size as f64 + 1.5 * log_blowup;
+        let field_bits_proven = (SECURE_FIELD_BITS as f64 - commit_phase_loss).max(0.0).floor() as u32;
+
+        Ok(SecurityEstimate {
+            conjectured_bits: (query_bits_conjectured + self.pow_bits).min(field_bits_conjectured),
+            proven_bits: (query_bits_proven + self.pow_bits).min(field_bits_proven),
+            query_bits_conjectured,
+            query_bits_proven,
+            field_bits_conjectured,
+            field_bits_proven,
+            pow_bits: self.pow_bits,
+            log_domain_size,
+        })
+    }
+}
+
+#[cfg(feature = "real-stwo")]
+impl SecurityParams {
+    /// Configuración equivalente del commitment scheme de STWO
+    pub fn pcs_config(&self) -> stwo::core::pcs::PcsConfig {
+        stwo::core::pcs::PcsConfig {
+            pow_bits: self.pow_bits,
+            fri_config: stwo::core::fri::FriConfig::new(
+                self.log_last_layer_degree_bound,
+                self.log_blowup_factor,
+                self.n_queries as usize,
+            ),
+        }
+    }
+}
+
+/// Estima la seguridad de una configuración (JSON de SecurityParams, null = por defecto)
+#[wasm_bindgen]
+pub fn estimate_security_level(params: &JsValue, log_trace_size: u32) -> Result<String, JsValue> {
+    let params: SecurityParams = match params.as_string() {
+        Some(params_json) => serde_json::from_str(&params_json)
+            .map_err(|e| format!("Invalid security params: {}", e))?,
+        None => SecurityParams::default(),
+    };
+    params.validate()?;
+    let estimate = params.estimate(log_trace_size)?;
+
+    crate::console_log!(
+        "🛡️ Seguridad estimada: {} bits conjeturados, {} bits probados",
+        estimate.conjectured_bits, estimate.proven_bits
+    );
+
+    Ok(serde_json::to_string(&estimate)
+        .map_err(|e| format!("Error serializing security estimate: {}", e))?)
+}
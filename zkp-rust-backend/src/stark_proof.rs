@@ -0,0 +1,554 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::{qm31_from_hex, qm31_to_hex, CircleStarkRangeProof, QM31};
+
+/// Versión actual del formato binario de pruebas STARK
+pub const STARK_PROOF_FORMAT_VERSION: u16 = 1;
+
+/// Prefijo de la entrada de `proof_data` que lleva la prueba serializada
+pub const STARK_PROOF_PREFIX: &str = "stark-proof:0x";
+
+const STARK_PROOF_MAGIC: &[u8; 4] = b"CSTK";
+const HASH_BYTES: usize = 32;
+const M31_MODULUS: u32 = (1u32 << 31) - 1;
+
+/// Parámetros del commitment scheme (PcsConfig + FriConfig de STWO)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StarkProofConfig {
+    pub pow_bits: u32,
+    pub log_blowup_factor: u32,
+    pub log_last_layer_degree_bound: u32,
+    pub n_queries: u32,
+}
+
+/// Camino de autenticación Merkle: hashes hermanos + valores de columnas vecinas
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleDecommitmentData {
+    pub hash_witness: Vec<String>,
+    pub column_witness: Vec<u32>,
+}
+
+/// Capa FRI: raíz, evaluaciones hermanas (QM31) y su decommitment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FriLayerData {
+    pub commitment: String,
+    pub fri_witness: Vec<String>,
+    pub decommitment: MerkleDecommitmentData,
+}
+
+/// Prueba FRI con la forma de STWO (primera capa circular, capas de línea y
+/// polinomio final en coeficientes QM31)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StarkFriData {
+    pub first_layer: FriLayerData,
+    pub inner_layers: Vec<FriLayerData>,
+    pub last_layer_poly: Vec<String>,
+}
+
+/// Serialización versionada de una prueba STWO (CommitmentSchemeProof).
+///
+/// Por árbol (preprocesado, traza, composición…): raíz, valores muestreados
+/// fuera del dominio (QM31 por columna), valores consultados (M31) y decommitment.
+/// Se codifica en binario (`to_bytes`) y viaja en `proof_data` de
+/// `CircleStarkRangeProof`; `from_range_proof` la reconstruye para el verificador
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionedStarkProof {
+    pub version: u16,
+    pub config: StarkProofConfig,
+    pub commitments: Vec<String>,
+    pub sampled_values: Vec<Vec<Vec<String>>>,
+    pub decommitments: Vec<MerkleDecommitmentData>,
+    pub queried_values: Vec<Vec<u32>>,
+    pub proof_of_work: u64,
+    pub fri_proof: StarkFriData,
+}
+
+impl VersionedStarkProof {
+    /// Comprueba la forma de la prueba: versión, tamaños y elementos canónicos
+    pub fn validate(&self) -> Result<(), String> {
+        if self.version != STARK_PROOF_FORMAT_VERSION {
+            return Err(format!("Unsupported STARK proof version {}", self.version));
+        }
+        let num_trees = self.commitments.len();
+        if num_trees == 0 {
+            return Err("STARK proof has no commitments".to_string());
+        }
+        if self.sampled_values.len() != num_trees
+            || self.decommitments.len() != num_trees
+            || self.queried_values.len() != num_trees
+        {
+            return Err(format!(
+                "STARK proof trees mismatch: {} commitments, {} sampled, {} decommitments, {} queried",
+                num_trees, self.sampled_values.len(), self.decommitments.len(), self.queried_values.len()
+            ));
+        }
+
+        for commitment in self.layer_commitments() {
+            decode_hash(commitment)?;
+        }
+        for decommitment in self.decommitments.iter().chain(self.fri_layers().map(|layer| &layer.decommitment)) {
+            validate_decommitment(decommitment)?;
+        }
+        for values in self.sampled_values.iter().flatten().flatten()
+            .chain(self.fri_layers().flat_map(|layer| layer.fri_witness.iter()))
+            .chain(self.fri_proof.last_layer_poly.iter())
+        {
+            qm31_from_hex(values)?;
+        }
+        for value in self.queried_values.iter().flatten() {
+            check_m31(*value)?;
+        }
+
+        let last_layer_len = self.fri_proof.last_layer_poly.len();
+        if !last_layer_len.is_power_of_two()
+            || last_layer_len > 1usize << self.config.log_last_layer_degree_bound.min(31)
+        {
+            return Err(format!(
+                "Last FRI layer has {} coefficients, bound is 2^{}",
+                last_layer_len, self.config.log_last_layer_degree_bound
+            ));
+        }
+        Ok(())
+    }
+
+    /// Raíces de los árboles del commitment scheme seguidas de las raíces FRI
+    pub fn layer_commitments(&self) -> impl Iterator<Item = &String> {
+        self.commitments.iter().chain(self.fri_layers().map(|layer| &layer.commitment))
+    }
+
+    fn fri_layers(&self) -> impl Iterator<Item = &FriLayerData> {
+        std::iter::once(&self.fri_proof.first_layer).chain(self.fri_proof.inner_layers.iter())
+    }
+
+    /// Codificación binaria: magic "CSTK", versión, config y campos con longitud prefijada
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        self.validate()?;
+        let mut writer = ProofWriter::default();
+        writer.bytes.extend_from_slice(STARK_PROOF_MAGIC);
+        writer.put_u16(self.version);
+        writer.put_u32(self.config.pow_bits);
+        writer.put_u32(self.config.log_blowup_factor);
+        writer.put_u32(self.config.log_last_layer_degree_bound);
+        writer.put_u32(self.config.n_queries);
+
+        writer.put_len(self.commitments.len());
+        for commitment in &self.commitments {
+            writer.put_hash(commitment)?;
+        }
+        for tree in &self.sampled_values {
+            writer.put_len(tree.len());
+            for column in tree {
+                writer.put_qm31_vec(column)?;
+            }
+        }
+        for decommitment in &self.decommitments {
+            writer.put_decommitment(decommitment)?;
+        }
+        for values in &self.queried_values {
+            writer.put_m31_vec(values);
+        }
+        writer.put_u64(self.proof_of_work);
+
+        writer.put_fri_layer(&self.fri_proof.first_layer)?;
+        writer.put_len(self.fri_proof.inner_layers.len());
+        for layer in &self.fri_proof.inner_layers {
+            writer.put_fri_layer(layer)?;
+        }
+        writer.put_qm31_vec(&self.fri_proof.last_layer_poly)?;
+        Ok(writer.bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<VersionedStarkProof, String> {
+        let mut reader = ProofReader { bytes, position: 0 };
+        if reader.take(STARK_PROOF_MAGIC.len())? != STARK_PROOF_MAGIC {
+            return Err("Invalid STARK proof magic".to_string());
+        }
+        let version = reader.u16()?;
+        if version != STARK_PROOF_FORMAT_VERSION {
+            return Err(format!("Unsupported STARK proof version {}", version));
+        }
+        let config = StarkProofConfig {
+            pow_bits: reader.u32()?,
+            log_blowup_factor: reader.u32()?,
+            log_last_layer_degree_bound: reader.u32()?,
+            n_queries: reader.u32()?,
+        };
+
+        let num_trees = reader.len(HASH_BYTES)?;
+        let commitments = (0..num_trees).map(|_| reader.hash()).collect::<Result<Vec<_>, _>>()?;
+        let mut sampled_values = Vec::with_capacity(num_trees);
+        for _ in 0..num_trees {
+            let num_columns = reader.len(4)?;
+            sampled_values.push((0..num_columns).map(|_| reader.qm31_vec()).collect::<Result<Vec<_>, _>>()?);
+        }
+        let decommitments = (0..num_trees).map(|_| reader.decommitment()).collect::<Result<Vec<_>, _>>()?;
+        let queried_values = (0..num_trees).map(|_| reader.m31_vec()).collect::<Result<Vec<_>, _>>()?;
+        let proof_of_work = reader.u64()?;
+
+        let first_layer = reader.fri_layer()?;
+        let num_inner_layers = reader.len(HASH_BYTES)?;
+        let inner_layers = (0..num_inner_layers).map(|_| reader.fri_layer()).collect::<Result<Vec<_>, _>>()?;
+        let last_layer_poly = reader.qm31_vec()?;
+
+        if reader.position != bytes.len() {
+            return Err(format!("{} trailing bytes after STARK proof", bytes.len() - reader.position));
+        }
+
+        let proof = VersionedStarkProof {
+            version,
+            config,
+            commitments,
+            sampled_values,
+            decommitments,
+            queried_values,
+            proof_of_work,
+            fri_proof: StarkFriData { first_layer, inner_layers, last_layer_poly },
+        };
+        proof.validate()?;
+        Ok(proof)
+    }
+
+    /// Empaqueta la prueba en el formato de cable de `CircleStarkRangeProof`.
+    /// `proof_data[0]` es la codificación completa; `fri_commitments` y
+    /// `circle_evaluations` son vistas (raíces y valores muestreados) para el frontend
+    pub fn to_range_proof(&self, public_inputs: Vec<String>) -> Result<CircleStarkRangeProof, String> {
+        let bytes = self.to_bytes()?;
+        Ok(CircleStarkRangeProof {
+            proof_data: vec![format!("{}{}", STARK_PROOF_PREFIX, hex::encode(bytes))],
+            public_inputs,
+            circle_evaluations: self.sampled_values.iter().flatten().flatten().cloned().collect(),
+            fri_commitments: self.layer_commitments().cloned().collect(),
+            fri_proof: None,
+        })
+    }
+
+    /// Reconstruye la prueba desde el formato de cable y comprueba que las vistas coinciden
+    pub fn from_range_proof(range_proof: &CircleStarkRangeProof) -> Result<VersionedStarkProof, String> {
+        let encoded = range_proof
+            .proof_data
+            .iter()
+            .find_map(|entry| entry.strip_prefix(STARK_PROOF_PREFIX))
+            .ok_or("Range proof does not carry a serialized STARK proof")?;
+        let bytes = hex::decode(encoded).map_err(|e| format!("Invalid STARK proof hex: {}", e))?;
+        let proof = VersionedStarkProof::from_bytes(&bytes)?;
+
+        if !range_proof.fri_commitments.iter().eq(proof.layer_commitments()) {
+            return Err("fri_commitments do not match the serialized STARK proof".to_string());
+        }
+        if !range_proof.circle_evaluations.iter().eq(proof.sampled_values.iter().flatten().flatten()) {
+            return Err("circle_evaluations do not match the serialized STARK proof".to_string());
+        }
+        Ok(proof)
+    }
+
+    /// Tamaño de la codificación binaria
+    pub fn size_bytes(&self) -> Result<usize, String> {
+        Ok(self.to_bytes()?.len())
+    }
+}
+
+/// Extrae la prueba STARK serializada de una range proof (JSON de VersionedStarkProof)
+#[wasm_bindgen]
+pub fn decode_stark_proof(range_proof_json: &str) -> Result<String, JsValue> {
+    let range_proof: CircleStarkRangeProof = serde_json::from_str(range_proof_json)
+        .map_err(|e| format!("Error parsing range proof: {}", e))?;
+    let proof = VersionedStarkProof::from_range_proof(&range_proof)?;
+    Ok(serde_json::to_string(&proof)
+        .map_err(|e| format!("Error serializing STARK proof: {}", e))?)
+}
+
+fn check_m31(value: u32) -> Result<(), String> {
+    if value >= M31_MODULUS {
+        return Err(format!("M31 value {} is not canonical", value));
+    }
+    Ok(())
+}
+
+fn decode_hash(value: &str) -> Result<[u8; HASH_BYTES], String> {
+    let bytes = hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid hash hex: {}", e))?;
+    bytes.try_into().map_err(|bytes: Vec<u8>| format!("Hash must be {} bytes, got {}", HASH_BYTES, bytes.len()))
+}
+
+fn validate_decommitment(decommitment: &MerkleDecommitmentData) -> Result<(), String> {
+    for hash in &decommitment.hash_witness {
+        decode_hash(hash)?;
+    }
+    for value in &decommitment.column_witness {
+        check_m31(*value)?;
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+struct ProofWriter {
+    bytes: Vec<u8>,
+}
+
+impl ProofWriter {
+    fn put_u16(&mut self, value: u16) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_u64(&mut self, value: u64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_len(&mut self, len: usize) {
+        self.put_u32(len as u32);
+    }
+
+    fn put_hash(&mut self, hash: &str) -> Result<(), String> {
+        self.bytes.extend_from_slice(&decode_hash(hash)?);
+        Ok(())
+    }
+
+    fn put_m31_vec(&mut self, values: &[u32]) {
+        self.put_len(values.len());
+        for value in values {
+            self.put_u32(*value);
+        }
+    }
+
+    fn put_qm31_vec(&mut self, values: &[String]) -> Result<(), String> {
+        self.put_len(values.len());
+        for value in values {
+            for limb in qm31_from_hex(value)?.to_m31_array() {
+                self.put_u32(limb);
+            }
+        }
+        Ok(())
+    }
+
+    fn put_decommitment(&mut self, decommitment: &MerkleDecommitmentData) -> Result<(), String> {
+        self.put_len(decommitment.hash_witness.len());
+        for hash in &decommitment.hash_witness {
+            self.put_hash(hash)?;
+        }
+        self.put_m31_vec(&decommitment.column_witness);
+        Ok(())
+    }
+
+    fn put_fri_layer(&mut self, layer: &FriLayerData) -> Result<(), String> {
+        self.put_hash(&layer.commitment)?;
+        self.put_qm31_vec(&layer.fri_witness)?;
+        self.put_decommitment(&layer.decommitment)
+    }
+}
+
+struct ProofReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ProofReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self.position.checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or("Truncated STARK proof")?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().expect("2 bytes")))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().expect("4 bytes")))
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().expect("8 bytes")))
+    }
+
+    /// Longitud de un vector; se acota por los bytes restantes para no reservar de más
+    fn len(&mut self, min_item_bytes: usize) -> Result<usize, String> {
+        let len = self.u32()? as usize;
+        if len.saturating_mul(min_item_bytes) > self.bytes.len() - self.position {
+            return Err(format!("STARK proof length {} exceeds remaining bytes", len));
+        }
+        Ok(len)
+    }
+
+    fn hash(&mut self) -> Result<String, String> {
+        Ok(format!("0x{}", hex::encode(self.take(HASH_BYTES)?)))
+    }
+
+    fn m31(&mut self) -> Result<u32, String> {
+        let value = self.u32()?;
+        check_m31(value)?;
+        Ok(value)
+    }
+
+    fn m31_vec(&mut self) -> Result<Vec<u32>, String> {
+        let len = self.len(4)?;
+        (0..len).map(|_| self.m31()).collect()
+    }
+
+    fn qm31_vec(&mut self) -> Result<Vec<String>, String> {
+        let len = self.len(16)?;
+        (0..len)
+            .map(|_| {
+                let limbs = [self.m31()?, self.m31()?, self.m31()?, self.m31()?];
+                Ok(qm31_to_hex(&QM31::new(limbs[0], limbs[1], limbs[2], limbs[3])))
+            })
+            .collect()
+    }
+
+    fn decommitment(&mut self) -> Result<MerkleDecommitmentData, String> {
+        let num_hashes = self.len(HASH_BYTES)?;
+        let hash_witness = (0..num_hashes).map(|_| self.hash()).collect::<Result<Vec<_>, _>>()?;
+        Ok(MerkleDecommitmentData { hash_witness, column_witness: self.m31_vec()? })
+    }
+
+    fn fri_layer(&mut self) -> Result<FriLayerData, String> {
+        Ok(FriLayerData {
+            commitment: self.hash()?,
+            fri_witness: self.qm31_vec()?,
+            decommitment: self.decommitment()?,
+        })
+    }
+}
+
+/// Conversión desde/hacia los tipos de STWO (Blake2s)
+#[cfg(feature = "real-stwo")]
+mod stwo_conversion {
+    use super::*;
+    use stwo::core::fields::m31::M31;
+    use stwo::core::fields::qm31::SecureField;
+    use stwo::core::fri::{FriConfig, FriLayerProof, FriProof};
+    use stwo::core::pcs::{CommitmentSchemeProof, PcsConfig, TreeVec};
+    use stwo::core::poly::line::LinePoly;
+    use stwo::core::prover::StarkProof;
+    use stwo::core::vcs::blake2_hash::Blake2sHash;
+    use stwo::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+    use stwo::core::vcs::verifier::MerkleDecommitment;
+
+    fn hash_to_hex(hash: &Blake2sHash) -> String {
+        format!("0x{}", hex::encode(hash.0))
+    }
+
+    fn secure_to_hex(value: &SecureField) -> String {
+        let limbs = value.to_m31_array();
+        qm31_to_hex(&QM31::new(limbs[0].0, limbs[1].0, limbs[2].0, limbs[3].0))
+    }
+
+    fn secure_from_hex(value: &str) -> Result<SecureField, String> {
+        let limbs = qm31_from_hex(value)?.to_m31_array();
+        Ok(SecureField::from_m31(
+            M31::from_u32_unchecked(limbs[0]),
+            M31::from_u32_unchecked(limbs[1]),
+            M31::from_u32_unchecked(limbs[2]),
+            M31::from_u32_unchecked(limbs[3]),
+        ))
+    }
+
+    fn decommitment_from_stwo(decommitment: &MerkleDecommitment<Blake2sMerkleHasher>) -> MerkleDecommitmentData {
+        MerkleDecommitmentData {
+            hash_witness: decommitment.hash_witness.iter().map(hash_to_hex).collect(),
+            column_witness: decommitment.column_witness.iter().map(|value| value.0).collect(),
+        }
+    }
+
+    fn decommitment_to_stwo(data: &MerkleDecommitmentData) -> Result<MerkleDecommitment<Blake2sMerkleHasher>, String> {
+        Ok(MerkleDecommitment {
+            hash_witness: data.hash_witness.iter()
+                .map(|hash| decode_hash(hash).map(Blake2sHash))
+                .collect::<Result<Vec<_>, _>>()?,
+            column_witness: data.column_witness.iter().map(|value| M31::from_u32_unchecked(*value)).collect(),
+        })
+    }
+
+    fn fri_layer_from_stwo(layer: &FriLayerProof<Blake2sMerkleHasher>) -> FriLayerData {
+        FriLayerData {
+            commitment: hash_to_hex(&layer.commitment),
+            fri_witness: layer.fri_witness.iter().map(secure_to_hex).collect(),
+            decommitment: decommitment_from_stwo(&layer.decommitment),
+        }
+    }
+
+    fn fri_layer_to_stwo(layer: &FriLayerData) -> Result<FriLayerProof<Blake2sMerkleHasher>, String> {
+        Ok(FriLayerProof {
+            fri_witness: layer.fri_witness.iter().map(|v| secure_from_hex(v)).collect::<Result<Vec<_>, _>>()?,
+            decommitment: decommitment_to_stwo(&layer.decommitment)?,
+            commitment: Blake2sHash(decode_hash(&layer.commitment)?),
+        })
+    }
+
+    impl VersionedStarkProof {
+        pub fn from_stwo(proof: &StarkProof<Blake2sMerkleHasher>) -> VersionedStarkProof {
+            let proof = &proof.0;
+            let fri_config = &proof.config.fri_config;
+            VersionedStarkProof {
+                version: STARK_PROOF_FORMAT_VERSION,
+                config: StarkProofConfig {
+                    pow_bits: proof.config.pow_bits,
+                    log_blowup_factor: fri_config.log_blowup_factor,
+                    log_last_layer_degree_bound: fri_config.log_last_layer_degree_bound,
+                    n_queries: fri_config.n_queries as u32,
+                },
+                commitments: proof.commitments.iter().map(hash_to_hex).collect(),
+                sampled_values: proof.sampled_values.iter()
+                    .map(|tree| tree.iter().map(|column| column.iter().map(secure_to_hex).collect()).collect())
+                    .collect(),
+                decommitments: proof.decommitments.iter().map(decommitment_from_stwo).collect(),
+                queried_values: proof.queried_values.iter()
+                    .map(|values| values.iter().map(|value| value.0).collect())
+                    .collect(),
+                proof_of_work: proof.proof_of_work,
+                fri_proof: StarkFriData {
+                    first_layer: fri_layer_from_stwo(&proof.fri_proof.first_layer),
+                    inner_layers: proof.fri_proof.inner_layers.iter().map(fri_layer_from_stwo).collect(),
+                    last_layer_poly: proof.fri_proof.last_layer_poly.iter().map(secure_to_hex).collect(),
+                },
+            }
+        }
+
+        pub fn to_stwo(&self) -> Result<StarkProof<Blake2sMerkleHasher>, String> {
+            self.validate()?;
+            let config = PcsConfig {
+                pow_bits: self.config.pow_bits,
+                fri_config: FriConfig::new(
+                    self.config.log_last_layer_degree_bound,
+                    self.config.log_blowup_factor,
+                    self.config.n_queries as usize,
+                ),
+            };
+            let last_layer_poly = self.fri_proof.last_layer_poly.iter()
+                .map(|v| secure_from_hex(v))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(StarkProof(CommitmentSchemeProof {
+                config,
+                commitments: TreeVec(self.commitments.iter()
+                    .map(|hash| decode_hash(hash).map(Blake2sHash))
+                    .collect::<Result<Vec<_>, _>>()?),
+                sampled_values: TreeVec(self.sampled_values.iter()
+                    .map(|tree| tree.iter()
+                        .map(|column| column.iter().map(|v| secure_from_hex(v)).collect::<Result<Vec<_>, _>>())
+                        .collect::<Result<Vec<_>, _>>())
+                    .collect::<Result<Vec<_>, _>>()?),
+                decommitments: TreeVec(self.decommitments.iter()
+                    .map(decommitment_to_stwo)
+                    .collect::<Result<Vec<_>, _>>()?),
+                queried_values: TreeVec(self.queried_values.iter()
+                    .map(|values| values.iter().map(|value| M31::from_u32_unchecked(*value)).collect())
+                    .collect()),
+                proof_of_work: self.proof_of_work,
+                fri_proof: FriProof {
+                    first_layer: fri_layer_to_stwo(&self.fri_proof.first_layer)?,
+                    inner_layers: self.fri_proof.inner_layers.iter()
+                        .map(fri_layer_to_stwo)
+                        .collect::<Result<Vec<_>, _>>()?,
+                    last_layer_poly: LinePoly::new(last_layer_poly),
+                },
+            }))
+        }
+    }
+}
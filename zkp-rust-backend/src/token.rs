@@ -0,0 +1,218 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::{normalize_felt_hex, U256};
+
+/// Token soportado por los pools: símbolo, decimales, contrato ERC20 y
+/// límites por defecto del pool en unidades del token (p.ej. "0.001")
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[wasm_bindgen(getter_with_clone)]
+pub struct TokenInfo {
+    pub symbol: String,
+    pub decimals: u8,
+    pub address: String,
+    pub default_min: String,
+    pub default_max: String,
+}
+
+impl TokenInfo {
+    pub fn new(symbol: &str, decimals: u8, address: &str, default_min: &str, default_max: &str) -> TokenInfo {
+        TokenInfo {
+            symbol: symbol.to_string(),
+            decimals,
+            address: address.to_string(),
+            default_min: default_min.to_string(),
+            default_max: default_max.to_string(),
+        }
+    }
+}
+
+/// Registro de tokens conocidos (búsqueda por símbolo o dirección)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenRegistry {
+    tokens: Vec<TokenInfo>,
+}
+
+impl Default for TokenRegistry {
+    fn default() -> Self {
+        Self {
+            tokens: vec![
+                // Ver src/lib/constants.ts del frontend
+                TokenInfo::new("STRK", 18,
+                    "0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d", "0.001", "1000"),
+                TokenInfo::new("ETH", 18,
+                    "0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7", "0.0001", "10"),
+                // USDC bridged de mainnet; en otras redes registrar la dirección correspondiente
+                TokenInfo::new("USDC", 6,
+                    "0x053c91253bc9682c04929ca02ed00b3e423f6710d2ee7e0d5ebb06f3ecf368a8", "1", "10000"),
+            ],
+        }
+    }
+}
+
+impl TokenRegistry {
+    pub fn get(&self, symbol: &str) -> Result<&TokenInfo, String> {
+        self.tokens
+            .iter()
+            .find(|token| token.symbol.eq_ignore_ascii_case(symbol.trim()))
+            .ok_or_else(|| format!("Unknown token {}", symbol))
+    }
+
+    pub fn by_address(&self, address: &str) -> Option<&TokenInfo> {
+        let address = normalize_felt_hex(address);
+        self.tokens.iter().find(|token| normalize_felt_hex(&token.address) == address)
+    }
+
+    /// Registra o reemplaza un token (mismo símbolo)
+    pub fn register(&mut self, token: TokenInfo) {
+        self.tokens.retain(|existing| !existing.symbol.eq_ignore_ascii_case(&token.symbol));
+        self.tokens.push(token);
+    }
+
+    pub fn tokens(&self) -> &[TokenInfo] {
+        &self.tokens
+    }
+}
+
+/// Monto de un token en unidades mínimas (wei para STRK/ETH, 10^-6 para USDC)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenAmount {
+    pub symbol: String,
+    pub decimals: u8,
+    pub raw: U256,
+}
+
+impl TokenAmount {
+    pub fn from_raw(token: &TokenInfo, raw: U256) -> TokenAmount {
+        TokenAmount { symbol: token.symbol.clone(), decimals: token.decimals, raw }
+    }
+
+    /// Parsea "24.929" de forma exacta; rechaza más decimales de los que admite el token
+    pub fn parse(token: &TokenInfo, value: &str) -> Result<TokenAmount, String> {
+        let value = value.trim();
+        let (integer, fraction) = match value.split_once('.') {
+            Some((integer, fraction)) => (integer, fraction),
+            None => (value, ""),
+        };
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(format!("Invalid {} amount: {:?}", token.symbol, value));
+        }
+        if !integer.bytes().chain(fraction.bytes()).all(|b| b.is_ascii_digit()) {
+            return Err(format!("Invalid {} amount: {:?}", token.symbol, value));
+        }
+        if fraction.len() > token.decimals as usize {
+            return Err(format!(
+                "{} supports at most {} decimals, got {:?}", token.symbol, token.decimals, value
+            ));
+        }
+
+        let digits = format!(
+            "{}{}{}",
+            if integer.is_empty() { "0" } else { integer },
+            fraction,
+            "0".repeat(token.decimals as usize - fraction.len())
+        );
+        let raw = U256::from_dec_str(&digits)
+            .map_err(|e| format!("Invalid {} amount: {}", token.symbol, e))?;
+        Ok(TokenAmount::from_raw(token, raw))
+    }
+
+    /// Formato decimal legible sin ceros finales ("24.929", "1")
+    pub fn format(&self) -> String {
+        let digits = format!("{:0>width$}", self.raw.to_string(), width = self.decimals as usize + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - self.decimals as usize);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            integer.to_string()
+        } else {
+            format!("{}.{}", integer, fraction)
+        }
+    }
+}
+
+/// Convierte un monto legible del token ("24.929") a unidades mínimas (decimal)
+#[wasm_bindgen]
+pub fn parse_token_amount(symbol: &str, amount: &str) -> Result<String, JsValue> {
+    let registry = TokenRegistry::default();
+    let amount = TokenAmount::parse(registry.get(symbol)?, amount)?;
+    Ok(amount.raw.to_string())
+}
+
+/// Convierte unidades mínimas a monto legible del token
+#[wasm_bindgen]
+pub fn format_token_amount(symbol: &str, raw_amount: &str) -> Result<String, JsValue> {
+    let registry = TokenRegistry::default();
+    let raw = U256::parse(raw_amount)?;
+    Ok(TokenAmount::from_raw(registry.get(symbol)?, raw).format())
+}
+
+/// Registro de tokens soportados (JSON)
+#[wasm_bindgen]
+pub fn get_token_registry() -> String {
+    serde_json::to_string(TokenRegistry::default().tokens()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strk() -> TokenInfo {
+        TokenRegistry::default().get("STRK").unwrap().clone()
+    }
+
+    #[test]
+    fn decimal_amounts_round_trip() {
+        let usdc = TokenRegistry::default().get("usdc").unwrap().clone();
+        for (token, value, raw, formatted) in [
+            (strk(), "24.929", "24929000000000000000", "24.929"),
+            (strk(), "0.001", "1000000000000000", "0.001"),
+            (strk(), "1.000", "1000000000000000000", "1"),
+            (strk(), ".5", "500000000000000000", "0.5"),
+            (strk(), "0.000000000000000001", "1", "0.000000000000000001"),
+            (usdc.clone(), "10000", "10000000000", "10000"),
+            (usdc, "1.5", "1500000", "1.5"),
+        ] {
+            let amount = TokenAmount::parse(&token, value).unwrap();
+            assert_eq!(amount.raw.to_string(), raw, "{} {}", value, token.symbol);
+            assert_eq!(amount.format(), formatted);
+            assert_eq!(TokenAmount::parse(&token, &amount.format()).unwrap(), amount);
+        }
+    }
+
+    #[test]
+    fn too_many_fractional_digits_are_rejected() {
+        let usdc = TokenRegistry::default().get("USDC").unwrap().clone();
+        assert!(TokenAmount::parse(&usdc, "1.000001").is_ok());
+        assert!(TokenAmount::parse(&usdc, "1.0000001").unwrap_err().contains("at most 6 decimals"));
+        assert!(TokenAmount::parse(&strk(), &format!("0.{}1", "0".repeat(18))).is_err());
+        for invalid in ["", ".", "1.2.3", "-1", "1e18", "0x10"] {
+            assert!(TokenAmount::parse(&strk(), invalid).is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn amounts_past_u256_are_rejected() {
+        let mut registry = TokenRegistry::default();
+        registry.register(TokenInfo::new("RAW", 0, "0x1", "1", "1"));
+        let raw = registry.get("RAW").unwrap();
+
+        let max = U256::MAX.to_string();
+        assert_eq!(TokenAmount::parse(raw, &max).unwrap().raw, U256::MAX);
+        let two_pow_256 = "115792089237316195423570985008687907853269984665640564039457584007913129639936";
+        assert!(TokenAmount::parse(raw, two_pow_256).unwrap_err().contains("overflows"));
+
+        // 10^60 STRK son 10^78 wei, más que 2^256 ≈ 1.16·10^77
+        assert!(TokenAmount::parse(&strk(), &format!("1{}", "0".repeat(60))).is_err());
+    }
+
+    #[test]
+    fn unknown_tokens_are_rejected() {
+        let registry = TokenRegistry::default();
+        assert_eq!(registry.get("DOGE").unwrap_err(), "Unknown token DOGE");
+        assert!(registry.by_address("0x1234").is_none());
+        assert_eq!(registry.get(" strk ").unwrap().symbol, "STRK");
+        assert_eq!(
+            registry.by_address("0x4718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d").unwrap().symbol,
+            "STRK"
+        );
+    }
+}
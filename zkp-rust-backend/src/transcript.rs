@@ -0,0 +1,54 @@
+use sha3::{Digest, Keccak256};
+
+/// Transcript Fiat–Shamir basado en Keccak256
+/// Cada mensaje se absorbe con su etiqueta y longitud para evitar ambigüedades
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    state: [u8; 32],
+}
+
+impl Transcript {
+    /// Crea un transcript nuevo separado por dominio del protocolo
+    pub fn new(protocol_label: &str) -> Transcript {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"ceaser-transcript-v1");
+        hasher.update(protocol_label.as_bytes());
+
+        let mut state = [0u8; 32];
+        state.copy_from_slice(&hasher.finalize());
+        Transcript { state }
+    }
+
+    /// Absorbe un mensaje etiquetado
+    pub fn append_message(&mut self, label: &str, message: &[u8]) {
+        let mut hasher = Keccak256::new();
+        hasher.update(self.state);
+        hasher.update((label.len() as u32).to_le_bytes());
+        hasher.update(label.as_bytes());
+        hasher.update((message.len() as u64).to_le_bytes());
+        hasher.update(message);
+        self.state.copy_from_slice(&hasher.finalize());
+    }
+
+    /// Absorbe un string (p.ej. valores hex de la prueba)
+    pub fn append_str(&mut self, label: &str, value: &str) {
+        self.append_message(label, value.as_bytes());
+    }
+
+    /// Absorbe un entero sin signo
+    pub fn append_u64(&mut self, label: &str, value: u64) {
+        self.append_message(label, &value.to_le_bytes());
+    }
+
+    /// Extrae un reto de 32 bytes y actualiza el estado
+    pub fn challenge_bytes(&mut self, label: &str) -> [u8; 32] {
+        self.append_message(label, b"challenge");
+
+        let mut hasher = Keccak256::new();
+        hasher.update(self.state);
+        hasher.update(b"squeeze");
+        let mut challenge = [0u8; 32];
+        challenge.copy_from_slice(&hasher.finalize());
+        challenge
+    }
+}
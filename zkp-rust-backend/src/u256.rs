@@ -0,0 +1,293 @@
+use std::fmt;
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::{M31Element, M31Vector};
+
+/// Entero sin signo de 256 bits, como el `u256` de Cairo: (low, high) de 128 bits
+/// El orden de los campos hace que `Ord` derivado compare primero `high`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct U256 {
+    high: u128,
+    low: u128,
+}
+
+/// Bits por limb M31: con 30 bits ningún limb alcanza el módulo 2^31 − 1
+pub const U256_M31_LIMB_BITS: u32 = 30;
+
+/// Número de limbs de 30 bits necesarios para 256 bits (9 · 30 = 270)
+pub const U256_M31_LIMBS: usize = 9;
+
+impl U256 {
+    pub const ZERO: U256 = U256 { high: 0, low: 0 };
+    pub const MAX: U256 = U256 { high: u128::MAX, low: u128::MAX };
+
+    pub fn from_parts(low: u128, high: u128) -> U256 {
+        U256 { high, low }
+    }
+
+    pub fn low(&self) -> u128 {
+        self.low
+    }
+
+    pub fn high(&self) -> u128 {
+        self.high
+    }
+
+    /// Valor como u128 si cabe
+    pub fn as_u128(&self) -> Option<u128> {
+        if self.high == 0 { Some(self.low) } else { None }
+    }
+
+    fn to_words(self) -> [u64; 4] {
+        [self.low as u64, (self.low >> 64) as u64, self.high as u64, (self.high >> 64) as u64]
+    }
+
+    fn from_words(words: [u64; 4]) -> U256 {
+        U256 {
+            low: words[0] as u128 | (words[1] as u128) << 64,
+            high: words[2] as u128 | (words[3] as u128) << 64,
+        }
+    }
+
+    /// Parsea decimal o hex con prefijo `0x`
+    pub fn parse(value: &str) -> Result<U256, String> {
+        let value = value.trim();
+        match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+            Some(digits) => U256::from_hex_str(digits),
+            None => U256::from_dec_str(value),
+        }
+    }
+
+    pub fn from_dec_str(value: &str) -> Result<U256, String> {
+        if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("Invalid decimal u256: {:?}", value));
+        }
+        value.bytes().try_fold(U256::ZERO, |acc, digit| {
+            acc.checked_mul_u64(10)
+                .and_then(|acc| acc.checked_add(&U256::from((digit - b'0') as u128)))
+                .ok_or_else(|| format!("Decimal value {} overflows u256", value))
+        })
+    }
+
+    pub fn from_hex_str(digits: &str) -> Result<U256, String> {
+        let digits = digits.trim_start_matches('0');
+        if digits.len() > 64 {
+            return Err(format!("Hex value 0x{} overflows u256", digits));
+        }
+        if digits.is_empty() {
+            return Ok(U256::ZERO);
+        }
+        let split = digits.len().saturating_sub(32);
+        let parse = |part: &str| -> Result<u128, String> {
+            if part.is_empty() {
+                return Ok(0);
+            }
+            u128::from_str_radix(part, 16).map_err(|e| format!("Invalid hex u256: {}", e))
+        };
+        Ok(U256 { high: parse(&digits[..split])?, low: parse(&digits[split..])? })
+    }
+
+    /// Reconstruye desde las dos felts (low, high) de la ABI de Starknet
+    pub fn from_felts(low: &str, high: &str) -> Result<U256, String> {
+        let low = U256::parse(low)?;
+        let high = U256::parse(high)?;
+        match (low.as_u128(), high.as_u128()) {
+            (Some(low), Some(high)) => Ok(U256 { high, low }),
+            _ => Err("u256 felts must each fit in 128 bits".to_string()),
+        }
+    }
+
+    /// (low, high) como felts hex para calldata
+    pub fn to_felts(&self) -> (String, String) {
+        (format!("0x{:x}", self.low), format!("0x{:x}", self.high))
+    }
+
+    pub fn checked_add(&self, other: &U256) -> Option<U256> {
+        let (low, carry) = self.low.overflowing_add(other.low);
+        let high = self.high.checked_add(other.high)?.checked_add(carry as u128)?;
+        Some(U256 { high, low })
+    }
+
+    pub fn checked_sub(&self, other: &U256) -> Option<U256> {
+        let (low, borrow) = self.low.overflowing_sub(other.low);
+        let high = self.high.checked_sub(other.high)?.checked_sub(borrow as u128)?;
+        Some(U256 { high, low })
+    }
+
+    pub fn checked_mul_u64(&self, factor: u64) -> Option<U256> {
+        let mut words = self.to_words();
+        let mut carry = 0u128;
+        for word in words.iter_mut() {
+            let product = *word as u128 * factor as u128 + carry;
+            *word = product as u64;
+            carry = product >> 64;
+        }
+        if carry != 0 { None } else { Some(U256::from_words(words)) }
+    }
+
+    /// División por un u64: (cociente, resto)
+    pub fn div_rem_u64(&self, divisor: u64) -> (U256, u64) {
+        assert!(divisor != 0, "division by zero");
+        let mut words = self.to_words();
+        let mut remainder = 0u128;
+        for word in words.iter_mut().rev() {
+            let current = remainder << 64 | *word as u128;
+            *word = (current / divisor as u128) as u64;
+            remainder = current % divisor as u128;
+        }
+        (U256::from_words(words), remainder as u64)
+    }
+
+    pub fn shr(&self, bits: u32) -> U256 {
+        match bits {
+            0 => *self,
+            1..=127 => U256 { high: self.high >> bits, low: self.low >> bits | self.high << (128 - bits) },
+            128..=255 => U256 { high: 0, low: self.high >> (bits - 128) },
+            _ => U256::ZERO,
+        }
+    }
+
+    pub fn shl(&self, bits: u32) -> U256 {
+        match bits {
+            0 => *self,
+            1..=127 => U256 { high: self.high << bits | self.low >> (128 - bits), low: self.low << bits },
+            128..=255 => U256 { high: self.low << (bits - 128), low: 0 },
+            _ => U256::ZERO,
+        }
+    }
+
+    pub fn bit(&self, index: u32) -> bool {
+        match index {
+            0..=127 => (self.low >> index) & 1 == 1,
+            128..=255 => (self.high >> (index - 128)) & 1 == 1,
+            _ => false,
+        }
+    }
+
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes[..16].copy_from_slice(&self.low.to_le_bytes());
+        bytes[16..].copy_from_slice(&self.high.to_le_bytes());
+        bytes
+    }
+
+    /// Descomposición en 9 limbs de 30 bits (little-endian), todos canónicos en M31
+    pub fn to_m31_limbs(&self) -> [u32; U256_M31_LIMBS] {
+        let mask = (1u128 << U256_M31_LIMB_BITS) - 1;
+        let mut limbs = [0u32; U256_M31_LIMBS];
+        let mut remaining = *self;
+        for limb in limbs.iter_mut() {
+            *limb = (remaining.low & mask) as u32;
+            remaining = remaining.shr(U256_M31_LIMB_BITS);
+        }
+        limbs
+    }
+
+    /// Recompone desde limbs de 30 bits (a lo sumo 9)
+    pub fn from_m31_limbs(limbs: &[u32]) -> Result<U256, String> {
+        if limbs.len() > U256_M31_LIMBS {
+            return Err(format!("At most {} limbs fit in u256, got {}", U256_M31_LIMBS, limbs.len()));
+        }
+        // El último limb solo aporta 256 − 8·30 = 16 bits
+        let last_limb_bits = 256 - (U256_M31_LIMBS as u32 - 1) * U256_M31_LIMB_BITS;
+        let mut value = U256::ZERO;
+        for (i, limb) in limbs.iter().enumerate().rev() {
+            if *limb >= 1 << U256_M31_LIMB_BITS {
+                return Err(format!("Limb {} does not fit in {} bits", limb, U256_M31_LIMB_BITS));
+            }
+            if i == U256_M31_LIMBS - 1 && *limb >= 1 << last_limb_bits {
+                return Err("Limbs overflow u256".to_string());
+            }
+            value = value.shl(U256_M31_LIMB_BITS).checked_add(&U256::from(*limb as u128))
+                .ok_or("Limbs overflow u256")?;
+        }
+        Ok(value)
+    }
+}
+
+impl From<u128> for U256 {
+    fn from(value: u128) -> U256 {
+        U256 { high: 0, low: value }
+    }
+}
+
+impl FromStr for U256 {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<U256, String> {
+        U256::parse(value)
+    }
+}
+
+impl fmt::Display for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(value) = self.as_u128() {
+            return fmt::Display::fmt(&value, f);
+        }
+        // Trozos de 19 dígitos decimales (10^19 < 2^64)
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut chunks = Vec::new();
+        let mut remaining = *self;
+        while remaining != U256::ZERO {
+            let (quotient, chunk) = remaining.div_rem_u64(CHUNK);
+            chunks.push(chunk);
+            remaining = quotient;
+        }
+        let mut digits = chunks.pop().unwrap_or(0).to_string();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:019}", chunk));
+        }
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+/// Hex en minúsculas; con `high == 0` respeta el ancho igual que un u128
+impl fmt::LowerHex for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.high == 0 {
+            return fmt::LowerHex::fmt(&self.low, f);
+        }
+        let digits = format!("{:x}{:032x}", self.high, self.low);
+        f.pad_integral(true, if f.alternate() { "0x" } else { "" }, &digits)
+    }
+}
+
+/// Se serializa como string decimal (JSON no representa enteros de 256 bits)
+impl Serialize for U256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for U256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        U256::parse(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Parsea un monto (decimal o hex) con el nombre del campo en el error
+pub fn parse_amount(value: &str, field: &str) -> Result<U256, String> {
+    U256::parse(value).map_err(|e| format!("Invalid {}: {}", field, e))
+}
+
+/// Divide un u256 en las felts (low, high) de la ABI de Starknet
+#[wasm_bindgen]
+pub fn u256_to_felts(value: &str) -> Result<Vec<String>, JsValue> {
+    let (low, high) = U256::parse(value)?.to_felts();
+    Ok(vec![low, high])
+}
+
+/// Recompone un u256 (decimal) desde sus felts (low, high)
+#[wasm_bindgen]
+pub fn u256_from_felts(low: &str, high: &str) -> Result<String, JsValue> {
+    Ok(U256::from_felts(low, high)?.to_string())
+}
+
+/// Recompone un u256 (decimal) desde limbs M31 de 30 bits
+#[wasm_bindgen]
+pub fn m31_elements_to_u256(limbs: &M31Vector) -> Result<String, JsValue> {
+    let limbs: Vec<u32> = limbs.elements().iter().map(M31Element::value).collect();
+    Ok(U256::from_m31_limbs(&limbs)?.to_string())
+}
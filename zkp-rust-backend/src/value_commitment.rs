@@ -0,0 +1,478 @@
+use std::sync::OnceLock;
+use ark_bn254::{Fq, Fr, G1Affine, G1Projective};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, PrimeField, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use crate::Transcript;
+
+static BLINDING_GENERATOR: OnceLock<G1Affine> = OnceLock::new();
+
+/// Generador del valor (generador estándar de G1)
+pub fn value_generator() -> G1Affine {
+    G1Affine::generator()
+}
+
+/// Generador del blinding (nothing-up-my-sleeve)
+pub fn blinding_generator() -> G1Affine {
+    *BLINDING_GENERATOR.get_or_init(|| hash_to_g1(b"ceaser-value-blinding-generator"))
+}
+
+/// Hash-to-curve por try-and-increment (cofactor de G1 en BN254 = 1)
+pub fn hash_to_g1(label: &[u8]) -> G1Affine {
+    let mut counter: u32 = 0;
+    loop {
+        let mut hasher = Keccak256::new();
+        hasher.update(label);
+        hasher.update(counter.to_le_bytes());
+        let x = Fq::from_be_bytes_mod_order(&hasher.finalize());
+
+        if let Some(point) = G1Affine::get_point_from_x_unchecked(x, false) {
+            return point;
+        }
+        counter += 1;
+    }
+}
+
+/// Generador de las claves de gasto del dominio: K_d = hash-to-curve(separador).
+/// Otro dominio da otro generador, así que una firma no se traslada de dominio
+pub fn nullifier_generator(domain_separator: &[u8; 32]) -> G1Affine {
+    let mut label = b"ceaser-nullifier-generator".to_vec();
+    label.extend_from_slice(domain_separator);
+    hash_to_g1(&label)
+}
+
+/// Deriva el blinding escalar del commitment de valor desde el blinding de la nota
+pub fn value_blinding_from_bytes(blinding: &[u8]) -> Fr {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"ceaser-value-blinding");
+    hasher.update(blinding);
+    Fr::from_le_bytes_mod_order(&hasher.finalize())
+}
+
+/// Commitment de valor homomórfico sobre BN254 G1: C = amount·G + blinding·H
+/// H se obtiene por hash-to-curve, por lo que nadie conoce log_G(H)
+pub fn commit_value(amount: u128, blinding: &Fr) -> G1Projective {
+    G1Projective::from(value_generator()) * Fr::from(amount)
+        + G1Projective::from(blinding_generator()) * blinding
+}
+
+/// Igual que `commit_value` pero con el generador de valor del activo (cegado): C = amount·A' + blinding·H
+pub fn commit_value_with_generator(amount: u128, generator: &G1Projective, blinding: &Fr) -> G1Projective {
+    *generator * Fr::from(amount) + G1Projective::from(blinding_generator()) * blinding
+}
+
+pub fn point_to_hex(point: &G1Projective) -> String {
+    let mut bytes = Vec::with_capacity(32);
+    point
+        .into_affine()
+        .serialize_compressed(&mut bytes)
+        .expect("serializing into a Vec cannot fail");
+    format!("0x{}", hex::encode(bytes))
+}
+
+pub fn point_from_hex(value: &str) -> Result<G1Projective, String> {
+    let bytes = hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid point hex: {}", e))?;
+    let point = G1Affine::deserialize_compressed(&bytes[..])
+        .map_err(|e| format!("Invalid curve point: {}", e))?;
+    Ok(point.into())
+}
+
+pub fn scalar_to_hex(scalar: &Fr) -> String {
+    format!("0x{}", hex::encode(scalar.into_bigint().to_bytes_be()))
+}
+
+pub fn scalar_from_hex(value: &str) -> Result<Fr, String> {
+    let bytes = hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid scalar hex: {}", e))?;
+    if bytes.len() != 32 {
+        return Err(format!("Scalar must be 32 bytes, got {}", bytes.len()));
+    }
+    let scalar = Fr::from_be_bytes_mod_order(&bytes);
+    if scalar_to_hex(&scalar) != format!("0x{}", hex::encode(&bytes)) {
+        return Err("Scalar is not canonical".to_string());
+    }
+    Ok(scalar)
+}
+
+/// Prueba Schnorr de que `excess = x·H` (conocimiento de x, sin componente en G)
+/// Con excess = ΣC_in − ΣC_out − fee·G demuestra que los montos cuadran
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceProof {
+    pub nonce_commitment: String,
+    pub response: String,
+}
+
+impl BalanceProof {
+    pub fn prove<R: RngCore + CryptoRng>(
+        excess_blinding: &Fr,
+        excess: &G1Projective,
+        transcript: &mut Transcript,
+        rng: &mut R,
+    ) -> BalanceProof {
+        let mut nonce_seed = [0u8; 64];
+        rng.fill_bytes(&mut nonce_seed);
+        let nonce = Fr::from_le_bytes_mod_order(&nonce_seed);
+
+        let nonce_commitment = G1Projective::from(blinding_generator()) * nonce;
+        let challenge = balance_challenge(transcript, excess, &nonce_commitment);
+
+        BalanceProof {
+            nonce_commitment: point_to_hex(&nonce_commitment),
+            response: scalar_to_hex(&(nonce + challenge * excess_blinding)),
+        }
+    }
+
+    /// Verifica s·H == R + c·excess
+    pub fn verify(&self, excess: &G1Projective, transcript: &mut Transcript) -> Result<bool, String> {
+        let nonce_commitment = point_from_hex(&self.nonce_commitment)?;
+        let response = scalar_from_hex(&self.response)?;
+        let challenge = balance_challenge(transcript, excess, &nonce_commitment);
+
+        let lhs = G1Projective::from(blinding_generator()) * response;
+        let rhs = nonce_commitment + *excess * challenge;
+        Ok(lhs == rhs)
+    }
+}
+
+fn balance_challenge(transcript: &mut Transcript, excess: &G1Projective, nonce_commitment: &G1Projective) -> Fr {
+    transcript.append_str("balance_excess", &point_to_hex(excess));
+    transcript.append_str("balance_nonce_commitment", &point_to_hex(nonce_commitment));
+    Fr::from_le_bytes_mod_order(&transcript.challenge_bytes("balance_challenge"))
+}
+
+/// Prueba OR de Schnorr (Cramer–Damgård–Schoenmakers) sobre H: para algún k,
+/// P_k = x·H, sin revelar k. Un par (reto, respuesta) por declaración y los
+/// retos suman el reto del transcript
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrProof {
+    pub challenges: Vec<String>,
+    pub responses: Vec<String>,
+}
+
+impl OrProof {
+    pub fn prove<R: RngCore + CryptoRng>(
+        statements: &[G1Projective],
+        secret_index: usize,
+        secret: &Fr,
+        transcript: &mut Transcript,
+        rng: &mut R,
+    ) -> Result<OrProof, String> {
+        if secret_index >= statements.len() {
+            return Err(format!("Secret index {} outside {} statements", secret_index, statements.len()));
+        }
+
+        // Ramas simuladas con (c_k, s_k) aleatorios; la real se cierra con el reto global
+        let h = G1Projective::from(blinding_generator());
+        let mut challenges = vec![Fr::zero(); statements.len()];
+        let mut responses = vec![Fr::zero(); statements.len()];
+        let mut nonce_commitments = vec![G1Projective::zero(); statements.len()];
+        let nonce = Fr::rand(rng);
+        for (k, statement) in statements.iter().enumerate() {
+            if k == secret_index {
+                nonce_commitments[k] = h * nonce;
+            } else {
+                challenges[k] = Fr::rand(rng);
+                responses[k] = Fr::rand(rng);
+                nonce_commitments[k] = h * responses[k] - *statement * challenges[k];
+            }
+        }
+
+        let challenge = or_challenge(transcript, statements, &nonce_commitments);
+        let simulated_sum: Fr = challenges.iter().sum();
+        challenges[secret_index] = challenge - simulated_sum;
+        responses[secret_index] = nonce + challenges[secret_index] * secret;
+
+        Ok(OrProof {
+            challenges: challenges.iter().map(scalar_to_hex).collect(),
+            responses: responses.iter().map(scalar_to_hex).collect(),
+        })
+    }
+
+    /// Verifica Σc_k == reto con R_k = s_k·H − c_k·P_k
+    pub fn verify(&self, statements: &[G1Projective], transcript: &mut Transcript) -> Result<bool, String> {
+        if statements.is_empty() || self.challenges.len() != statements.len() || self.responses.len() != statements.len() {
+            return Ok(false);
+        }
+
+        let h = G1Projective::from(blinding_generator());
+        let challenges = self.challenges.iter().map(|c| scalar_from_hex(c)).collect::<Result<Vec<_>, _>>()?;
+        let responses = self.responses.iter().map(|s| scalar_from_hex(s)).collect::<Result<Vec<_>, _>>()?;
+        let nonce_commitments: Vec<G1Projective> = statements
+            .iter()
+            .zip(challenges.iter().zip(&responses))
+            .map(|(statement, (challenge, response))| h * response - *statement * challenge)
+            .collect();
+
+        let challenge = or_challenge(transcript, statements, &nonce_commitments);
+        Ok(challenges.iter().sum::<Fr>() == challenge)
+    }
+}
+
+fn or_challenge(transcript: &mut Transcript, statements: &[G1Projective], nonce_commitments: &[G1Projective]) -> Fr {
+    for (statement, nonce_commitment) in statements.iter().zip(nonce_commitments) {
+        transcript.append_str("or_statement", &point_to_hex(statement));
+        transcript.append_str("or_nonce_commitment", &point_to_hex(nonce_commitment));
+    }
+    Fr::from_le_bytes_mod_order(&transcript.challenge_bytes("or_challenge"))
+}
+
+/// Prueba de rango sobre un commitment de valor V = v·A' + y·H: 0 ≤ v ≤ max.
+/// v y max − v se descomponen en n = bits(max) bits comprometidos con
+/// B_j = b_j·A' + t_j·H; cada B_j lleva una prueba OR (CDS) de que abre a 0 o a 1
+/// y ΣB_j·2^j − V (resp. ΣD_j·2^j − (max·A' − V)) es múltiplo de H.
+/// Todas las pruebas OR comparten el reto global `challenge`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValueRangeProof {
+    pub value_bits: Vec<BitProof>,
+    pub complement_bits: Vec<BitProof>,
+    pub challenge: String,
+    pub value_proof: BalanceProof,
+    pub complement_proof: BalanceProof,
+}
+
+/// Commitment de un bit y su prueba OR: rama 0 (B = t·H) o rama 1 (B − A' = t·H).
+/// El reto de la rama 1 es el reto global menos el de la rama 0
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BitProof {
+    pub commitment: String,
+    pub challenge_zero: String,
+    pub response_zero: String,
+    pub response_one: String,
+}
+
+/// Bits necesarios para representar `max` (y por tanto cualquier valor ≤ max)
+pub fn range_bit_length(max: u128) -> usize {
+    (u128::BITS - max.leading_zeros()) as usize
+}
+
+/// Secretos del prover para un bit: valor, blinding, nonce de la rama real
+/// y (reto, respuesta) con los que se simula la otra rama
+struct BitWitness {
+    bit: bool,
+    blinding: Fr,
+    nonce: Fr,
+    simulated_challenge: Fr,
+    simulated_response: Fr,
+}
+
+impl BitWitness {
+    fn new<R: RngCore + CryptoRng>(bit: bool, rng: &mut R) -> BitWitness {
+        BitWitness {
+            bit,
+            blinding: Fr::rand(rng),
+            nonce: Fr::rand(rng),
+            simulated_challenge: Fr::rand(rng),
+            simulated_response: Fr::rand(rng),
+        }
+    }
+
+    /// B = b·A' + t·H
+    fn commitment(&self, generator: &G1Projective) -> G1Projective {
+        let h = G1Projective::from(blinding_generator());
+        if self.bit { *generator + h * self.blinding } else { h * self.blinding }
+    }
+
+    /// Nonces (R_0, R_1): la rama real es k·H, la simulada s·H − c·P
+    fn nonce_commitments(&self, commitment: &G1Projective, generator: &G1Projective) -> (G1Projective, G1Projective) {
+        let h = G1Projective::from(blinding_generator());
+        let real = h * self.nonce;
+        if self.bit {
+            (h * self.simulated_response - *commitment * self.simulated_challenge, real)
+        } else {
+            (real, h * self.simulated_response - (*commitment - generator) * self.simulated_challenge)
+        }
+    }
+
+    /// Cierra la rama real con el reto global
+    fn respond(&self, commitment: &G1Projective, challenge: &Fr) -> BitProof {
+        let real_challenge = *challenge - self.simulated_challenge;
+        let real_response = self.nonce + real_challenge * self.blinding;
+        let (challenge_zero, response_zero, response_one) = if self.bit {
+            (self.simulated_challenge, self.simulated_response, real_response)
+        } else {
+            (real_challenge, real_response, self.simulated_response)
+        };
+        BitProof {
+            commitment: point_to_hex(commitment),
+            challenge_zero: scalar_to_hex(&challenge_zero),
+            response_zero: scalar_to_hex(&response_zero),
+            response_one: scalar_to_hex(&response_one),
+        }
+    }
+}
+
+impl ValueRangeProof {
+    /// `blinding` es y en V = amount·A' + y·H
+    pub fn prove<R: RngCore + CryptoRng>(
+        amount: u128,
+        blinding: &Fr,
+        max: u128,
+        generator: &G1Projective,
+        transcript: &mut Transcript,
+        rng: &mut R,
+    ) -> Result<ValueRangeProof, String> {
+        if amount > max {
+            return Err(format!("Amount {} exceeds range proof bound {}", amount, max));
+        }
+        let value = commit_value_with_generator(amount, generator, blinding);
+        let bits = range_bit_length(max);
+
+        let witnesses: Vec<BitWitness> = [amount, max - amount]
+            .iter()
+            .flat_map(|decomposed| (0..bits).map(move |j| decomposed >> j & 1 == 1))
+            .map(|bit| BitWitness::new(bit, rng))
+            .collect();
+        let commitments: Vec<G1Projective> = witnesses.iter().map(|witness| witness.commitment(generator)).collect();
+        let nonce_commitments: Vec<(G1Projective, G1Projective)> = witnesses
+            .iter()
+            .zip(&commitments)
+            .map(|(witness, commitment)| witness.nonce_commitments(commitment, generator))
+            .collect();
+
+        let challenge = range_challenge(transcript, generator, &value, max, &commitments, &nonce_commitments);
+        let mut value_bits: Vec<BitProof> = witnesses
+            .iter()
+            .zip(&commitments)
+            .map(|(witness, commitment)| witness.respond(commitment, &challenge))
+            .collect();
+        let complement_bits = value_bits.split_off(bits);
+
+        // ΣB_j·2^j − V = (Σt_j·2^j − y)·H y ΣD_j·2^j − (max·A' − V) = (Σt'_j·2^j + y)·H
+        let (value_witnesses, complement_witnesses) = witnesses.split_at(bits);
+        let value_excess = weighted_sum(&commitments[..bits]) - value;
+        let complement_excess = weighted_sum(&commitments[bits..]) - (*generator * Fr::from(max) - value);
+        let value_proof = BalanceProof::prove(
+            &(weighted_blinding(value_witnesses) - blinding), &value_excess, transcript, rng,
+        );
+        let complement_proof = BalanceProof::prove(
+            &(weighted_blinding(complement_witnesses) + blinding), &complement_excess, transcript, rng,
+        );
+
+        Ok(ValueRangeProof {
+            value_bits,
+            complement_bits,
+            challenge: scalar_to_hex(&challenge),
+            value_proof,
+            complement_proof,
+        })
+    }
+
+    /// Verifica que `value` abre a un monto en [0, max] sobre el generador `generator`
+    pub fn verify(
+        &self,
+        value: &G1Projective,
+        max: u128,
+        generator: &G1Projective,
+        transcript: &mut Transcript,
+    ) -> Result<bool, String> {
+        let bits = range_bit_length(max);
+        if self.value_bits.len() != bits || self.complement_bits.len() != bits {
+            return Ok(false);
+        }
+
+        let h = G1Projective::from(blinding_generator());
+        let challenge = scalar_from_hex(&self.challenge)?;
+        let mut commitments = Vec::with_capacity(2 * bits);
+        let mut nonce_commitments = Vec::with_capacity(2 * bits);
+        for proof in self.value_bits.iter().chain(&self.complement_bits) {
+            let commitment = point_from_hex(&proof.commitment)?;
+            let challenge_zero = scalar_from_hex(&proof.challenge_zero)?;
+            let challenge_one = challenge - challenge_zero;
+            nonce_commitments.push((
+                h * scalar_from_hex(&proof.response_zero)? - commitment * challenge_zero,
+                h * scalar_from_hex(&proof.response_one)? - (commitment - generator) * challenge_one,
+            ));
+            commitments.push(commitment);
+        }
+        if range_challenge(transcript, generator, value, max, &commitments, &nonce_commitments) != challenge {
+            return Ok(false);
+        }
+
+        let value_excess = weighted_sum(&commitments[..bits]) - value;
+        let complement_excess = weighted_sum(&commitments[bits..]) - (*generator * Fr::from(max) - value);
+        Ok(self.value_proof.verify(&value_excess, transcript)?
+            && self.complement_proof.verify(&complement_excess, transcript)?)
+    }
+}
+
+fn range_challenge(
+    transcript: &mut Transcript,
+    generator: &G1Projective,
+    value: &G1Projective,
+    max: u128,
+    commitments: &[G1Projective],
+    nonce_commitments: &[(G1Projective, G1Projective)],
+) -> Fr {
+    transcript.append_str("range_generator", &point_to_hex(generator));
+    transcript.append_str("range_value", &point_to_hex(value));
+    transcript.append_message("range_max", &max.to_le_bytes());
+    for (commitment, (zero, one)) in commitments.iter().zip(nonce_commitments) {
+        transcript.append_str("range_bit_commitment", &point_to_hex(commitment));
+        transcript.append_str("range_nonce_zero", &point_to_hex(zero));
+        transcript.append_str("range_nonce_one", &point_to_hex(one));
+    }
+    Fr::from_le_bytes_mod_order(&transcript.challenge_bytes("range_challenge"))
+}
+
+/// Σ P_j·2^j
+fn weighted_sum(points: &[G1Projective]) -> G1Projective {
+    points.iter().enumerate().map(|(j, point)| *point * Fr::from(1u128 << j)).sum()
+}
+
+/// Σ t_j·2^j
+fn weighted_blinding(witnesses: &[BitWitness]) -> Fr {
+    witnesses.iter().enumerate().map(|(j, witness)| witness.blinding * Fr::from(1u128 << j)).sum()
+}
+
+/// Firma Schnorr de la declaración con la clave de gasto S = s·K: solo quien
+/// conoce el secreto del nullifier puede ligarlo a receptor, relayer y fee.
+/// El nonce se deriva de (s, declaración), así que la firma es determinista
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpendSignature {
+    pub spend_key: String,
+    pub nonce_commitment: String,
+    pub response: String,
+}
+
+impl SpendSignature {
+    pub fn sign(secret: &Fr, generator: &G1Projective, statement_hash: &str) -> SpendSignature {
+        let spend_key = *generator * secret;
+
+        let mut hasher = Keccak256::new();
+        hasher.update(b"ceaser-spend-nonce");
+        hasher.update(secret.into_bigint().to_bytes_le());
+        hasher.update(statement_hash.as_bytes());
+        let nonce = Fr::from_le_bytes_mod_order(&hasher.finalize());
+
+        let nonce_commitment = *generator * nonce;
+        let challenge = spend_challenge(statement_hash, &spend_key, &nonce_commitment);
+
+        SpendSignature {
+            spend_key: point_to_hex(&spend_key),
+            nonce_commitment: point_to_hex(&nonce_commitment),
+            response: scalar_to_hex(&(nonce + challenge * secret)),
+        }
+    }
+
+    /// Verifica s·K == R + c·S
+    pub fn verify(&self, generator: &G1Projective, statement_hash: &str) -> Result<bool, String> {
+        let spend_key = point_from_hex(&self.spend_key)?;
+        let nonce_commitment = point_from_hex(&self.nonce_commitment)?;
+        let response = scalar_from_hex(&self.response)?;
+        let challenge = spend_challenge(statement_hash, &spend_key, &nonce_commitment);
+
+        Ok(*generator * response == nonce_commitment + spend_key * challenge)
+    }
+}
+
+fn spend_challenge(statement_hash: &str, spend_key: &G1Projective, nonce_commitment: &G1Projective) -> Fr {
+    let mut transcript = Transcript::new("ceaser-spend-signature-v1");
+    transcript.append_str("statement", statement_hash);
+    transcript.append_str("spend_key", &point_to_hex(spend_key));
+    transcript.append_str("nonce_commitment", &point_to_hex(nonce_commitment));
+    Fr::from_le_bytes_mod_order(&transcript.challenge_bytes("spend_challenge"))
+}
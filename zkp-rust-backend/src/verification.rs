@@ -0,0 +1,279 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use ark_bn254::G1Projective;
+use crate::value_commitment::{nullifier_generator, point_from_hex};
+use crate::{
+    compute_statement_hash, nullifier_from_spend_key, parse_amount, spent_leaf_hash, verify_backend_range_proof,
+    verify_merkle_proof_with_index, verify_pedersen_commitment, CeaserZKProof, CircleStarkRangeProof, Domain,
+    InMemoryNullifierSet, NullifierSet, SecurityParams, ZKProofConfig, U256,
+};
+
+/// Motivo concreto por el que una prueba no es aceptable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationFailure {
+    WrongDomain,
+    InvalidCommitment,
+    InvalidRangeProof,
+    InvalidMerkleProof,
+    StatementMismatch,
+    AlreadySpent,
+    InvalidPublicInputs,
+    BindingMismatch,
+    InvalidDenomination,
+    InvalidSignature,
+}
+
+/// Resultado detallado de la verificación (lo que consume el relayer)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationReport {
+    pub valid: bool,
+    pub failure: Option<VerificationFailure>,
+    pub nullifier: String,
+    pub detail: String,
+}
+
+impl VerificationReport {
+    pub fn success(nullifier: &str) -> VerificationReport {
+        VerificationReport {
+            valid: true,
+            failure: None,
+            nullifier: nullifier.to_string(),
+            detail: "ok".to_string(),
+        }
+    }
+
+    pub fn failed(failure: VerificationFailure, nullifier: &str, detail: &str) -> VerificationReport {
+        VerificationReport {
+            valid: false,
+            failure: Some(failure),
+            nullifier: nullifier.to_string(),
+            detail: detail.to_string(),
+        }
+    }
+}
+
+/// Verifica la prueba completa; si se pasa un registro, el nullifier no debe estar gastado.
+/// La range proof se verifica con los parámetros de seguridad por defecto
+pub fn build_verification_report(
+    proof: &CeaserZKProof,
+    domain: &Domain,
+    nullifiers: Option<&dyn NullifierSet>,
+) -> Result<VerificationReport, JsValue> {
+    verification_report(proof, domain, &SecurityParams::default(), nullifiers)
+}
+
+fn verification_report(
+    proof: &CeaserZKProof,
+    domain: &Domain,
+    security_params: &SecurityParams,
+    nullifiers: Option<&dyn NullifierSet>,
+) -> Result<VerificationReport, JsValue> {
+    let nullifier = proof.nullifier.as_str();
+
+    // Anti-replay: la prueba debe ser de esta cadena/contrato/versión
+    if !proof.domain.matches(domain) {
+        crate::console_log!("❌ Dominio incorrecto: {} {} v{}",
+                     proof.domain.chain_id, proof.domain.contract_address, proof.domain.protocol_version);
+        return Ok(VerificationReport::failed(
+            VerificationFailure::WrongDomain, nullifier, "proof belongs to another chain/contract/version",
+        ));
+    }
+
+    // Confirmar modo de verificación
+    #[cfg(feature = "real-stwo")]
+    crate::console_log!("✅ Usando verificador STWO REAL");
+
+    #[cfg(all(feature = "mock-stwo", not(feature = "real-stwo")))]
+    crate::console_log!("⚠️ Usando verificador MOCK (solo para testing)");
+
+    // Verificaciones básicas
+    if !verify_pedersen_commitment(&proof.amount_commitment)? {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::InvalidCommitment, nullifier, "malformed amount commitment",
+        ));
+    }
+
+    // Verificación de range proof - usar verificador apropiado según el modo
+    if !verify_range_proof_for_domain(&proof.range_proof, &proof.domain, security_params)? {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::InvalidRangeProof, nullifier, "range proof rejected",
+        ));
+    }
+
+    // La range proof debe ser la del commitment de esta prueba; las v0 no lo declaran
+    let range_commitment = range_proof_commitment(&proof.range_proof);
+    let legacy_unbound_range = range_commitment.is_none() && proof.domain.is_legacy();
+    if !legacy_unbound_range && range_commitment != Some(proof.amount_commitment.commitment_hash.as_str()) {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::InvalidRangeProof, nullifier, "range proof is not over the amount commitment",
+        ));
+    }
+
+    // Desde v1 la hoja es la de la nota N, igual que en depósitos y join-split
+    let leaf_hash = match spent_leaf_hash(proof) {
+        Ok(leaf_hash) => leaf_hash,
+        Err(e) => return Ok(VerificationReport::failed(VerificationFailure::InvalidCommitment, nullifier, &e)),
+    };
+    let merkle_valid = verify_merkle_proof_with_index(
+        proof.merkle_proof.proof_path.clone(),
+        &proof.merkle_root,
+        &leaf_hash,
+        proof.merkle_proof.leaf_index,
+    )?;
+    if !merkle_valid {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::InvalidMerkleProof, nullifier, "commitment is not in the anonymous set",
+        ));
+    }
+
+    // La declaración debe coincidir con receptor/relayer/fee publicados. Las
+    // pruebas v0 anteriores al binding no traen hash: solo las acepta un verificador v0
+    let legacy_unbound = proof.statement_hash.is_empty() && proof.domain.is_legacy();
+    if !legacy_unbound && proof.statement_hash != compute_statement_hash(proof) {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::StatementMismatch, nullifier, "public inputs do not match statement hash",
+        ));
+    }
+
+    // El hash no tiene clave: desde v1 lo firma la clave de gasto de la que sale el nullifier
+    if !proof.domain.is_legacy() && !spend_signature_valid(proof) {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::InvalidSignature, nullifier, "statement is not signed by the nullifier's spend key",
+        ));
+    }
+
+    // Doble gasto: la prueba es válida pero el nullifier ya se usó
+    if let Some(set) = nullifiers {
+        if set.contains(nullifier) {
+            crate::console_log!("🚫 Nullifier ya gastado: {}", nullifier);
+            return Ok(VerificationReport::failed(
+                VerificationFailure::AlreadySpent, nullifier, "nullifier already spent",
+            ));
+        }
+    }
+
+    Ok(VerificationReport::success(nullifier))
+}
+
+/// Las range proofs v0 son anteriores a FRI (sin `fri_proof`): en el dominio v0
+/// solo se les puede exigir el formato de entonces. Desde v1 decide el backend
+fn verify_range_proof_for_domain(
+    range_proof: &CircleStarkRangeProof,
+    domain: &Domain,
+    security_params: &SecurityParams,
+) -> Result<bool, JsValue> {
+    if domain.is_legacy() && range_proof.fri_proof.is_none() {
+        return Ok(legacy_range_proof_well_formed(range_proof));
+    }
+    verify_backend_range_proof(range_proof, security_params)
+}
+
+/// Comprobación estructural del verificador v0: evaluaciones, commitments FRI,
+/// al menos 8 palabras de proof_data y 4 entradas públicas en hex
+fn legacy_range_proof_well_formed(range_proof: &CircleStarkRangeProof) -> bool {
+    !range_proof.circle_evaluations.is_empty()
+        && !range_proof.fri_commitments.is_empty()
+        && range_proof.proof_data.len() >= 8
+        && range_proof.public_inputs.len() >= 4
+        && range_proof.public_inputs.iter().all(|input| input.starts_with("0x"))
+}
+
+/// La firma verifica con S y el nullifier publicado es el de S en el dominio de la prueba
+fn spend_signature_valid(proof: &CeaserZKProof) -> bool {
+    let Some(signature) = &proof.spend_signature else {
+        return false;
+    };
+    let Ok(spend_key) = point_from_hex(&signature.spend_key) else {
+        return false;
+    };
+    let generator = G1Projective::from(nullifier_generator(&proof.domain.separator()));
+    nullifier_from_spend_key(&spend_key, &proof.domain) == proof.nullifier
+        && signature.verify(&generator, &proof.statement_hash).unwrap_or(false)
+}
+
+/// Límites `(min, max)` que declara la range proof. El backend mock publica
+/// `[min, max, field_size, domain_size, commitment_hash]` y el STWO real
+/// `[min, max, commitment_hash]`
+pub(crate) fn range_proof_bounds(range_proof: &CircleStarkRangeProof) -> Option<(U256, U256)> {
+    let inputs = &range_proof.public_inputs;
+    let min = parse_amount(inputs.first()?, "min amount").ok()?;
+    let max = parse_amount(inputs.get(1)?, "max amount").ok()?;
+    Some((min, max))
+}
+
+/// Hash del commitment al que está ligada la range proof (última entrada pública);
+/// las pruebas v0 no lo traen
+pub(crate) fn range_proof_commitment(range_proof: &CircleStarkRangeProof) -> Option<&str> {
+    match range_proof.public_inputs.as_slice() {
+        [_, _, _, _, commitment_hash] | [_, _, commitment_hash] => Some(commitment_hash.as_str()),
+        _ => None,
+    }
+}
+
+/// Una range proof válida para otros límites no prueba que el monto esté en los del pool
+pub(crate) fn range_proof_bounds_match(range_proof: &CircleStarkRangeProof, min: U256, max: U256) -> bool {
+    range_proof_bounds(range_proof) == Some((min, max))
+}
+
+/// Verifica además el modo de montos del pool: en modo denominación la
+/// prueba debe declarar un ID existente y probar el rango [d, d] de ese ID,
+/// en modo rango no debe declarar ninguno. La range proof usa `config.security`
+pub fn build_verification_report_for_config(
+    proof: &CeaserZKProof,
+    config: &ZKProofConfig,
+    nullifiers: Option<&dyn NullifierSet>,
+) -> Result<VerificationReport, JsValue> {
+    // El ID va en la declaración firmada y fija los límites [d, d] de la range proof
+    let (min, max) = match config.range_bounds(proof.denomination_id) {
+        Ok(bounds) => bounds,
+        Err(e) => return Ok(VerificationReport::failed(VerificationFailure::InvalidDenomination, &proof.nullifier, &e)),
+    };
+
+    let min = parse_amount(&min, "min amount")?;
+    let max = parse_amount(&max, "max amount")?;
+    if !range_proof_bounds_match(&proof.range_proof, min, max) {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::InvalidRangeProof, &proof.nullifier, "range proof bounds differ from pool limits",
+        ));
+    }
+
+    verification_report(proof, &config.domain, &config.security_params(), nullifiers)
+}
+
+/// Verifica y, si es válida, registra el nullifier de forma atómica (flujo del relayer)
+pub fn verify_and_record_nullifier(
+    proof: &CeaserZKProof,
+    domain: &Domain,
+    nullifiers: &mut dyn NullifierSet,
+) -> Result<VerificationReport, JsValue> {
+    let report = build_verification_report(proof, domain, Some(&*nullifiers))?;
+    if !report.valid {
+        return Ok(report);
+    }
+
+    if !nullifiers.insert_if_absent(&proof.nullifier)? {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::AlreadySpent, &proof.nullifier, "nullifier already spent",
+        ));
+    }
+    Ok(report)
+}
+
+/// Verifica una prueba contra un registro de nullifiers y devuelve el reporte (JSON)
+#[wasm_bindgen]
+pub fn verify_ceaser_zk_proof_report(
+    proof_json: &str,
+    domain: &Domain,
+    nullifiers: &InMemoryNullifierSet,
+) -> Result<JsValue, JsValue> {
+    let proof: CeaserZKProof = serde_json::from_str(proof_json)
+        .map_err(|e| format!("Error parsing proof: {}", e))?;
+
+    let report = build_verification_report(&proof, domain, Some(nullifiers))?;
+    crate::console_log!("📋 Reporte de verificación: valid={}, failure={:?}", report.valid, report.failure);
+
+    let json_string = serde_json::to_string(&report)
+        .map_err(|e| format!("Error serializing report: {}", e))?;
+    Ok(JsValue::from_str(&json_string))
+}
@@ -0,0 +1,395 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use ark_bn254::{Fr, G1Projective};
+use sha3::{Digest, Keccak256};
+use crate::value_commitment::{
+    nullifier_generator, point_from_hex, point_to_hex, value_blinding_from_bytes, OrProof,
+    SpendSignature,
+};
+use crate::{
+    build_anonymous_set_merkle_proof, deposit_note_commitment, normalize_felt_hex, note_leaf,
+    nullifier_from_spend_key, parse_amount, parse_proof_config, pool_value_generator, time_phase,
+    verify_merkle_proof_with_index, Domain, MerkleProof, NullifierSet, ProofPhase, ProofRecorder, ProofRng,
+    TransferBinding, Transcript, VerificationFailure, VerificationReport, ZKProofConfig, U256,
+};
+
+/// Orden de las entradas públicas del retiro (calldata de `withdraw_private`)
+pub const WITHDRAW_PUBLIC_INPUTS_LAYOUT: [&str; 7] = [
+    "merkle_root",
+    "nullifier",
+    "recipient",
+    "relayer",
+    "fee",
+    "amount",
+    "domain_separator",
+];
+
+/// Prueba de retiro: pertenencia al anonymous set, nullifier y monto revelado
+/// ligados al receptor/relayer/fee. No incluye range proof: el monto es público.
+/// No se publica la hoja del depósito: `anonymity_set` es una ventana de notas
+/// N_i del árbol (su raíz es `merkle_proof.leaf_hash`) y `membership_proof`
+/// prueba que una de ellas abre a `amount` con la clave de gasto S, sin decir cuál
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WithdrawProof {
+    pub nullifier: String,
+    pub spend_key: String,
+    pub anonymity_set: Vec<String>,
+    pub merkle_proof: MerkleProof,
+    pub merkle_root: String,
+    pub amount: String, // En wei, revelado
+    pub binding: TransferBinding,
+    pub domain: Domain,
+    pub public_inputs: Vec<String>,
+    pub statement_hash: String,
+    pub denomination_id: Option<u32>,
+    pub spend_signature: SpendSignature,
+    pub membership_proof: OrProof,
+}
+
+/// Entradas públicas del retiro según `WITHDRAW_PUBLIC_INPUTS_LAYOUT`
+pub fn withdraw_public_inputs(
+    merkle_root: &str,
+    nullifier: &str,
+    binding: &TransferBinding,
+    amount: U256,
+    domain: &Domain,
+) -> Result<Vec<String>, String> {
+    let fee = parse_amount(&binding.fee, "fee")?;
+    Ok(vec![
+        merkle_root.to_string(),
+        nullifier.to_string(),
+        normalize_felt_hex(&binding.recipient),
+        normalize_felt_hex(&binding.relayer),
+        format!("0x{:032x}", fee),
+        format!("0x{:032x}", amount),
+        domain.separator_hex(),
+    ])
+}
+
+fn withdraw_statement_hash(proof: &WithdrawProof) -> String {
+    let mut transcript = Transcript::new("ceaser-withdraw-statement-v1");
+    proof.domain.append_to(&mut transcript);
+    for (label, input) in WITHDRAW_PUBLIC_INPUTS_LAYOUT.iter().zip(&proof.public_inputs) {
+        transcript.append_str(label, input);
+    }
+    transcript.append_str("spend_key", &proof.spend_key);
+    for note in &proof.anonymity_set {
+        transcript.append_str("anonymity_note", note);
+    }
+    transcript.append_str("window_root", &proof.merkle_proof.leaf_hash);
+    transcript.append_str("window_index", &proof.merkle_proof.leaf_index.to_string());
+    if let Some(denomination_id) = proof.denomination_id {
+        transcript.append_u64("denomination_id", denomination_id as u64);
+    }
+    format!("0x{}", hex::encode(transcript.challenge_bytes("statement")))
+}
+
+/// Raíz de la ventana del anonymous set: Keccak del par ordenado por nivel sobre
+/// las hojas (`note_leaf`) de sus notas. El tamaño debe ser potencia de dos
+pub fn anonymity_window_root(
+    anonymity_set: &[G1Projective],
+    denomination_id: Option<u32>,
+    domain: &Domain,
+) -> Result<String, String> {
+    if anonymity_set.is_empty() || !anonymity_set.len().is_power_of_two() {
+        return Err(format!("Anonymity set size {} is not a power of two", anonymity_set.len()));
+    }
+
+    let mut level = anonymity_set
+        .iter()
+        .map(|note| {
+            let leaf = note_leaf(note, denomination_id, domain)?;
+            hex::decode(leaf.commitment_hash.trim_start_matches("0x")).map_err(|e| format!("Invalid leaf hash: {}", e))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let mut hasher = Keccak256::new();
+                hasher.update(&pair[0]);
+                hasher.update(&pair[1]);
+                hasher.finalize().to_vec()
+            })
+            .collect();
+    }
+    Ok(format!("0x{}", hex::encode(&level[0])))
+}
+
+/// P_i = N_i − S − v·A: solo la nota propia es múltiplo de H (su cegado r)
+fn membership_statements(anonymity_set: &[G1Projective], spend_key: &G1Projective, value: &G1Projective) -> Vec<G1Projective> {
+    anonymity_set.iter().map(|note| *note - spend_key - value).collect()
+}
+
+fn membership_transcript(statement_hash: &str) -> Transcript {
+    let mut transcript = Transcript::new("ceaser-withdraw-membership-v1");
+    transcript.append_str("statement", statement_hash);
+    transcript
+}
+
+/// Verifica una prueba de retiro contra el config del pool; si se pasa un
+/// registro, el nullifier no debe estar gastado
+pub fn verify_withdraw(
+    proof: &WithdrawProof,
+    config: &ZKProofConfig,
+    nullifiers: Option<&dyn NullifierSet>,
+) -> Result<VerificationReport, JsValue> {
+    let nullifier = proof.nullifier.as_str();
+
+    if !proof.domain.matches(&config.domain) {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::WrongDomain, nullifier, "withdrawal belongs to another chain/contract/version",
+        ));
+    }
+
+    let amount = match parse_amount(&proof.amount, "amount") {
+        Ok(amount) => amount,
+        Err(e) => return Ok(VerificationReport::failed(VerificationFailure::InvalidPublicInputs, nullifier, &e)),
+    };
+
+    // En modo denominación solo se retiran denominaciones del pool, con su ID declarado
+    let denomination_valid = match config.amount_mode.denomination_id(&proof.amount) {
+        Ok(denomination_id) => denomination_id == proof.denomination_id,
+        Err(_) => false,
+    };
+    if !denomination_valid {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::InvalidDenomination, nullifier, "withdrawn amount is not the declared pool denomination",
+        ));
+    }
+
+    let expected_inputs = match withdraw_public_inputs(
+        &proof.merkle_root, nullifier, &proof.binding, amount, &proof.domain
+    ) {
+        Ok(inputs) => inputs,
+        Err(e) => return Ok(VerificationReport::failed(VerificationFailure::InvalidPublicInputs, nullifier, &e)),
+    };
+    if proof.public_inputs != expected_inputs {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::InvalidPublicInputs, nullifier, "public inputs do not follow the withdraw layout",
+        ));
+    }
+
+    // El fee lo cobra el relayer del monto retirado
+    if parse_amount(&proof.binding.fee, "fee").unwrap_or(U256::MAX) > amount {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::BindingMismatch, nullifier, "fee exceeds withdrawn amount",
+        ));
+    }
+
+    // La ventana del anonymous set debe colgar del árbol del pool
+    let anonymity_set = match proof.anonymity_set.iter().map(|note| point_from_hex(note)).collect::<Result<Vec<_>, _>>() {
+        Ok(notes) => notes,
+        Err(e) => return Ok(VerificationReport::failed(VerificationFailure::InvalidPublicInputs, nullifier, &e)),
+    };
+    let window_valid = anonymity_window_root(&anonymity_set, proof.denomination_id, &proof.domain)
+        .map(|root| root == proof.merkle_proof.leaf_hash)
+        .unwrap_or(false);
+    let merkle_valid = window_valid && verify_merkle_proof_with_index(
+        proof.merkle_proof.proof_path.clone(),
+        &proof.merkle_root,
+        &proof.merkle_proof.leaf_hash,
+        proof.merkle_proof.leaf_index,
+    )?;
+    if !merkle_valid {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::InvalidMerkleProof, nullifier, "anonymity set is not in the pool tree",
+        ));
+    }
+
+    if proof.statement_hash != withdraw_statement_hash(proof) {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::StatementMismatch, nullifier, "public inputs do not match statement hash",
+        ));
+    }
+
+    // El nullifier sale de S y solo quien conoce s firma el statement
+    let spend_generator = G1Projective::from(nullifier_generator(&proof.domain.separator()));
+    let spend_key = match point_from_hex(&proof.spend_key) {
+        Ok(spend_key) => spend_key,
+        Err(e) => return Ok(VerificationReport::failed(VerificationFailure::InvalidSignature, nullifier, &e)),
+    };
+    let signature_valid = proof.spend_signature.spend_key == proof.spend_key
+        && nullifier_from_spend_key(&spend_key, &proof.domain) == proof.nullifier
+        && proof.spend_signature.verify(&spend_generator, &proof.statement_hash).unwrap_or(false);
+    if !signature_valid {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::InvalidSignature, nullifier, "nullifier is not signed by its spend key",
+        ));
+    }
+
+    // Alguna nota de la ventana abre a (amount, S): el monto queda ligado a la hoja
+    let Some(amount) = amount.as_u128() else {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::InvalidPublicInputs, nullifier, "withdraw amount must fit in 128 bits",
+        ));
+    };
+    let value = pool_value_generator(config)? * Fr::from(amount);
+    let statements = membership_statements(&anonymity_set, &spend_key, &value);
+    let membership_valid = proof
+        .membership_proof
+        .verify(&statements, &mut membership_transcript(&proof.statement_hash))
+        .unwrap_or(false);
+    if !membership_valid {
+        return Ok(VerificationReport::failed(
+            VerificationFailure::InvalidCommitment, nullifier, "no note in the anonymity set opens to the withdrawn amount",
+        ));
+    }
+
+    if let Some(set) = nullifiers {
+        if set.contains(nullifier) {
+            return Ok(VerificationReport::failed(
+                VerificationFailure::AlreadySpent, nullifier, "nullifier already spent",
+            ));
+        }
+    }
+
+    Ok(VerificationReport::success(nullifier))
+}
+
+/// Construye la prueba de retiro de la nota (amount, nonce, user_secret).
+/// `anonymity_set` son los puntos N_i de la ventana del árbol que contiene la nota
+/// y `window_proof` el camino de la raíz de la ventana a la raíz del pool.
+/// `rng` genera los nonces de la prueba de pertenencia
+#[allow(clippy::too_many_arguments)]
+pub fn build_withdraw_proof(
+    amount_wei: &str,
+    nonce: &str,
+    user_secret: &str,
+    recipient: &str,
+    relayer: &str,
+    fee_wei: &str,
+    anonymity_set: &[String],
+    window_proof: MerkleProof,
+    config: &ZKProofConfig,
+    rng: &mut ProofRng,
+) -> Result<WithdrawProof, JsValue> {
+    crate::console_log!("📤 Generando prueba de retiro: {} wei hacia {}", amount_wei, recipient);
+
+    let amount = parse_amount(amount_wei, "amount")?;
+    let fee = parse_amount(fee_wei, "fee")?;
+    if fee > amount {
+        return Err(format!("Fee {} exceeds amount {}", fee, amount).into());
+    }
+    let value_amount = amount.as_u128().ok_or("Withdraw amount must fit in 128 bits")?;
+
+    // Se reconstruye la nota propia y se localiza en la ventana; solo se revelan S y el monto
+    // (en modo denominación el monto debe ser una de las denominaciones del pool)
+    let denomination_id = config.amount_mode.denomination_id(amount_wei)?;
+    let (note_commitment, spend_secret) = time_phase(ProofPhase::Commitment, || {
+        deposit_note_commitment(amount_wei, nonce, user_secret, config)
+    })?;
+    let notes = anonymity_set.iter().map(|note| point_from_hex(note)).collect::<Result<Vec<_>, _>>()?;
+    let secret_index = notes
+        .iter()
+        .position(|note| *note == note_commitment)
+        .ok_or("Deposit note is not in the anonymity set")?;
+
+    let spend_generator = G1Projective::from(nullifier_generator(&config.domain.separator()));
+    let spend_key = spend_generator * spend_secret;
+    let nullifier = nullifier_from_spend_key(&spend_key, &config.domain);
+
+    let window_root = anonymity_window_root(&notes, denomination_id, &config.domain)?;
+    if window_proof.leaf_hash != window_root {
+        return Err(format!("Merkle proof starts at {} instead of the window root {}", window_proof.leaf_hash, window_root).into());
+    }
+    let merkle_proof = window_proof;
+    let merkle_root = merkle_proof.root.clone();
+
+    let binding = TransferBinding {
+        recipient: recipient.to_string(),
+        relayer: relayer.to_string(),
+        fee: fee.to_string(),
+    };
+    let public_inputs = withdraw_public_inputs(&merkle_root, &nullifier, &binding, amount, &config.domain)?;
+
+    let mut proof = WithdrawProof {
+        nullifier,
+        spend_key: point_to_hex(&spend_key),
+        anonymity_set: anonymity_set.to_vec(),
+        merkle_proof,
+        merkle_root,
+        amount: amount.to_string(),
+        binding,
+        domain: config.domain.clone(),
+        public_inputs,
+        statement_hash: String::new(),
+        denomination_id,
+        spend_signature: SpendSignature::default(),
+        membership_proof: OrProof::default(),
+    };
+    proof.statement_hash = withdraw_statement_hash(&proof);
+    proof.spend_signature = SpendSignature::sign(&spend_secret, &spend_generator, &proof.statement_hash);
+
+    // N − S − v·A = r·H con r el cegado derivado del nonce
+    let nonce_bytes = hex::decode(nonce.trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid nonce hex: {}", e))?;
+    let value = pool_value_generator(config)? * Fr::from(value_amount);
+    proof.membership_proof = OrProof::prove(
+        &membership_statements(&notes, &spend_key, &value),
+        secret_index,
+        &value_blinding_from_bytes(&nonce_bytes),
+        &mut membership_transcript(&proof.statement_hash),
+        rng,
+    )?;
+
+    crate::console_log!("✅ Prueba de retiro generada, nullifier {}", proof.nullifier);
+    Ok(proof)
+}
+
+/// Genera la prueba de retiro (membership + nullifier + binding + monto revelado)
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn generate_withdraw_proof(
+    amount_wei: &str,
+    nonce: &str,
+    user_secret: &str,
+    recipient: &str,
+    relayer: &str,
+    fee_wei: &str,
+    anonymity_set: Vec<String>,
+    config: &JsValue,
+) -> Result<JsValue, JsValue> {
+    console_error_panic_hook::set_once();
+
+    let config = parse_proof_config(config)?;
+    let recorder = ProofRecorder::start("withdraw_proof");
+
+    let notes = anonymity_set.iter().map(|note| point_from_hex(note)).collect::<Result<Vec<_>, _>>()?;
+    let denomination_id = config.amount_mode.denomination_id(amount_wei)?;
+    let window_root = anonymity_window_root(&notes, denomination_id, &config.domain)?;
+    let window_proof = build_anonymous_set_merkle_proof(amount_wei, &window_root)?;
+
+    let proof = build_withdraw_proof(
+        amount_wei, nonce, user_secret, recipient, relayer, fee_wei, &anonymity_set, window_proof, &config,
+        &mut ProofRng::from_entropy(),
+    )?;
+
+    let json_string = time_phase(ProofPhase::Serialization, || serde_json::to_string(&proof))
+        .map_err(|e| format!("Error serializing withdraw proof: {}", e))?;
+    recorder.finish(json_string.len());
+    Ok(JsValue::from_str(&json_string))
+}
+
+/// Verifica una prueba de retiro exigiendo receptor, relayer y fee esperados
+#[wasm_bindgen]
+pub fn verify_withdraw_proof(
+    proof_json: &str,
+    recipient: &str,
+    relayer: &str,
+    fee_wei: &str,
+    config: &JsValue,
+) -> Result<bool, JsValue> {
+    let proof: WithdrawProof = serde_json::from_str(proof_json)
+        .map_err(|e| format!("Error parsing withdraw proof: {}", e))?;
+    let config = parse_proof_config(config)?;
+
+    if !proof.binding.matches(recipient, relayer, fee_wei) {
+        crate::console_log!("❌ El retiro está ligado a otro receptor/relayer/fee");
+        return Ok(false);
+    }
+
+    let report = verify_withdraw(&proof, &config, None)?;
+    crate::console_log!("📋 Verificación retiro: valid={}, failure={:?}", report.valid, report.failure);
+    Ok(report.valid)
+}
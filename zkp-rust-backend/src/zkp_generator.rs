@@ -1,16 +1,30 @@
 use wasm_bindgen::prelude::*;
+use ark_bn254::{Fr, G1Projective};
+use ark_ff::PrimeField;
 use sha3::{Digest, Keccak256};
-use crate::{PedersenCommitment, CircleStarkRangeProof};
+use crate::value_commitment::{nullifier_generator, point_to_hex};
+use crate::{commitment_from_hash, parse_amount, PedersenCommitment, CircleStarkRangeProof, Domain, U256};
 
-/// Genera un commitment de Pedersen para ocultar el monto
+/// Genera un commitment de Pedersen con la derivación v0 (sin dominio), la de
+/// las notas existentes: cambiarla movería sus commitments y nullifiers
 #[wasm_bindgen]
 pub fn generate_pedersen_commitment(
     amount_wei: &str,
     nonce: &str,
+) -> Result<PedersenCommitment, JsValue> {
+    generate_pedersen_commitment_for_domain(amount_wei, nonce, &Domain::legacy())
+}
+
+/// Genera un commitment de Pedersen separado por dominio (cadena + contrato + versión).
+/// La versión del dominio elige la derivación: v0 es la original, sin separador
+#[wasm_bindgen]
+pub fn generate_pedersen_commitment_for_domain(
+    amount_wei: &str,
+    nonce: &str,
+    domain: &Domain,
 ) -> Result<PedersenCommitment, JsValue> {
     // Parsear monto
-    let amount = amount_wei.parse::<u128>()
-        .map_err(|e| format!("Invalid amount: {}", e))?;
+    let amount = parse_amount(amount_wei, "amount")?;
     
     // Parsear nonce (hex string)
     let nonce_bytes = hex::decode(nonce.trim_start_matches("0x"))
@@ -18,27 +32,31 @@ pub fn generate_pedersen_commitment(
     
     // Simular Pedersen commitment usando Keccak (placeholder)
     // En producción, usar la implementación real de Pedersen de StarkNet
+    let commitment_hash = if domain.is_legacy() {
+        legacy_commitment_hash(amount, &nonce_bytes)?
+    } else {
+        // Monto de ancho fijo y nonce con prefijo de longitud: una sola lectura por entrada
+        let mut hasher = Keccak256::new();
+        hasher.update(domain.separator());
+        hasher.update(amount.to_le_bytes());
+        hasher.update((nonce_bytes.len() as u32).to_le_bytes());
+        hasher.update(&nonce_bytes);
+        hasher.finalize().into()
+    };
+    
+    // Coordenadas de punto de curva elíptica (simuladas) derivadas del hash
+    Ok(commitment_from_hash(&commitment_hash))
+}
+
+/// Keccak(monto u128 LE ‖ nonce): el monto ocupa siempre 16 bytes, así que v0
+/// no admite montos de 2^128 o más
+fn legacy_commitment_hash(amount: U256, nonce_bytes: &[u8]) -> Result<[u8; 32], String> {
+    let amount = amount.as_u128()
+        .ok_or_else(|| format!("Amount {} does not fit the legacy u128 commitment", amount))?;
     let mut hasher = Keccak256::new();
     hasher.update(amount.to_le_bytes());
-    hasher.update(&nonce_bytes);
-    let commitment_hash = hasher.finalize();
-    
-    // Generar coordenadas de punto de curva elíptica (simulado)
-    let mut x_hasher = Keccak256::new();
-    x_hasher.update(&commitment_hash);
-    x_hasher.update(b"x_coord");
-    let x_coord = x_hasher.finalize();
-    
-    let mut y_hasher = Keccak256::new();
-    y_hasher.update(&commitment_hash);
-    y_hasher.update(b"y_coord");
-    let y_coord = y_hasher.finalize();
-    
-    Ok(PedersenCommitment {
-        x: format!("0x{}", hex::encode(x_coord)),
-        y: format!("0x{}", hex::encode(y_coord)),
-        commitment_hash: format!("0x{}", hex::encode(commitment_hash)),
-    })
+    hasher.update(nonce_bytes);
+    Ok(hasher.finalize().into())
 }
 
 /// Verifica un commitment de Pedersen
@@ -52,12 +70,29 @@ pub fn verify_pedersen_commitment(commitment: &PedersenCommitment) -> Result<boo
     Ok(x_valid && y_valid && hash_valid)
 }
 
-/// Genera nullifier único para evitar doble gasto
+/// Genera nullifier único para evitar doble gasto (derivación v0, sin dominio)
 #[wasm_bindgen]
 pub fn generate_nullifier(
     commitment_hash: &str,
     user_secret: &str,
 ) -> Result<String, JsValue> {
+    generate_nullifier_for_domain(commitment_hash, user_secret, &Domain::legacy())
+}
+
+/// Genera nullifier separado por dominio: no es reutilizable en otra cadena o contrato.
+/// En v0 es el nullifier original, Keccak(commitment ‖ secreto ‖ "nullifier");
+/// desde v1 es el hash de la clave de gasto S = s·K_d
+#[wasm_bindgen]
+pub fn generate_nullifier_for_domain(
+    commitment_hash: &str,
+    user_secret: &str,
+    domain: &Domain,
+) -> Result<String, JsValue> {
+    if !domain.is_legacy() {
+        let (_, spend_key) = spend_key_for_domain(commitment_hash, user_secret, domain)?;
+        return Ok(nullifier_from_spend_key(&spend_key, domain));
+    }
+    
     let commitment_bytes = hex::decode(commitment_hash.trim_start_matches("0x"))
         .map_err(|e| format!("Invalid commitment hash: {}", e))?;
     
@@ -73,6 +108,39 @@ pub fn generate_nullifier(
     Ok(format!("0x{}", hex::encode(nullifier)))
 }
 
+/// Clave de gasto de la nota en el dominio: s = H(separador, commitment, secreto) y S = s·K_d
+pub fn spend_key_for_domain(
+    commitment_hash: &str,
+    user_secret: &str,
+    domain: &Domain,
+) -> Result<(Fr, G1Projective), String> {
+    let commitment_bytes = hex::decode(commitment_hash.trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid commitment hash: {}", e))?;
+    let secret_bytes = hex::decode(user_secret.trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid user secret: {}", e))?;
+    
+    let separator = domain.separator();
+    let mut hasher = Keccak256::new();
+    hasher.update(b"ceaser-spend-key-v1");
+    hasher.update(separator);
+    hasher.update((commitment_bytes.len() as u32).to_le_bytes());
+    hasher.update(&commitment_bytes);
+    hasher.update((secret_bytes.len() as u32).to_le_bytes());
+    hasher.update(&secret_bytes);
+    let secret = Fr::from_le_bytes_mod_order(&hasher.finalize());
+    
+    Ok((secret, G1Projective::from(nullifier_generator(&separator)) * secret))
+}
+
+/// Nullifier v1: Keccak(separador ‖ S ‖ "nullifier"), comprobable desde la clave pública
+pub fn nullifier_from_spend_key(spend_key: &G1Projective, domain: &Domain) -> String {
+    let mut hasher = Keccak256::new();
+    hasher.update(domain.separator());
+    hasher.update(point_to_hex(spend_key).as_bytes());
+    hasher.update(b"nullifier");
+    format!("0x{}", hex::encode(hasher.finalize()))
+}
+
 /// Genera range proof usando arkworks (fallback si STWO no está disponible)
 #[wasm_bindgen]
 pub fn generate_arkworks_range_proof(
@@ -81,12 +149,9 @@ pub fn generate_arkworks_range_proof(
     min_amount: &str,
     max_amount: &str,
 ) -> Result<CircleStarkRangeProof, JsValue> {
-    let amount = amount_wei.parse::<u128>()
-        .map_err(|e| format!("Invalid amount: {}", e))?;
-    let min = min_amount.parse::<u128>()
-        .map_err(|e| format!("Invalid min amount: {}", e))?;
-    let max = max_amount.parse::<u128>()
-        .map_err(|e| format!("Invalid max amount: {}", e))?;
+    let amount = parse_amount(amount_wei, "amount")?;
+    let min = parse_amount(min_amount, "min amount")?;
+    let max = parse_amount(max_amount, "max amount")?;
     
     // Verificar que el monto está en rango
     if amount < min || amount > max {
@@ -117,6 +182,7 @@ pub fn generate_arkworks_range_proof(
         public_inputs,
         circle_evaluations: vec![], // Se llenará con STWO
         fri_commitments: vec![],    // Se llenará con STWO
+        fri_proof: None,
     })
 }
 
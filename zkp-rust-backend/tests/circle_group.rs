@@ -0,0 +1,75 @@
+//! Grupo del círculo sobre M31: ley de grupo, órdenes de los generadores y
+//! dominios canónicos.
+
+use std::collections::HashSet;
+use zkp_ceaser::{generate_circle_points, verify_circle_domain, CircleDomain, CirclePoint, Coset};
+
+const LOG_ORDER: u32 = 31;
+
+#[test]
+fn group_law_holds_on_generator_multiples() {
+    let g = CirclePoint::generator();
+    let zero = CirclePoint::zero();
+    assert!(g.is_on_circle() && zero.is_on_circle());
+
+    let points: Vec<CirclePoint> = [1u64, 2, 7, 1 << 20, (1 << 31) - 1].iter().map(|k| g.mul_scalar(*k)).collect();
+    for p in &points {
+        assert!(p.is_on_circle());
+        assert_eq!(p.add(&zero), *p);
+        assert_eq!(p.add(&p.conjugate()), zero);
+        assert_eq!(p.double(), p.add(p));
+        for q in &points {
+            assert_eq!(p.add(q), q.add(p));
+            for r in &points {
+                assert_eq!(p.add(q).add(r), p.add(&q.add(r)));
+            }
+        }
+    }
+    assert_eq!(g.mul_scalar(5).sub(&g.mul_scalar(3)), g.mul_scalar(2));
+}
+
+#[test]
+fn generator_has_order_two_to_the_31() {
+    let g = CirclePoint::generator();
+    assert_eq!(g.repeated_double(LOG_ORDER), CirclePoint::zero());
+    assert_ne!(g.repeated_double(LOG_ORDER - 1), CirclePoint::zero());
+
+    for log_size in [1, 5, 16, LOG_ORDER] {
+        let subgroup = Coset::subgroup(log_size).unwrap();
+        assert_eq!(subgroup.step.repeated_double(log_size), CirclePoint::zero());
+        assert_ne!(subgroup.step.repeated_double(log_size - 1), CirclePoint::zero());
+    }
+}
+
+#[test]
+fn canonic_domains_are_distinct_points_on_the_circle() {
+    for log_size in 1..=12 {
+        assert!(verify_circle_domain(log_size).unwrap(), "log size {}", log_size);
+
+        let domain = CircleDomain::canonic(log_size).unwrap();
+        let points: Vec<CirclePoint> = domain.iter().collect();
+        assert_eq!(points.len(), 1 << log_size);
+        assert!(points.iter().all(CirclePoint::is_on_circle));
+        let distinct: HashSet<(u32, u32)> = points.iter().map(|p| (p.x().value(), p.y().value())).collect();
+        assert_eq!(distinct.len(), points.len());
+    }
+}
+
+#[test]
+fn circle_points_cover_requested_count() {
+    assert_eq!(generate_circle_points(0).unwrap().len(), 0);
+    assert_eq!(generate_circle_points(1).unwrap().len(), 1);
+    assert_eq!(generate_circle_points(100).unwrap().len(), 100);
+    assert_eq!(CircleDomain::covering(100).unwrap().log_size(), 7);
+    assert_eq!(CircleDomain::covering(1).unwrap().log_size(), 1);
+}
+
+#[test]
+fn oversized_domains_are_rejected() {
+    assert_eq!(CircleDomain::covering(1 << 30).unwrap().log_size(), 30);
+    for num_points in [(1 << 30) + 1, 1 << 31, u32::MAX] {
+        assert!(CircleDomain::covering(num_points).is_err(), "{} points", num_points);
+    }
+    assert!(CircleDomain::canonic(u32::MAX).is_err());
+    assert!(Coset::half_odds(u32::MAX).is_err());
+}
@@ -0,0 +1,103 @@
+//! Utilidades compartidas por los tests de integración: rutas de `proofs/`,
+//! comparación con ficheros dorados, vectores JSON y caminos Merkle.
+#![allow(dead_code)]
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use zkp_ceaser::{IncrementalMerkleTree, MerkleProof};
+
+/// Versión del formato de los vectores (`proofs/vectors/*.json`)
+pub const VECTORS_VERSION: u32 = 1;
+
+pub fn proofs_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../proofs")
+}
+
+pub fn update_mode() -> bool {
+    std::env::var_os("UPDATE_GOLDEN").is_some()
+}
+
+/// Compara `actual` con `proofs/<relative>` (o lo reescribe con UPDATE_GOLDEN)
+pub fn assert_golden(relative: &str, actual: &str) {
+    let path = proofs_dir().join(relative);
+    if update_mode() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, format!("{}\n", actual)).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("missing golden file {} ({}); run with UPDATE_GOLDEN=1", path.display(), e));
+    assert!(expected.trim_end() == actual, "{} no longer matches the golden file", relative);
+}
+
+pub fn read_json<T: DeserializeOwned>(relative: &str) -> T {
+    let path = proofs_dir().join(relative);
+    let json = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("cannot read {}: {}", path.display(), e));
+    serde_json::from_str(&json).unwrap_or_else(|e| panic!("invalid JSON in {}: {}", path.display(), e))
+}
+
+/// Fichero de vectores: lo que consumen tanto estos tests como el lado TypeScript
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VectorFile<I, E> {
+    pub version: u32,
+    pub description: String,
+    pub cases: Vec<VectorCase<I, E>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VectorCase<I, E> {
+    pub name: String,
+    pub input: I,
+    pub expected: E,
+}
+
+/// Recalcula `expected` para cada entrada y lo compara con `proofs/vectors/<file>`
+pub fn check_vectors<I, E>(file: &str, description: &str, inputs: Vec<(&str, I)>, compute: impl Fn(&I) -> E)
+where
+    I: Serialize,
+    E: Serialize,
+{
+    let vectors = VectorFile {
+        version: VECTORS_VERSION,
+        description: description.to_string(),
+        cases: inputs
+            .into_iter()
+            .map(|(name, input)| {
+                let expected = compute(&input);
+                VectorCase { name: name.to_string(), input, expected }
+            })
+            .collect(),
+    };
+    assert_golden(&format!("vectors/{}", file), &serde_json::to_string_pretty(&vectors).unwrap());
+}
+
+/// Entero como hex de 32 bytes ("0x000…01"): nonces, secretos y hojas de relleno
+pub fn hex32(value: u64) -> String {
+    format!("0x{:064x}", value)
+}
+
+/// Árbol de altura 10 (el anonymous set de 1024) con `leaves` a partir de
+/// `first_index`; las hojas anteriores son de relleno
+fn tree_with_leaves_at(leaves: &[String], first_index: u32) -> IncrementalMerkleTree {
+    let mut tree = IncrementalMerkleTree::new(10).unwrap();
+    for filler in 0..first_index {
+        tree.append(&hex32(u64::from(filler) + 1)).unwrap();
+    }
+    for leaf in leaves {
+        tree.append(leaf).unwrap();
+    }
+    tree
+}
+
+/// Camino de `leaf_hash` en la posición `leaf_index`
+pub fn merkle_proof_at(leaf_hash: &str, leaf_index: u32) -> MerkleProof {
+    tree_with_leaves_at(&[leaf_hash.to_string()], leaf_index).proof(leaf_index).unwrap()
+}
+
+/// Camino de la raíz de la ventana `window` (tamaño potencia de dos) colocada
+/// como subárbol número `window_index` de su nivel
+pub fn window_proof_at(window: &[String], window_index: u32) -> MerkleProof {
+    let size = window.len() as u32;
+    tree_with_leaves_at(window, window_index * size).window_proof(window_index * size, size).unwrap()
+}
@@ -0,0 +1,462 @@
+//! Conformidad de pruebas completas: la prueba CEASER de referencia debe
+//! verificarse y cada manipulación de `proofs/vectors/*_rejections.json`
+//! debe rechazarse con el motivo indicado. Los casos se describen como
+//! (JSON pointer, valor nuevo) para poder repetirlos desde TypeScript.
+//!
+//! Regenerar tras un cambio intencionado: `UPDATE_GOLDEN=1 cargo test --test conformance`
+#![cfg(all(feature = "mock-stwo", not(feature = "real-stwo")))]
+
+mod common;
+
+use common::{assert_golden, hex32, merkle_proof_at, read_json, VectorCase, VectorFile, VECTORS_VERSION};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use zkp_ceaser::value_commitment::{point_to_hex, value_generator};
+use zkp_ceaser::{
+    build_deposit_proof, build_verification_report, build_verification_report_for_config,
+    compute_statement_hash, deposit_note_commitment, encrypt_receiver_metadata, generate_nullifier,
+    generate_nullifier_for_domain, generate_pedersen_commitment_for_domain, generate_stwo_range_proof, sign_statement,
+    spent_leaf_hash, verify_and_record_nullifier, verify_deposit, verify_merkle_proof_with_index,
+    AmountMode, CeaserZKProof, DepositProof, Domain, InMemoryNullifierSet, MerkleProof, NullifierSet,
+    PedersenCommitment, TransferBinding, VerificationFailure, VerificationReport, ZKProofConfig,
+};
+
+/// Prueba del ejemplo de `proofs/`: su commitment y su camino Merkle son reales
+const EXAMPLE_PROOF: &str = "ceaser-zk-proof-valid-example.json";
+const EXAMPLE_AMOUNT_WEI: &str = "24929000000000000000";
+const RECEIVER: &str = "0x02d4c0a53f31F0f359B5f439728A05273c23f0fA6FE2405A691DFd09FAfAFa49";
+const RELAYER: &str = "0x5793";
+const FEE_WEI: &str = "100000000000000";
+const USER_SECRET: u64 = 0xc0ffee;
+const LEAF_INDEX: u32 = 5;
+
+/// Cambia el último nibble de un valor hex
+fn flip_hex(value: &str) -> String {
+    let mut flipped = value.to_string();
+    let last = flipped.pop().unwrap();
+    flipped.push(if last == '0' { '1' } else { '0' });
+    flipped
+}
+
+/// Pone la hoja que gasta la prueba (`spent_leaf_hash`) en el árbol de relleno
+fn place_in_tree(proof: &mut CeaserZKProof) {
+    proof.merkle_proof = merkle_proof_at(&spent_leaf_hash(proof).unwrap(), LEAF_INDEX);
+    proof.merkle_root = proof.merkle_proof.root.clone();
+}
+
+/// Prueba CEASER de referencia: commitment del ejemplo, range proof sobre ese
+/// commitment, nullifier y metadata recalculados, la nota N del mismo monto en
+/// el árbol y declaración ligada a receptor/relayer/fee
+fn conformance_proof() -> CeaserZKProof {
+    let example: Value = read_json(EXAMPLE_PROOF);
+    let commitment: PedersenCommitment = serde_json::from_value(example["zk_proof"]["amount_commitment"].clone()).unwrap();
+    let config = ZKProofConfig::default();
+    let user_secret = hex32(USER_SECRET);
+    let (note_commitment, _) = deposit_note_commitment(EXAMPLE_AMOUNT_WEI, &hex32(1), &user_secret, &config).unwrap();
+
+    let mut proof = CeaserZKProof {
+        range_proof: generate_stwo_range_proof(
+            EXAMPLE_AMOUNT_WEI, &hex32(1), &config.min_amount, &config.max_amount, &commitment.commitment_hash,
+        )
+        .unwrap(),
+        nullifier: generate_nullifier_for_domain(&commitment.commitment_hash, &user_secret, &config.domain).unwrap(),
+        merkle_root: String::new(),
+        merkle_proof: MerkleProof { proof_path: Vec::new(), leaf_index: 0, leaf_hash: String::new(), root: String::new() },
+        encrypted_metadata: encrypt_receiver_metadata(RECEIVER, &user_secret).unwrap(),
+        amount_commitment: commitment,
+        binding: TransferBinding {
+            recipient: RECEIVER.to_string(),
+            relayer: RELAYER.to_string(),
+            fee: FEE_WEI.to_string(),
+        },
+        statement_hash: String::new(),
+        domain: config.domain,
+        denomination_id: None,
+        spend_signature: None,
+        note_commitment: Some(point_to_hex(&note_commitment)),
+    };
+    place_in_tree(&mut proof);
+    sign_statement(&mut proof, &user_secret).unwrap();
+    proof
+}
+
+/// Manipulación de un caso: reemplazar el valor en `pointer` y/o marcar nullifiers como gastados
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Tamper {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pointer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    value: Option<Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    spent_nullifiers: Vec<String>,
+}
+
+impl Tamper {
+    fn replace(pointer: &str, value: Value) -> Tamper {
+        Tamper { pointer: Some(pointer.to_string()), value: Some(value), ..Tamper::default() }
+    }
+
+    fn apply(&self, proof: &Value) -> Value {
+        let mut tampered = proof.clone();
+        if let (Some(pointer), Some(value)) = (&self.pointer, &self.value) {
+            *tampered.pointer_mut(pointer).unwrap_or_else(|| panic!("no field at {}", pointer)) = value.clone();
+        }
+        tampered
+    }
+
+    fn spent_set(&self) -> InMemoryNullifierSet {
+        let export = serde_json::to_string(&self.spent_nullifiers).unwrap();
+        InMemoryNullifierSet::from_json_export(&export).unwrap()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Outcome {
+    valid: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    failure: Option<VerificationFailure>,
+}
+
+impl Outcome {
+    fn accepted() -> Outcome {
+        Outcome { valid: true, failure: None }
+    }
+
+    fn rejected(failure: VerificationFailure) -> Outcome {
+        Outcome { valid: false, failure: Some(failure) }
+    }
+}
+
+impl From<VerificationReport> for Outcome {
+    fn from(report: VerificationReport) -> Self {
+        Outcome { valid: report.valid, failure: report.failure }
+    }
+}
+
+/// Aplica cada manipulación a `base`, comprueba el resultado esperado y
+/// guarda los casos en `proofs/vectors/<file>` para el lado TypeScript
+fn check_rejections(
+    file: &str,
+    description: &str,
+    base: &Value,
+    cases: Vec<(&str, Tamper, Outcome)>,
+    verify: impl Fn(&Value, &Tamper) -> Outcome,
+) {
+    for (name, tamper, expected) in &cases {
+        assert_eq!(&verify(&tamper.apply(base), tamper), expected, "case {}", name);
+    }
+    let vectors = VectorFile {
+        version: VECTORS_VERSION,
+        description: description.to_string(),
+        cases: cases
+            .into_iter()
+            .map(|(name, input, expected)| VectorCase { name: name.to_string(), input, expected })
+            .collect(),
+    };
+    assert_golden(&format!("vectors/{}", file), &serde_json::to_string_pretty(&vectors).unwrap());
+}
+
+#[test]
+fn conformance_proof_matches_golden_and_verifies() {
+    let proof = conformance_proof();
+    assert_golden("golden/ceaser-zk-proof-mock-stwo.json", &serde_json::to_string(&proof).unwrap());
+
+    let report = build_verification_report(&proof, &Domain::default(), None).unwrap();
+    assert!(report.valid, "reference proof rejected: {:?}", report);
+}
+
+#[test]
+fn merkle_vectors() {
+    #[derive(Deserialize)]
+    struct MerkleInput {
+        leaf_hash: String,
+        leaf_index: u32,
+        proof_path: Vec<String>,
+        root: String,
+    }
+    #[derive(Deserialize)]
+    struct MerkleExpected {
+        valid: bool,
+    }
+
+    let vectors: VectorFile<MerkleInput, MerkleExpected> = read_json("vectors/merkle.json");
+    assert_eq!(vectors.version, VECTORS_VERSION);
+    for case in vectors.cases {
+        let input = case.input;
+        let valid = verify_merkle_proof_with_index(input.proof_path, &input.root, &input.leaf_hash, input.leaf_index)
+            .unwrap_or(false);
+        assert_eq!(valid, case.expected.valid, "merkle case {}", case.name);
+    }
+}
+
+#[test]
+fn tampered_proofs_are_rejected() {
+    let proof = conformance_proof();
+    let base = serde_json::to_value(&proof).unwrap();
+    let at = |pointer: &str| base.pointer(pointer).unwrap().as_str().unwrap().to_string();
+    let work = base.pointer("/range_proof/fri_proof/proof_of_work").unwrap().as_u64().unwrap();
+
+    use VerificationFailure::*;
+    let cases = vec![
+        ("untampered", Tamper::default(), Outcome::accepted()),
+        ("wrong_root", Tamper::replace("/merkle_root", json!(flip_hex(&proof.merkle_root))), Outcome::rejected(InvalidMerkleProof)),
+        ("tampered_merkle_sibling", Tamper::replace("/merkle_proof/proof_path/3", json!(flip_hex(&at("/merkle_proof/proof_path/3")))), Outcome::rejected(InvalidMerkleProof)),
+        ("tampered_commitment_hash", Tamper::replace("/amount_commitment/commitment_hash", json!(flip_hex(&proof.amount_commitment.commitment_hash))), Outcome::rejected(InvalidRangeProof)),
+        ("range_proof_of_other_commitment", Tamper::replace("/range_proof/public_inputs/4", json!(flip_hex(&at("/range_proof/public_inputs/4")))), Outcome::rejected(InvalidRangeProof)),
+        ("malformed_commitment", Tamper::replace("/amount_commitment/x", json!("0x1234")), Outcome::rejected(InvalidCommitment)),
+        ("tampered_fee", Tamper::replace("/binding/fee", json!("1")), Outcome::rejected(StatementMismatch)),
+        ("tampered_recipient", Tamper::replace("/binding/recipient", json!(RELAYER)), Outcome::rejected(StatementMismatch)),
+        ("tampered_nullifier", Tamper::replace("/nullifier", json!(flip_hex(&proof.nullifier))), Outcome::rejected(StatementMismatch)),
+        ("wrong_chain", Tamper::replace("/domain/chain_id", json!("SN_MAIN")), Outcome::rejected(WrongDomain)),
+        ("tampered_spend_signature", Tamper::replace("/spend_signature/response", json!(flip_hex(&at("/spend_signature/response")))), Outcome::rejected(InvalidSignature)),
+        ("foreign_spend_key", Tamper::replace("/spend_signature/spend_key", json!(at("/spend_signature/nonce_commitment"))), Outcome::rejected(InvalidSignature)),
+        ("tampered_fri_last_layer", Tamper::replace("/range_proof/fri_proof/last_layer/0", json!(flip_hex(&at("/range_proof/fri_proof/last_layer/0")))), Outcome::rejected(InvalidRangeProof)),
+        ("tampered_proof_of_work", Tamper::replace("/range_proof/fri_proof/proof_of_work", json!(work + 1)), Outcome::rejected(InvalidRangeProof)),
+        (
+            "spent_nullifier",
+            Tamper { spent_nullifiers: vec![proof.nullifier.clone()], ..Tamper::default() },
+            Outcome::rejected(AlreadySpent),
+        ),
+    ];
+
+    check_rejections(
+        "proof_rejections.json",
+        "Tampered copies of golden/ceaser-zk-proof-mock-stwo.json: replace the value at `pointer` (RFC 6901), \
+         treat `spent_nullifiers` as already spent and verify against the default Sepolia domain",
+        &base,
+        cases,
+        |tampered, tamper| {
+            let proof: CeaserZKProof = serde_json::from_value(tampered.clone()).unwrap();
+            let spent = tamper.spent_set();
+            build_verification_report(&proof, &Domain::default(), Some(&spent)).unwrap().into()
+        },
+    );
+}
+
+#[test]
+fn rebinding_requires_the_spend_key() {
+    let proof = conformance_proof();
+    assert!(proof.binding.matches(RECEIVER, RELAYER, "0x5af3107a4000"));
+    assert!(!proof.binding.matches(RECEIVER, RELAYER, "100000000000001"));
+
+    // Sin el secreto se puede recalcular el hash, pero no la firma
+    let mut rebound = proof.clone();
+    rebound.binding.recipient = RELAYER.to_string();
+    rebound.statement_hash = compute_statement_hash(&rebound);
+    let report = build_verification_report(&rebound, &Domain::default(), None).unwrap();
+    assert_eq!(Outcome::from(report), Outcome::rejected(VerificationFailure::InvalidSignature));
+
+    let mut unsigned = proof;
+    unsigned.spend_signature = None;
+    let report = build_verification_report(&unsigned, &Domain::default(), None).unwrap();
+    assert_eq!(Outcome::from(report), Outcome::rejected(VerificationFailure::InvalidSignature));
+}
+
+#[test]
+fn legacy_unbound_proofs_verify_only_in_legacy_domain() {
+    let mut proof = conformance_proof();
+    proof.domain = Domain::legacy();
+    proof.nullifier = generate_nullifier(&proof.amount_commitment.commitment_hash, &hex32(USER_SECRET)).unwrap();
+    proof.statement_hash = String::new();
+    proof.spend_signature = None;
+    proof.note_commitment = None;
+    place_in_tree(&mut proof);
+
+    // Pruebas anteriores a los campos `domain` y `statement_hash`
+    let mut json = serde_json::to_value(&proof).unwrap();
+    for field in ["domain", "statement_hash", "binding", "denomination_id"] {
+        json.as_object_mut().unwrap().remove(field);
+    }
+    let legacy: CeaserZKProof = serde_json::from_value(json).unwrap();
+    assert!(build_verification_report(&legacy, &Domain::legacy(), None).unwrap().valid);
+    let report = build_verification_report(&legacy, &Domain::default(), None).unwrap();
+    assert_eq!(Outcome::from(report), Outcome::rejected(VerificationFailure::WrongDomain));
+
+    // Un hash presente en v0 se sigue comprobando
+    let mut tampered = legacy;
+    tampered.statement_hash = hex32(1);
+    let report = build_verification_report(&tampered, &Domain::legacy(), None).unwrap();
+    assert_eq!(Outcome::from(report), Outcome::rejected(VerificationFailure::StatementMismatch));
+}
+
+/// La prueba de `proofs/` tal como se generó antes de FRI, dominio y binding
+#[test]
+fn checked_in_v0_example_verifies_only_in_legacy_domain() {
+    let example: Value = read_json(EXAMPLE_PROOF);
+    let proof: CeaserZKProof = serde_json::from_value(example["zk_proof"].clone()).unwrap();
+    assert!(proof.range_proof.fri_proof.is_none());
+    assert_eq!(proof.domain, Domain::legacy());
+
+    let report = build_verification_report(&proof, &Domain::legacy(), None).unwrap();
+    assert!(report.valid, "v0 example rejected: {:?}", report);
+    let legacy_pool = ZKProofConfig { domain: Domain::legacy(), ..ZKProofConfig::default() };
+    assert!(build_verification_report_for_config(&proof, &legacy_pool, None).unwrap().valid);
+
+    let report = build_verification_report(&proof, &Domain::default(), None).unwrap();
+    assert_eq!(Outcome::from(report), Outcome::rejected(VerificationFailure::WrongDomain));
+
+    // Sin FRI solo vale como v0: declarada v1 pasa por el verificador del backend
+    let mut relabeled = proof.clone();
+    relabeled.domain = Domain::default();
+    let report = build_verification_report(&relabeled, &Domain::default(), None).unwrap();
+    assert_eq!(Outcome::from(report), Outcome::rejected(VerificationFailure::InvalidRangeProof));
+
+    // El formato v0 se sigue exigiendo
+    let mut truncated = proof;
+    truncated.range_proof.proof_data.truncate(4);
+    let report = build_verification_report(&truncated, &Domain::legacy(), None).unwrap();
+    assert_eq!(Outcome::from(report), Outcome::rejected(VerificationFailure::InvalidRangeProof));
+}
+
+#[test]
+fn tampered_deposits_are_rejected() {
+    let base: Value = read_json("golden/deposit-proof-mock-stwo.json");
+    let at = |pointer: &str| base.pointer(pointer).unwrap().as_str().unwrap().to_string();
+
+    use VerificationFailure::*;
+    let cases = vec![
+        ("untampered", Tamper::default(), Outcome::accepted()),
+        ("wrong_contract", Tamper::replace("/domain/contract_address", json!(RELAYER)), Outcome::rejected(WrongDomain)),
+        ("malformed_commitment", Tamper::replace("/amount_commitment/y", json!("0x1234")), Outcome::rejected(InvalidCommitment)),
+        ("unbound_note_commitment", Tamper::replace("/note_commitment", json!(point_to_hex(&value_generator().into()))), Outcome::rejected(InvalidCommitment)),
+        ("tampered_commitment_input", Tamper::replace("/public_inputs/0", json!(flip_hex(&at("/public_inputs/0")))), Outcome::rejected(InvalidPublicInputs)),
+        ("tampered_domain_separator", Tamper::replace("/public_inputs/3", json!(flip_hex(&at("/public_inputs/3")))), Outcome::rejected(InvalidPublicInputs)),
+        ("widened_max_amount", Tamper::replace("/public_inputs/2", json!(format!("0x{:032x}", u128::MAX))), Outcome::rejected(InvalidPublicInputs)),
+        ("tampered_statement_hash", Tamper::replace("/statement_hash", json!(flip_hex(&at("/statement_hash")))), Outcome::rejected(StatementMismatch)),
+    ];
+
+    check_rejections(
+        "deposit_rejections.json",
+        "Tampered copies of golden/deposit-proof-mock-stwo.json: replace the value at `pointer` (RFC 6901) \
+         and verify the deposit against the default Sepolia pool config",
+        &base,
+        cases,
+        |tampered, _| {
+            let proof: DepositProof = serde_json::from_value(tampered.clone()).unwrap();
+            verify_deposit(&proof, &ZKProofConfig::default()).unwrap().into()
+        },
+    );
+}
+
+#[test]
+fn reused_nullifier_is_rejected() {
+    let proof = conformance_proof();
+    let mut nullifiers = InMemoryNullifierSet::new();
+
+    let first = verify_and_record_nullifier(&proof, &Domain::default(), &mut nullifiers).unwrap();
+    assert!(first.valid);
+    assert!(nullifiers.contains(&proof.nullifier));
+
+    let second = verify_and_record_nullifier(&proof, &Domain::default(), &mut nullifiers).unwrap();
+    assert_eq!(Outcome::from(second), Outcome::rejected(VerificationFailure::AlreadySpent));
+    assert_eq!(nullifiers.len(), 1);
+}
+
+#[test]
+fn range_proof_must_be_over_the_amount_commitment() {
+    let config = ZKProofConfig::default();
+
+    // Range proof honesta del mismo monto con nonce hex32(1), pero de otro commitment
+    let unrelated = generate_pedersen_commitment_for_domain(EXAMPLE_AMOUNT_WEI, &hex32(1), &config.domain).unwrap();
+    let mut proof = conformance_proof();
+    assert_ne!(unrelated.commitment_hash, proof.amount_commitment.commitment_hash);
+    proof.range_proof = generate_stwo_range_proof(
+        EXAMPLE_AMOUNT_WEI, &hex32(1), &config.min_amount, &config.max_amount, &unrelated.commitment_hash,
+    )
+    .unwrap();
+    sign_statement(&mut proof, &hex32(USER_SECRET)).unwrap();
+    let report = build_verification_report(&proof, &config.domain, None).unwrap();
+    assert_eq!(Outcome::from(report), Outcome::rejected(VerificationFailure::InvalidRangeProof));
+
+    // Lo mismo en depósitos: la range proof de otro depósito no vale
+    let deposit = build_deposit_proof(EXAMPLE_AMOUNT_WEI, &hex32(1), &hex32(USER_SECRET), &config).unwrap();
+    let mut swapped = build_deposit_proof(EXAMPLE_AMOUNT_WEI, &hex32(2), &hex32(USER_SECRET), &config).unwrap();
+    swapped.range_proof = deposit.range_proof;
+    let report = verify_deposit(&swapped, &config).unwrap();
+    assert_eq!(Outcome::from(report), Outcome::rejected(VerificationFailure::InvalidRangeProof));
+}
+
+#[test]
+fn out_of_range_amounts_are_rejected() {
+    let config = ZKProofConfig::default();
+    let nonce = hex32(1);
+    for amount in [&config.min_amount, &config.max_amount] {
+        let deposit = build_deposit_proof(amount, &nonce, &hex32(USER_SECRET), &config).unwrap();
+        assert!(verify_deposit(&deposit, &config).unwrap().valid, "{} is inside the pool range", amount);
+    }
+
+    // Range proofs honestas pero para límites más amplios que los del pool
+    let (wide_min, wide_max) = ("0", "1000000000000000000000000000000");
+    for amount in ["999999999999999", "1000000000000000000001"] {
+        let mut proof = conformance_proof();
+        proof.range_proof =
+            generate_stwo_range_proof(amount, &nonce, wide_min, wide_max, &proof.amount_commitment.commitment_hash).unwrap();
+        sign_statement(&mut proof, &hex32(USER_SECRET)).unwrap();
+
+        let report = build_verification_report_for_config(&proof, &config, None).unwrap();
+        assert_eq!(Outcome::from(report), Outcome::rejected(VerificationFailure::InvalidRangeProof), "amount {}", amount);
+
+        // El depósito con la range proof del pool amplio no vale en este pool
+        let wide_config = ZKProofConfig { min_amount: wide_min.to_string(), max_amount: wide_max.to_string(), ..config.clone() };
+        let deposit = build_deposit_proof(amount, &nonce, &hex32(USER_SECRET), &wide_config).unwrap();
+        assert!(verify_deposit(&deposit, &wide_config).unwrap().valid);
+        let report = verify_deposit(&deposit, &config).unwrap();
+        assert_eq!(Outcome::from(report), Outcome::rejected(VerificationFailure::InvalidRangeProof), "deposit of {}", amount);
+    }
+}
+
+#[test]
+fn denomination_pools_bind_the_declared_id() {
+    let denomination_config = ZKProofConfig {
+        amount_mode: AmountMode::Denomination {
+            denominations: vec!["1000000000000000000".to_string(), EXAMPLE_AMOUNT_WEI.to_string()],
+        },
+        ..ZKProofConfig::default()
+    };
+    let with_denomination = |denomination_id: Option<u32>, min: &str, max: &str| {
+        let mut proof = conformance_proof();
+        proof.denomination_id = denomination_id;
+        place_in_tree(&mut proof);
+        proof.range_proof =
+            generate_stwo_range_proof(EXAMPLE_AMOUNT_WEI, &hex32(1), min, max, &proof.amount_commitment.commitment_hash)
+                .unwrap();
+        sign_statement(&mut proof, &hex32(USER_SECRET)).unwrap();
+        proof
+    };
+    let report = |proof: &CeaserZKProof, config: &ZKProofConfig| {
+        Outcome::from(build_verification_report_for_config(proof, config, None).unwrap())
+    };
+    use VerificationFailure::*;
+
+    // La range proof fija el monto a la denominación: [d, d]
+    let pinned = with_denomination(Some(1), EXAMPLE_AMOUNT_WEI, EXAMPLE_AMOUNT_WEI);
+    assert_eq!(report(&pinned, &denomination_config), Outcome::accepted());
+    let pool_range = with_denomination(Some(1), &denomination_config.min_amount, &denomination_config.max_amount);
+    assert_eq!(report(&pool_range, &denomination_config), Outcome::rejected(InvalidRangeProof));
+
+    // Otro ID, uno inexistente o ninguno no valen en este pool; un ID no vale en un pool de rango
+    let other_id = with_denomination(Some(0), EXAMPLE_AMOUNT_WEI, EXAMPLE_AMOUNT_WEI);
+    assert_eq!(report(&other_id, &denomination_config), Outcome::rejected(InvalidRangeProof));
+    let unknown_id = with_denomination(Some(7), EXAMPLE_AMOUNT_WEI, EXAMPLE_AMOUNT_WEI);
+    assert_eq!(report(&unknown_id, &denomination_config), Outcome::rejected(InvalidDenomination));
+    assert_eq!(report(&conformance_proof(), &denomination_config), Outcome::rejected(InvalidDenomination));
+    assert_eq!(report(&pinned, &ZKProofConfig::default()), Outcome::rejected(InvalidDenomination));
+
+    // El ID va en la declaración firmada
+    let mut relabeled = pinned.clone();
+    relabeled.denomination_id = Some(0);
+    place_in_tree(&mut relabeled);
+    assert_eq!(
+        Outcome::from(build_verification_report(&relabeled, &denomination_config.domain, None).unwrap()),
+        Outcome::rejected(StatementMismatch)
+    );
+
+    // Depósitos: mismo criterio
+    let deposit = build_deposit_proof(EXAMPLE_AMOUNT_WEI, &hex32(1), &hex32(USER_SECRET), &denomination_config).unwrap();
+    assert_eq!(deposit.denomination_id, Some(1));
+    assert_eq!(Outcome::from(verify_deposit(&deposit, &denomination_config).unwrap()), Outcome::accepted());
+    assert_eq!(Outcome::from(verify_deposit(&deposit, &ZKProofConfig::default()).unwrap()), Outcome::rejected(InvalidDenomination));
+    let mut unknown_deposit = deposit;
+    unknown_deposit.denomination_id = Some(7);
+    assert_eq!(Outcome::from(verify_deposit(&unknown_deposit, &denomination_config).unwrap()), Outcome::rejected(InvalidCommitment));
+}
@@ -0,0 +1,64 @@
+//! Extensiones CM31/QM31: leyes de cuerpo y, con `real-stwo`, igualdad con
+//! los tipos de STWO.
+//!
+//! `cargo test --test extension_fields` / `cargo test --no-default-features --features real-stwo --test extension_fields`
+
+use zkp_ceaser::{verify_extension_fields, CM31, QM31};
+
+const P: u32 = (1 << 31) - 1;
+
+fn samples() -> [QM31; 4] {
+    [
+        QM31::new(1, 2, 3, 4),
+        QM31::new(P - 1, 7, 0, 123456789),
+        QM31::new(0, 0, 0, 1),
+        QM31::new(2147483000, 5, 99, 2),
+    ]
+}
+
+#[test]
+fn field_laws_hold() {
+    assert!(verify_extension_fields().unwrap());
+
+    let one = QM31::new(1, 0, 0, 0);
+    for x in samples() {
+        assert_eq!(x.mul(&x.inv().unwrap()), one);
+        assert_eq!(x.frobenius().frobenius().frobenius().frobenius(), x);
+        assert_eq!(x.frobenius(), x.pow(P as u64));
+        for y in samples() {
+            assert_eq!(x.mul(&y), y.mul(&x));
+            assert_eq!(x.add(&y).sub(&y), x);
+        }
+
+        let cm = x.a();
+        assert_eq!(cm.frobenius(), cm.pow(P as u64));
+        if !cm.is_zero() {
+            assert_eq!(cm.mul(&cm.inv().unwrap()), CM31::new(1, 0));
+        }
+    }
+}
+
+#[cfg(feature = "real-stwo")]
+#[test]
+fn arithmetic_matches_stwo() {
+    use stwo::core::fields::cm31::CM31 as StwoCM31;
+    use stwo::core::fields::qm31::QM31 as StwoQM31;
+    use stwo::core::fields::FieldExpOps;
+
+    let to_stwo = |x: &QM31| {
+        let [a, b, c, d]: [u32; 4] = x.to_m31_array().try_into().unwrap();
+        StwoQM31::from_u32_unchecked(a, b, c, d)
+    };
+    let from_stwo = |x: StwoQM31| {
+        let StwoQM31(StwoCM31(a, b), StwoCM31(c, d)) = x;
+        QM31::new(a.0, b.0, c.0, d.0)
+    };
+
+    let samples = samples();
+    for (x, y) in samples.iter().zip(samples.iter().rev()) {
+        assert_eq!(from_stwo(to_stwo(x) + to_stwo(y)), x.add(y));
+        assert_eq!(from_stwo(to_stwo(x) - to_stwo(y)), x.sub(y));
+        assert_eq!(from_stwo(to_stwo(x) * to_stwo(y)), x.mul(y));
+        assert_eq!(from_stwo(to_stwo(x).inverse()), x.inv().unwrap());
+    }
+}
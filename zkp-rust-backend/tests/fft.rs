@@ -0,0 +1,55 @@
+//! FFT circular: ida y vuelta, acuerdo con la evaluación directa en cada
+//! punto del dominio y extensión de bajo grado.
+
+use zkp_ceaser::{
+    check_circle_fft, circle_evaluate, circle_interpolate, circle_low_degree_extension, evaluate_circle_poly_at,
+    CircleDomain, M31Element,
+};
+
+fn sample_coefficients(size: usize) -> Vec<M31Element> {
+    (0..size as u32).map(|i| M31Element::new(i.wrapping_mul(2654435761).wrapping_add(777))).collect()
+}
+
+#[test]
+fn interpolate_inverts_evaluate() {
+    for log_size in 1..=12 {
+        let coefficients = sample_coefficients(1 << log_size);
+        let evaluations = circle_evaluate(&coefficients).unwrap();
+        assert_eq!(circle_interpolate(&evaluations).unwrap(), coefficients, "log size {}", log_size);
+    }
+}
+
+#[test]
+fn evaluate_matches_naive_evaluation() {
+    for log_size in 1..=8 {
+        let coefficients = sample_coefficients(1 << log_size);
+        let evaluations = circle_evaluate(&coefficients).unwrap();
+        let domain = CircleDomain::canonic(log_size).unwrap();
+        for (point, value) in domain.iter().zip(&evaluations) {
+            assert_eq!(evaluate_circle_poly_at(&coefficients, &point), *value, "log size {}", log_size);
+        }
+    }
+}
+
+#[test]
+fn low_degree_extension_evaluates_the_same_polynomial() {
+    let coefficients = sample_coefficients(1 << 6);
+    let evaluations = circle_evaluate(&coefficients).unwrap();
+    for log_blowup in 1..=3 {
+        let extended = circle_low_degree_extension(&evaluations, log_blowup).unwrap();
+        let domain = CircleDomain::canonic(6 + log_blowup).unwrap();
+        assert_eq!(extended.len(), domain.size());
+        for (point, value) in domain.iter().zip(&extended) {
+            assert_eq!(evaluate_circle_poly_at(&coefficients, &point), *value);
+        }
+    }
+}
+
+#[test]
+fn self_test_passes_and_rejects_invalid_sizes() {
+    assert_eq!(check_circle_fft(8, 2), Ok(true));
+    for (log_size, log_blowup) in [(0, 1), (31, 0), (64, 0), (u32::MAX, 0), (20, 11), (4, u32::MAX)] {
+        assert!(check_circle_fft(log_size, log_blowup).is_err(), "log size {} blowup {}", log_size, log_blowup);
+    }
+    assert!(circle_evaluate(&sample_coefficients(3)).is_err());
+}
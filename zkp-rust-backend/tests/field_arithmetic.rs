@@ -0,0 +1,32 @@
+//! `batch_inverse` (truco de Montgomery) coincide con `inv()` elemento a
+//! elemento, también para vectores de uno y cero elementos, y rechaza el cero.
+
+use zkp_ceaser::{batch_inverse, M31Element};
+
+const P: u32 = (1 << 31) - 1;
+
+#[test]
+fn batch_inverse_matches_element_wise_inverse() {
+    let samples: Vec<u32> = vec![1, 2, 3, 7, 12345, 1 << 30, P - 2, P - 1];
+    for len in 1..=samples.len() {
+        let elements: Vec<M31Element> = samples[..len].iter().map(|&value| M31Element::new(value)).collect();
+        let inverses = batch_inverse(&elements).unwrap();
+        assert_eq!(inverses.len(), len);
+        for (element, inverse) in elements.iter().zip(&inverses) {
+            assert_eq!(*inverse, element.inv().unwrap(), "inverse of {}", element.value());
+            assert_eq!(element.mul(inverse), M31Element::new(1));
+        }
+    }
+
+    // Un solo elemento y el vector vacío
+    assert_eq!(batch_inverse(&[M31Element::new(2)]).unwrap(), vec![M31Element::new(1 << 30)]);
+    assert!(batch_inverse(&[]).unwrap().is_empty());
+}
+
+#[test]
+fn batch_inverse_rejects_zero() {
+    let with_zero: Vec<M31Element> = [5, 0, 9].iter().map(|&value| M31Element::new(value)).collect();
+    assert!(batch_inverse(&with_zero).is_err());
+    assert!(batch_inverse(&[M31Element::new(0)]).is_err());
+    assert!(batch_inverse(&[M31Element::new(P)]).is_err(), "p reduces to zero");
+}
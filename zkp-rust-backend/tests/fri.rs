@@ -0,0 +1,76 @@
+//! FRI sobre M31: un polinomio dentro de la cota de grado verifica; uno de
+//! grado mayor, una raíz de capa cambiada o un camino de consulta manipulado
+//! se rechazan. Sin el transcript del prover la prueba tampoco verifica.
+
+use zkp_ceaser::{circle_evaluate, fri_prove, fri_verify, FriParams, FriProof, M31Element, Transcript};
+
+/// Dominio 2^8 con blowup 4: grado < 64, tres plegados hasta una última capa de 32
+const PARAMS: FriParams = FriParams {
+    log_domain_size: 8,
+    log_blowup: 2,
+    num_queries: 10,
+    pow_bits: 4,
+    log_last_layer_degree_bound: 3,
+};
+const DEGREE_BOUND: usize = 64;
+
+/// Evaluaciones en el dominio canónico de un polinomio con `degree` coeficientes
+fn evaluations_of_degree(degree: usize) -> Vec<M31Element> {
+    let coefficients: Vec<M31Element> = (0..1u32 << PARAMS.log_domain_size)
+        .map(|i| if (i as usize) < degree { M31Element::new(i.wrapping_mul(2654435761) >> 1) } else { M31Element::new(0) })
+        .collect();
+    circle_evaluate(&coefficients).unwrap()
+}
+
+fn prove(evaluations: &[M31Element]) -> FriProof {
+    fri_prove(evaluations, &PARAMS, &mut Transcript::new("fri-test")).unwrap()
+}
+
+fn verifies(proof: &FriProof) -> bool {
+    fri_verify(proof, &PARAMS, &mut Transcript::new("fri-test")).unwrap()
+}
+
+#[test]
+fn low_degree_polynomial_verifies() {
+    let proof = prove(&evaluations_of_degree(DEGREE_BOUND));
+    assert_eq!(proof.layer_roots.len(), 3);
+    assert_eq!(proof.last_layer.len(), 32);
+    assert_eq!(proof.queries.len(), PARAMS.num_queries);
+    assert!(verifies(&proof));
+
+    // Otro transcript da otros retos
+    assert!(!fri_verify(&proof, &PARAMS, &mut Transcript::new("other")).unwrap());
+}
+
+#[test]
+fn polynomial_above_the_degree_bound_is_rejected() {
+    assert!(!verifies(&prove(&evaluations_of_degree(2 * DEGREE_BOUND))));
+    assert!(!verifies(&prove(&evaluations_of_degree(1 << PARAMS.log_domain_size))));
+}
+
+#[test]
+fn tampered_layer_commitment_is_rejected() {
+    let proof = prove(&evaluations_of_degree(DEGREE_BOUND));
+    for layer in 0..proof.layer_roots.len() {
+        let mut tampered = proof.clone();
+        tampered.layer_roots[layer] = format!("0x{}", "ab".repeat(32));
+        assert!(!verifies(&tampered), "layer {}", layer);
+    }
+}
+
+#[test]
+fn tampered_query_path_is_rejected() {
+    let proof = prove(&evaluations_of_degree(DEGREE_BOUND));
+
+    let mut sibling = proof.clone();
+    sibling.queries[0].layers[1].paths[0][0] = format!("0x{}", "cd".repeat(32));
+    assert!(!verifies(&sibling));
+
+    let mut value = proof.clone();
+    value.queries[3].layers[0].values.swap(0, 1);
+    assert!(!verifies(&value));
+
+    let mut index = proof;
+    index.queries[0].index ^= 1;
+    assert!(!verifies(&index));
+}
@@ -0,0 +1,105 @@
+//! Vectores de prueba reproducibles: con el RNG con semilla las pruebas deben
+//! coincidir byte a byte con los ficheros de `proofs/golden/`.
+//!
+//! Si un cambio altera el formato a propósito, regenerarlos con
+//! `UPDATE_GOLDEN=1 cargo test --test golden_proofs` y revisar el diff.
+#![cfg(all(feature = "mock-stwo", not(feature = "real-stwo")))]
+
+mod common;
+
+use common::assert_golden;
+use zkp_ceaser::{
+    build_deposit_proof, encrypt_note_for_recipient_with_rng, prove_join_split, JoinSplitConfig,
+    JoinSplitInput, KeyHierarchy, MerkleProof, Note, ProofRng, TransferBinding, ZKProofConfig,
+};
+
+const GOLDEN_SEED: [u8; 32] = *b"ceaser-golden-vectors-seed-v1\0\0\0";
+
+fn seeded_rng() -> ProofRng {
+    ProofRng::from_seed(GOLDEN_SEED)
+}
+
+/// Camino Merkle fijo: la prueba join-split solo lo transporta
+fn fixed_merkle_proof(note: &Note, leaf_index: u32) -> MerkleProof {
+    let leaf_hash = note.leaf_in_domain("STRK", &Default::default()).unwrap().commitment_hash;
+    MerkleProof {
+        proof_path: (0..10).map(|level| format!("0x{:064x}", level + 1)).collect(),
+        leaf_index,
+        leaf_hash,
+        root: format!("0x{:064x}", 0xcea5e4),
+    }
+}
+
+fn join_split_proof_json(rng: &mut ProofRng) -> String {
+    let keys = KeyHierarchy::from_seed(b"ceaser-golden-vectors");
+    let input_a = keys.note_at("sepolia", 5_000_000_000_000_000, 0).unwrap();
+    let input_b = keys.note_at("sepolia", 3_000_000_000_000_000, 1).unwrap();
+    let output_a = keys.note_at("sepolia", 6_000_000_000_000_000, 2).unwrap();
+    let output_b = keys.note_at("sepolia", 1_900_000_000_000_000, 3).unwrap();
+
+    let inputs = vec![
+        JoinSplitInput { merkle_proof: fixed_merkle_proof(&input_a, 3), note: input_a },
+        JoinSplitInput { merkle_proof: fixed_merkle_proof(&input_b, 7), note: input_b },
+    ];
+    let binding = TransferBinding {
+        recipient: "0x2d4c0a53f31f0f359b5f439728a05273c23f0fa6fe2405a691dfd09fafafa49".to_string(),
+        relayer: "0x0".to_string(),
+        fee: "100000000000000".to_string(),
+    };
+    let proof = prove_join_split(
+        &inputs, &[output_a, output_b], "STRK", &binding, &JoinSplitConfig::default(), rng,
+    )
+    .unwrap();
+    serde_json::to_string(&proof).unwrap()
+}
+
+#[test]
+fn deposit_proof_matches_golden() {
+    let nonce = format!("0x{:064x}", 1);
+    let proof = build_deposit_proof("1000000000000000000", &nonce, &format!("0x{:064x}", 0xc0ffee), &ZKProofConfig::default()).unwrap();
+    assert_golden("golden/deposit-proof-mock-stwo.json", &serde_json::to_string(&proof).unwrap());
+}
+
+#[test]
+fn seeded_join_split_matches_golden() {
+    let json = join_split_proof_json(&mut seeded_rng());
+    assert_eq!(json, join_split_proof_json(&mut seeded_rng()), "seeded proofs must be reproducible");
+    assert_golden("golden/join-split-proof-mock-stwo.json", &json);
+}
+
+#[test]
+fn seeded_note_and_encryption_match_golden() {
+    let mut rng = seeded_rng();
+    let note = Note::generate_with_rng("sepolia", 24_929_000_000_000_000_000, &mut rng).unwrap();
+    let viewing = KeyHierarchy::from_seed(b"ceaser-golden-vectors").viewing();
+    let blob = encrypt_note_for_recipient_with_rng(
+        &viewing.public_key(), 24_929_000_000_000_000_000, &viewing.blinding_at(0), "golden", &mut rng,
+    )
+    .unwrap();
+
+    let vector = serde_json::json!({ "note": note.encode(), "encrypted_note": blob });
+    assert_golden("golden/note-encryption.json", &serde_json::to_string_pretty(&vector).unwrap());
+}
+
+#[test]
+fn entropy_rng_is_not_deterministic() {
+    assert!(seeded_rng().is_deterministic());
+    assert!(!ProofRng::from_entropy().is_deterministic());
+    assert_ne!(
+        join_split_proof_json(&mut ProofRng::from_entropy()),
+        join_split_proof_json(&mut ProofRng::from_entropy()),
+    );
+}
+
+#[cfg(not(feature = "deterministic-rng"))]
+#[test]
+fn config_seed_is_ignored_without_deterministic_feature() {
+    let config: JoinSplitConfig = serde_json::from_value(serde_json::json!({
+        "max_inputs": 2,
+        "max_outputs": 2,
+        "max_amount": "1000000000000000000000",
+        "rng_seed": format!("0x{}", hex::encode(GOLDEN_SEED)),
+    }))
+    .unwrap();
+    assert!(!serde_json::to_string(&config).unwrap().contains("rng_seed"));
+}
@@ -0,0 +1,67 @@
+//! Árbol Merkle incremental nativo: los caminos que genera verifican con
+//! `verify_merkle_proof_with_index`, la raíz de un árbol vacío es la de los
+//! ceros y no se añaden hojas más allá de la capacidad.
+
+use sha3::{Digest, Keccak256};
+use zkp_ceaser::{verify_merkle_proof_with_index, IncrementalMerkleTree};
+
+fn leaf(index: u32) -> String {
+    format!("0x{}", hex::encode(Keccak256::digest(index.to_le_bytes())))
+}
+
+#[test]
+fn appended_leaves_have_verifying_paths() {
+    for height in [1u32, 10, 20, 32] {
+        let mut tree = IncrementalMerkleTree::new(height).unwrap();
+        for index in 0..5u32.min(1 << (height - 1)) {
+            assert_eq!(tree.append(&leaf(index)).unwrap(), index);
+        }
+        for index in 0..tree.len() as u32 {
+            let proof = tree.proof(index).unwrap();
+            assert_eq!(proof.proof_path.len(), height as usize);
+            assert_eq!(proof.leaf_hash, leaf(index));
+            assert!(verify_merkle_proof_with_index(proof.proof_path.clone(), &tree.root(), &proof.leaf_hash, index).unwrap());
+            assert!(!verify_merkle_proof_with_index(proof.proof_path, &tree.root(), &leaf(99), index).unwrap());
+        }
+    }
+}
+
+#[test]
+fn empty_root_and_capacity() {
+    // Altura 1 vacía: Keccak(0^32 ‖ 0^32)
+    let mut tree = IncrementalMerkleTree::new(1).unwrap();
+    assert_eq!(tree.root(), format!("0x{}", hex::encode(Keccak256::digest([0u8; 64]))));
+    assert!(tree.proof(0).is_err());
+
+    let empty_root = tree.root();
+    tree.append(&leaf(0)).unwrap();
+    assert_ne!(tree.root(), empty_root);
+    tree.append(&leaf(1)).unwrap();
+    assert!(tree.append(&leaf(2)).is_err());
+
+    assert!(IncrementalMerkleTree::new(0).is_err());
+    assert!(IncrementalMerkleTree::new(33).is_err());
+    assert!(tree.append("0x1234").is_err());
+}
+
+#[test]
+fn window_paths_start_at_the_window_root() {
+    let mut tree = IncrementalMerkleTree::new(10).unwrap();
+    for index in 0..6 {
+        tree.append(&leaf(index)).unwrap();
+    }
+
+    // Ventana 4..8 (dos hojas vacías): raíz = Keccak(Keccak(l4 ‖ l5) ‖ Keccak(0 ‖ 0))
+    let pair = |left: &[u8], right: &[u8]| Keccak256::digest([left, right].concat()).to_vec();
+    let decode = |hash: String| hex::decode(hash.trim_start_matches("0x")).unwrap();
+    let window_root = pair(&pair(&decode(leaf(4)), &decode(leaf(5))), &pair(&[0u8; 32], &[0u8; 32]));
+
+    let proof = tree.window_proof(5, 4).unwrap();
+    assert_eq!(proof.leaf_hash, format!("0x{}", hex::encode(window_root)));
+    assert_eq!((proof.leaf_index, proof.proof_path.len()), (1, 8));
+    assert!(verify_merkle_proof_with_index(proof.proof_path, &tree.root(), &proof.leaf_hash, 1).unwrap());
+
+    assert_eq!(format!("{:?}", tree.window_proof(3, 1).unwrap()), format!("{:?}", tree.proof(3).unwrap()));
+    assert!(tree.window_proof(3, 3).is_err());
+    assert!(tree.window_proof(3, 2048).is_err());
+}
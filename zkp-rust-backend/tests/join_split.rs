@@ -0,0 +1,239 @@
+//! Join-split: una transferencia balanceada verifica; cambiar el fee, quitar
+//! salidas o desligar V, S y nullifier de la hoja hace que se rechace. La
+//! range proof de salida se verifica sobre el propio commitment de valor.
+//! Las notas de otro activo o un activo fuera de la lista permitida no cuadran;
+//! una lista vacía no admite ninguno y los configs la rechazan.
+//! El fee es un u256 en decimal o hex y entra al transcript en forma canónica.
+
+mod common;
+
+use ark_bn254::{Fr, G1Projective};
+use common::merkle_proof_at;
+use zkp_ceaser::value_commitment::{blinding_generator, point_from_hex, point_to_hex, ValueRangeProof};
+use zkp_ceaser::{
+    asset_generator, check_allowed_assets, check_asset_allowed, default_allowed_assets, note_leaf, nullifier_from_spend_key, prove_join_split, resolve_asset_id,
+    verify_join_split, BlindedAsset, Domain, JoinSplitConfig, JoinSplitInput, JoinSplitInputProof, JoinSplitProof,
+    KeyHierarchy, MerkleProof, Note, Transcript, TransferBinding,
+};
+
+const FEE: &str = "100000000000000";
+
+fn merkle_proof(note: &Note, asset: &str, leaf_index: u32) -> MerkleProof {
+    merkle_proof_at(&note.leaf_in_domain(asset, &Domain::default()).unwrap().commitment_hash, leaf_index)
+}
+
+fn binding(fee: &str) -> TransferBinding {
+    TransferBinding {
+        recipient: "0x2d4c0a53f31f0f359b5f439728a05273c23f0fa6fe2405a691dfd09fafafa49".to_string(),
+        relayer: "0x0".to_string(),
+        fee: fee.to_string(),
+    }
+}
+
+/// 5e15 + 3e15 → 6e15 + 1.9e15 + fee 1e14
+fn notes() -> [Note; 4] {
+    let keys = KeyHierarchy::from_seed(b"ceaser-join-split-tests");
+    [5_000_000_000_000_000, 3_000_000_000_000_000, 6_000_000_000_000_000, 1_900_000_000_000_000]
+        .iter()
+        .enumerate()
+        .map(|(index, amount)| keys.note_at("sepolia", *amount, index as u32).unwrap())
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap()
+}
+
+fn valid_proof() -> JoinSplitProof {
+    let [input_a, input_b, output_a, output_b] = notes();
+    let inputs = vec![
+        JoinSplitInput { merkle_proof: merkle_proof(&input_a, "STRK", 3), note: input_a },
+        JoinSplitInput { merkle_proof: merkle_proof(&input_b, "STRK", 7), note: input_b },
+    ];
+    prove_join_split(
+        &inputs, &[output_a, output_b], "STRK", &binding(FEE), &JoinSplitConfig::default(), &mut rand::rngs::OsRng,
+    )
+    .unwrap()
+}
+
+#[test]
+fn balanced_join_split_verifies() {
+    let proof = valid_proof();
+    let config = JoinSplitConfig::default();
+    assert!(verify_join_split(&proof, &config).unwrap());
+
+    let decoded: JoinSplitProof = serde_json::from_str(&serde_json::to_string(&proof).unwrap()).unwrap();
+    assert!(verify_join_split(&decoded, &config).unwrap());
+}
+
+#[test]
+fn unbalanced_join_split_is_rejected() {
+    let proof = valid_proof();
+    let config = JoinSplitConfig::default();
+
+    let mut higher_fee = proof.clone();
+    higher_fee.binding = binding("200000000000000");
+    assert!(!verify_join_split(&higher_fee, &config).unwrap());
+
+    let mut lower_fee = proof.clone();
+    lower_fee.binding = binding("0");
+    assert!(!verify_join_split(&lower_fee, &config).unwrap());
+
+    let mut dropped_output = proof;
+    dropped_output.outputs.pop();
+    assert!(!verify_join_split(&dropped_output, &config).unwrap());
+}
+
+#[test]
+fn fee_is_a_canonical_u256() {
+    let [input_a, input_b, output_a, output_b] = notes();
+    let inputs = vec![
+        JoinSplitInput { merkle_proof: merkle_proof(&input_a, "STRK", 3), note: input_a },
+        JoinSplitInput { merkle_proof: merkle_proof(&input_b, "STRK", 7), note: input_b },
+    ];
+    let config = JoinSplitConfig::default();
+    let outputs = [output_a, output_b];
+
+    // 0x5af3107a4000 = 1e14: la prueba publica el fee en decimal y el JSON en hex también verifica
+    let proof = prove_join_split(&inputs, &outputs, "STRK", &binding("0x5af3107a4000"), &config, &mut rand::rngs::OsRng)
+        .unwrap();
+    assert_eq!(proof.binding.fee, FEE);
+    assert!(verify_join_split(&proof, &config).unwrap());
+    let mut hex_fee = proof.clone();
+    hex_fee.binding = binding("0x5af3107a4000");
+    assert!(verify_join_split(&hex_fee, &config).unwrap());
+
+    // fee + r da el mismo exceso de balance; fuera del campo escalar se rechaza
+    let mut aliased = proof;
+    aliased.binding = binding("21888242871839275222246405745257275088548364400416034343698204286575808495617");
+    assert!(!verify_join_split(&aliased, &config).unwrap());
+
+}
+
+#[test]
+fn inputs_and_outputs_are_bound_to_their_leaves() {
+    let proof = valid_proof();
+    let config = JoinSplitConfig::default();
+
+    // V de otra nota: la hoja recalculada ya no es la del árbol
+    let mut swapped_value = proof.clone();
+    swapped_value.inputs[0].value_commitment = proof.inputs[1].value_commitment.clone();
+    assert!(!verify_join_split(&swapped_value, &config).unwrap());
+
+    // Otra clave de gasto daría otro nullifier para la misma hoja
+    let mut other_spend_key = proof.clone();
+    let spend_key = point_from_hex(&proof.inputs[0].spend_key).unwrap();
+    other_spend_key.inputs[0].spend_key = point_to_hex(&(spend_key + G1Projective::from(blinding_generator())));
+    assert!(!verify_join_split(&other_spend_key, &config).unwrap());
+
+    let mut other_nullifier = proof.clone();
+    other_nullifier.inputs[0].nullifier = proof.inputs[1].nullifier.clone();
+    assert!(!verify_join_split(&other_nullifier, &config).unwrap());
+
+    // V de salida que no corresponde a la hoja publicada
+    let mut unbound_output = proof.clone();
+    unbound_output.outputs[0].value_commitment = proof.outputs[1].value_commitment.clone();
+    assert!(!verify_join_split(&unbound_output, &config).unwrap());
+}
+
+#[test]
+fn range_proof_is_over_the_value_commitment() {
+    let generator = asset_generator(&resolve_asset_id("STRK").unwrap());
+    let h = G1Projective::from(blinding_generator());
+    let blinding = Fr::from(42u64);
+    let max = 1_000_000u128;
+    let prove = |amount: u128| {
+        ValueRangeProof::prove(amount, &blinding, max, &generator, &mut Transcript::new("test"), &mut rand::rngs::OsRng)
+    };
+
+    for amount in [0, 1, 765_432, max] {
+        let value = generator * Fr::from(amount) + h * blinding;
+        assert!(prove(amount).unwrap().verify(&value, max, &generator, &mut Transcript::new("test")).unwrap(), "amount {}", amount);
+    }
+    assert!(prove(max + 1).is_err());
+
+    let range_proof = prove(500).unwrap();
+    let other_value = generator * Fr::from(501u64) + h * blinding;
+    assert!(!range_proof.verify(&other_value, max, &generator, &mut Transcript::new("test")).unwrap());
+    let value = generator * Fr::from(500u64) + h * blinding;
+    assert!(!range_proof.verify(&value, max / 2, &generator, &mut Transcript::new("test")).unwrap());
+    assert!(!range_proof.verify(&value, max, &generator, &mut Transcript::new("other")).unwrap());
+}
+
+/// Entrada pública de `note` depositada como otro activo: hoja, nullifier y
+/// merkle proof válidos, pero V usa el generador de ese activo
+fn input_as_asset(note: &Note, asset: &str, leaf_index: u32) -> JoinSplitInputProof {
+    let domain = Domain::default();
+    let value_commitment = note.value_commitment(asset).unwrap();
+    let (_, spend_key) = note.spend_key_in_domain(asset, &domain).unwrap();
+    let merkle_proof = merkle_proof(note, asset, leaf_index);
+    assert_eq!(merkle_proof.leaf_hash, note_leaf(&(value_commitment + spend_key), None, &domain).unwrap().commitment_hash);
+    JoinSplitInputProof {
+        nullifier: nullifier_from_spend_key(&spend_key, &domain),
+        commitment_hash: merkle_proof.leaf_hash.clone(),
+        value_commitment: point_to_hex(&value_commitment),
+        spend_key: point_to_hex(&spend_key),
+        merkle_root: merkle_proof.root.clone(),
+        merkle_proof,
+    }
+}
+
+#[test]
+fn mixed_asset_join_split_is_rejected() {
+    let proof = valid_proof();
+    let config = JoinSplitConfig::default();
+    let [input_a, ..] = notes();
+
+    // Misma nota y mismo monto, pero depositada como ETH: el balance no cuadra en A'
+    let mut eth_input = proof.clone();
+    eth_input.inputs[0] = input_as_asset(&input_a, "ETH", 3);
+    assert!(!verify_join_split(&eth_input, &config).unwrap());
+
+    // Salida de ETH dentro de un join-split de STRK
+    let mut eth_output = proof.clone();
+    let [.., output_b] = notes();
+    let domain = Domain::default();
+    let value_commitment = output_b.value_commitment("ETH").unwrap();
+    let (_, spend_key) = output_b.spend_key_in_domain("ETH", &domain).unwrap();
+    eth_output.outputs[1].commitment = note_leaf(&(value_commitment + spend_key), None, &domain).unwrap();
+    eth_output.outputs[1].value_commitment = point_to_hex(&value_commitment);
+    eth_output.outputs[1].spend_key = point_to_hex(&spend_key);
+    assert!(!verify_join_split(&eth_output, &config).unwrap());
+
+    // A' de ETH con la prueba de pertenencia de STRK
+    let mut eth_generator = proof;
+    eth_generator.asset.generator = point_to_hex(&asset_generator(&resolve_asset_id("ETH").unwrap()));
+    assert!(!verify_join_split(&eth_generator, &config).unwrap());
+}
+
+#[test]
+fn assets_outside_the_allowed_list_are_rejected() {
+    let strk = resolve_asset_id("STRK").unwrap();
+    let others: Vec<String> = default_allowed_assets().into_iter().filter(|asset| *asset != strk).collect();
+    assert!(!others.is_empty());
+
+    let proof = valid_proof();
+    let config = JoinSplitConfig { allowed_assets: others.clone(), ..JoinSplitConfig::default() };
+    assert!(!verify_join_split(&proof, &config).unwrap());
+
+    let strk_only = JoinSplitConfig { allowed_assets: vec![strk.clone()], ..JoinSplitConfig::default() };
+    assert!(!verify_join_split(&proof, &strk_only).unwrap(), "the surjection proof covers the whole allowed list");
+
+    assert!(BlindedAsset::prove(&strk, &others, &mut Transcript::new("test"), &mut rand::rngs::OsRng).is_err());
+}
+
+#[test]
+fn empty_allowed_list_accepts_no_asset() {
+    let strk = resolve_asset_id("STRK").unwrap();
+    assert!(check_allowed_assets(&[]).is_err());
+    assert!(check_allowed_assets(&default_allowed_assets()).is_ok());
+    assert!(check_asset_allowed(&strk, &[]).is_err());
+    assert!(check_asset_allowed(&strk, &default_allowed_assets()).is_ok());
+
+    // Igual que `check_asset_allowed`: con la lista vacía ninguna prueba verifica
+    let empty = JoinSplitConfig { allowed_assets: Vec::new(), ..JoinSplitConfig::default() };
+    assert!(!verify_join_split(&valid_proof(), &empty).unwrap());
+    assert!(BlindedAsset::prove(&strk, &[], &mut Transcript::new("test"), &mut rand::rngs::OsRng).is_err());
+
+    let config: JoinSplitConfig =
+        serde_json::from_str(r#"{"max_inputs":2,"max_outputs":2,"max_amount":"1000","allowed_assets":[]}"#).unwrap();
+    assert!(check_allowed_assets(&config.allowed_assets).is_err());
+}
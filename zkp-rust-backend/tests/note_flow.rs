@@ -0,0 +1,90 @@
+//! Ciclo de vida de una nota en un único árbol: depósito → join-split → retiro.
+//! Depósitos y salidas de join-split publican la misma hoja `note_leaf(N)`,
+//! así que las tres pruebas verifican contra el mismo `IncrementalMerkleTree`
+//! y una nota tiene un único nullifier sea cual sea el camino con que se gasta.
+
+use zkp_ceaser::value_commitment::{point_from_hex, point_to_hex};
+use zkp_ceaser::{
+    build_deposit_proof, build_withdraw_proof, nullifier_from_spend_key, prove_join_split, verify_deposit,
+    verify_join_split, verify_withdraw, IncrementalMerkleTree, JoinSplitConfig, JoinSplitInput, KeyHierarchy, Note,
+    ProofRng, TransferBinding, ZKProofConfig,
+};
+
+const ASSET: &str = "STRK";
+const RECIPIENT: &str = "0x2d4c0a53f31f0f359b5f439728a05273c23f0fa6fe2405a691dfd09fafafa49";
+const RELAYER: &str = "0x5793";
+const FEE_WEI: &str = "100000000000000";
+
+/// Deposita la nota en el pool del activo, comprueba la prueba y devuelve (N, hoja)
+fn deposit(note: &Note, config: &ZKProofConfig) -> (String, String) {
+    let proof = build_deposit_proof(&note.amount().to_string(), &note.commitment_nonce(), &note.nullifier_secret(), config)
+        .unwrap();
+    assert!(verify_deposit(&proof, config).unwrap().valid);
+    assert_eq!(proof.amount_commitment.commitment_hash, note.leaf_in_domain(ASSET, &config.domain).unwrap().commitment_hash);
+    (proof.note_commitment, proof.amount_commitment.commitment_hash)
+}
+
+#[test]
+fn deposited_note_is_split_and_withdrawn_from_one_tree() {
+    let config = ZKProofConfig { asset: Some(ASSET.to_string()), ..ZKProofConfig::default() };
+    let keys = KeyHierarchy::from_seed(b"ceaser-note-flow-tests");
+    let note = |amount: u128, index: u32| keys.note_at("sepolia", amount, index).unwrap();
+    let mut tree = IncrementalMerkleTree::new(10).unwrap();
+
+    // Tres depósitos ajenos y el propio (índice 3)
+    for index in 0..3 {
+        let (_, leaf) = deposit(&note(2_000_000_000_000_000, 10 + index), &config);
+        tree.append(&leaf).unwrap();
+    }
+    let deposited = note(5_000_000_000_000_000, 0);
+    let (_, leaf) = deposit(&deposited, &config);
+    let deposit_index = tree.append(&leaf).unwrap();
+
+    // 5e15 → 3e15 + 1.9e15 + fee 1e14; las salidas entran al árbol (índices 4 y 5)
+    let spent = note(3_000_000_000_000_000, 1);
+    let change = note(1_900_000_000_000_000, 2);
+    let join_split_config = JoinSplitConfig::default();
+    let binding = TransferBinding { recipient: RECIPIENT.to_string(), relayer: RELAYER.to_string(), fee: FEE_WEI.to_string() };
+    let inputs = [JoinSplitInput { merkle_proof: tree.proof(deposit_index).unwrap(), note: deposited.clone() }];
+    let join_split = prove_join_split(
+        &inputs, &[spent.clone(), change], ASSET, &binding, &join_split_config, &mut ProofRng::from_seed([7; 32]),
+    )
+    .unwrap();
+    assert!(verify_join_split(&join_split, &join_split_config).unwrap());
+    assert_eq!(join_split.inputs[0].merkle_root, tree.root());
+
+    let mut window = Vec::new();
+    for output in &join_split.outputs {
+        tree.append(&output.commitment.commitment_hash).unwrap();
+        let note_commitment = point_from_hex(&output.value_commitment).unwrap() + point_from_hex(&output.spend_key).unwrap();
+        window.push(point_to_hex(&note_commitment));
+    }
+    for index in 0..2 {
+        let (note_commitment, leaf) = deposit(&note(1_000_000_000_000_000, 20 + index), &config);
+        tree.append(&leaf).unwrap();
+        window.push(note_commitment);
+    }
+
+    // Retiro de la salida de 3e15 desde la ventana de hojas 4..8
+    let withdraw = build_withdraw_proof(
+        &spent.amount().to_string(),
+        &spent.commitment_nonce(),
+        &spent.nullifier_secret(),
+        RECIPIENT,
+        RELAYER,
+        FEE_WEI,
+        &window,
+        tree.window_proof(4, 4).unwrap(),
+        &config,
+        &mut ProofRng::from_seed([9; 32]),
+    )
+    .unwrap();
+    let report = verify_withdraw(&withdraw, &config, None).unwrap();
+    assert!(report.valid, "withdraw rejected: {:?}", report);
+    assert_eq!(withdraw.merkle_root, tree.root());
+
+    // El nullifier del retiro es el que publicaría un join-split que gastara la misma nota
+    let (_, spend_key) = spent.spend_key_in_domain(ASSET, &config.domain).unwrap();
+    assert_eq!(withdraw.nullifier, nullifier_from_spend_key(&spend_key, &config.domain));
+    assert_ne!(withdraw.nullifier, join_split.inputs[0].nullifier);
+}
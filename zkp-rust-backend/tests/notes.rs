@@ -0,0 +1,115 @@
+//! Formato de backup de notas: una sola codificación válida por nota, y sin
+//! secretos ni claves en la salida `Debug`. El escaneo devuelve solo las notas
+//! cifradas para la propia clave de visualización y una nota solo se gasta en
+//! el dominio de su cadena.
+
+use zkp_ceaser::{encrypt_note_for_recipient_with_rng, Domain, KeyHierarchy, Note, ProofRng, SN_MAIN};
+
+fn sample_note() -> Note {
+    KeyHierarchy::from_seed(b"ceaser-note-tests").note_at("sepolia", 5_000_000_000_000_000, 0).unwrap()
+}
+
+#[test]
+fn note_round_trips_through_backup_string() {
+    let note = sample_note();
+    let encoded = note.encode();
+    assert_eq!(Note::decode(&encoded).unwrap(), note);
+    assert_eq!(Note::decode(&format!("  {}\n", encoded)).unwrap(), note);
+}
+
+#[test]
+fn non_canonical_encodings_are_rejected() {
+    let encoded = sample_note().encode();
+    let amount = "5000000000000000";
+    let variants = [
+        encoded.replace(&format!("-{}-", amount), &format!("-+{}-", amount)),
+        encoded.replace(&format!("-{}-", amount), &format!("-000{}-", amount)),
+        encoded.to_uppercase().replacen("CEASER-NOTE-V1-SEPOLIA", "ceaser-note-v1-sepolia", 1),
+    ];
+    for variant in variants {
+        assert_ne!(variant, encoded);
+        assert!(Note::decode(&variant).is_err(), "accepted {}", variant);
+    }
+}
+
+#[test]
+fn debug_output_redacts_secrets() {
+    let note = sample_note();
+    let debug = format!("{:?}", note);
+    assert!(debug.contains("sepolia") && debug.contains("<redacted>"));
+    for secret in [note.secret_bytes(), note.nullifier_bytes(), note.blinding_bytes()] {
+        assert!(!debug.contains(&hex::encode(secret)));
+        assert!(!debug.contains(&format!("{:?}", secret)));
+    }
+}
+
+#[test]
+fn key_debug_output_redacts_keys() {
+    let keys = KeyHierarchy::from_seed(b"ceaser-note-tests");
+    let debug = format!("{:?} {:?}", keys, keys.viewing());
+    for key in [keys.spending_key_bytes(), keys.viewing_key_bytes(), keys.nullifier_key_bytes()] {
+        assert!(!debug.contains(&hex::encode(key)));
+        assert!(!debug.contains(&format!("{:?}", key)));
+    }
+    assert!(debug.contains("<redacted>"));
+}
+
+#[test]
+fn encrypted_note_with_tampered_tag_is_ignored() {
+    let viewing = KeyHierarchy::from_seed(b"ceaser-note-tests").viewing();
+    let blob = encrypt_note_for_recipient_with_rng(
+        &viewing.public_key(), 42, &viewing.blinding_at(0), "memo", &mut ProofRng::from_entropy(),
+    )
+    .unwrap();
+    assert_eq!(viewing.scan(std::slice::from_ref(&blob)).len(), 1);
+
+    // Byte 40 cae dentro del tag (tras los 32 bytes del punto efímero)
+    let mut bytes = hex::decode(blob.trim_start_matches("0x")).unwrap();
+    bytes[40] ^= 1;
+    assert!(viewing.scan(&[hex::encode(bytes)]).is_empty());
+}
+
+#[test]
+fn scan_finds_own_notes_and_skips_others() {
+    let viewing = KeyHierarchy::from_seed(b"ceaser-note-tests").viewing();
+    let other = KeyHierarchy::from_seed(b"ceaser-other-wallet").viewing();
+    let mut rng = ProofRng::from_seed([3; 32]);
+    let mut encrypt = |public_key: &str, amount: u128, index: u32, memo: &str| {
+        encrypt_note_for_recipient_with_rng(public_key, amount, &viewing.blinding_at(index), memo, &mut rng).unwrap()
+    };
+
+    let blobs = vec![
+        encrypt(&other.public_key(), 7, 0, "not mine"),
+        encrypt(&viewing.public_key(), 42, 1, "first"),
+        "0xdeadbeef".to_string(),
+        "not hex".to_string(),
+        encrypt(&other.public_key(), 8, 2, ""),
+        encrypt(&viewing.public_key(), 5_000_000_000_000_000, 3, ""),
+    ];
+
+    let scanned = viewing.scan(&blobs);
+    let found: Vec<_> = scanned.iter().map(|note| (note.blob_index, note.amount_wei.as_str(), note.memo.as_str())).collect();
+    assert_eq!(found, vec![(1, "42", "first"), (5, "5000000000000000", "")]);
+    assert_eq!(scanned[0].blinding, format!("0x{}", hex::encode(viewing.blinding_at(1))));
+
+    let theirs = other.scan(&blobs);
+    assert_eq!(theirs.iter().map(|note| note.blob_index).collect::<Vec<_>>(), vec![0, 4]);
+}
+
+#[test]
+fn notes_are_spent_only_on_their_chain() {
+    let keys = KeyHierarchy::from_seed(b"ceaser-note-tests");
+    let sepolia = Domain::default();
+    let mainnet = Domain { chain_id: SN_MAIN.to_string(), ..Domain::default() };
+
+    let note = keys.note_at("sepolia", 5_000_000_000_000_000, 0).unwrap();
+    assert_eq!(note.check_chain(&sepolia), Ok(()));
+    assert_eq!(note.check_chain(&Domain::legacy()), Ok(()));
+    assert_eq!(note.check_chain(&mainnet).unwrap_err(), "Note is for chain sepolia but the pool is on SN_MAIN");
+
+    // Lo que comprueba `generate_ceaser_zk_proof_from_note` tras decodificar el backup
+    let decoded = Note::decode(&keys.note_at("mainnet", 1, 0).unwrap().encode()).unwrap();
+    assert!(decoded.check_chain(&mainnet).is_ok());
+    assert!(decoded.check_chain(&sepolia).is_err());
+    assert!(keys.note_at("sn_sepolia", 1, 0).unwrap().check_chain(&sepolia).is_ok());
+}
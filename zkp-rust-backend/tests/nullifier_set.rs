@@ -0,0 +1,48 @@
+//! `FileNullifierSet`: lo registrado sobrevive a reabrir el archivo (el doble
+//! gasto se detecta tras reiniciar el relayer) y una escritura fallida deja el
+//! registro como estaba.
+
+use zkp_ceaser::{FileNullifierSet, NullifierSet};
+
+const NULLIFIER: &str = "0x00ab12cd";
+
+#[test]
+fn spent_nullifiers_survive_reopening() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("nullifiers.json");
+
+    let mut set = FileNullifierSet::open(&path).unwrap();
+    assert!(set.is_empty());
+    assert!(set.insert_if_absent(NULLIFIER).unwrap());
+    drop(set);
+
+    let mut reopened = FileNullifierSet::open(&path).unwrap();
+    assert_eq!(reopened.len(), 1);
+    assert!(reopened.contains("0xAB12CD"));
+    assert!(!reopened.insert_if_absent("0xab12cd").unwrap(), "double spend after reopening");
+    assert!(reopened.insert_if_absent("0x01").unwrap());
+    assert_eq!(FileNullifierSet::open(&path).unwrap().len(), 2);
+}
+
+#[test]
+fn failed_write_rolls_back_the_insert() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("nullifiers.json");
+    let mut set = FileNullifierSet::open(&path).unwrap();
+    assert!(set.insert_if_absent("0x01").unwrap());
+
+    // Un directorio en el lugar del archivo temporal hace fallar la escritura
+    std::fs::create_dir(path.with_extension("tmp")).unwrap();
+    assert!(set.insert_if_absent(NULLIFIER).is_err());
+    assert!(!set.contains(NULLIFIER));
+    assert_eq!(set.len(), 1);
+
+    let reopened = FileNullifierSet::open(&path).unwrap();
+    assert_eq!(reopened.len(), 1);
+    assert!(!reopened.contains(NULLIFIER));
+
+    // Resuelto el problema, el mismo nullifier se registra con normalidad
+    std::fs::remove_dir(path.with_extension("tmp")).unwrap();
+    assert!(set.insert_if_absent(NULLIFIER).unwrap());
+    assert!(FileNullifierSet::open(&path).unwrap().contains(NULLIFIER));
+}
@@ -0,0 +1,98 @@
+//! La feature `parallel` no debe cambiar ninguna salida: cada prueba se
+//! ejecuta en un pool de 1 hilo y en uno de varios y se compara el resultado.
+//! Sin la feature se repite en el hilo actual, así que `cargo test` también
+//! comprueba que las salidas son deterministas.
+//!
+//! `cargo test --test parallel_determinism` / `cargo test --features parallel --test parallel_determinism`
+
+use zkp_ceaser::{
+    build_deposit_proof, circle_evaluate, circle_interpolate, circle_low_degree_extension, fri_prove,
+    FriParams, M31Element, M31Vector, Transcript, ZKProofConfig, PARALLEL_THRESHOLD,
+};
+
+const THREAD_COUNTS: [usize; 3] = [1, 2, 4];
+
+#[cfg(feature = "parallel")]
+fn in_pool<T: Send>(threads: usize, f: impl FnOnce() -> T + Send) -> T {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .expect("thread pool")
+        .install(f)
+}
+
+#[cfg(not(feature = "parallel"))]
+fn in_pool<T: Send>(_threads: usize, f: impl FnOnce() -> T + Send) -> T {
+    f()
+}
+
+/// Ejecuta `f` con cada número de hilos y comprueba que todas las salidas coinciden
+fn assert_same_output<T: Send + PartialEq + std::fmt::Debug>(f: impl Fn() -> T + Send + Sync) -> T {
+    let reference = in_pool(1, &f);
+    for threads in &THREAD_COUNTS[1..] {
+        assert_eq!(in_pool(*threads, &f), reference, "output differs with {} threads", threads);
+    }
+    reference
+}
+
+fn sample_elements(size: usize, seed: u32) -> Vec<M31Element> {
+    (0..size as u32)
+        .map(|i| M31Element::new(i.wrapping_mul(2654435761).wrapping_add(seed) | 1))
+        .collect()
+}
+
+#[test]
+fn circle_fft_is_deterministic() {
+    let coefficients = sample_elements(1 << 16, 12345);
+
+    let evaluations = assert_same_output(|| circle_evaluate(&coefficients).unwrap());
+    let round_trip = assert_same_output(|| circle_interpolate(&evaluations).unwrap());
+    assert_eq!(round_trip, coefficients);
+
+    let trace = sample_elements(PARALLEL_THRESHOLD, 7);
+    assert_same_output(|| circle_low_degree_extension(&trace, 2).unwrap());
+}
+
+#[test]
+fn vector_ops_are_deterministic() {
+    let left = M31Vector::from_elements(sample_elements(1 << 18, 3));
+    let right = M31Vector::from_elements(sample_elements(1 << 18, 11));
+
+    assert_same_output(|| left.mul_vector(&right).unwrap().elements().to_vec());
+    assert_same_output(|| left.sub_vector(&right).unwrap().elements().to_vec());
+    assert_same_output(|| (left.sum(), left.product()));
+}
+
+#[test]
+fn fri_proof_is_deterministic() {
+    let log_trace_size = 14;
+    let params = FriParams {
+        log_domain_size: log_trace_size + 1,
+        log_blowup: 1,
+        num_queries: 16,
+        pow_bits: 4,
+        log_last_layer_degree_bound: 0,
+    };
+    let coefficients = {
+        let mut coefficients = sample_elements(1 << log_trace_size, 99);
+        coefficients.resize(1 << params.log_domain_size, M31Element::new(0));
+        coefficients
+    };
+    let evaluations = circle_evaluate(&coefficients).unwrap();
+
+    let proof = assert_same_output(|| {
+        let mut transcript = Transcript::new("parallel-determinism");
+        fri_prove(&evaluations, &params, &mut transcript).unwrap()
+    });
+    assert_eq!(proof.layer_roots.len(), params.num_folds().unwrap());
+}
+
+#[test]
+fn deposit_proof_is_deterministic() {
+    let config = ZKProofConfig::default();
+    let nonce = format!("0x{:064x}", 42);
+    assert_same_output(|| {
+        let proof = build_deposit_proof("1000000000000000000", &nonce, &nonce, &config).unwrap();
+        serde_json::to_string(&proof).unwrap()
+    });
+}
@@ -0,0 +1,54 @@
+//! Pico de memoria por ejecución con `TrackingAllocator`: una ejecución sola
+//! mide sus propias reservas; si otra medida se solapa (otro hilo), ninguna de
+//! las dos reporta un pico que no es suyo.
+
+use std::sync::{Arc, Barrier};
+use zkp_ceaser::{ProofRecorder, TrackingAllocator};
+
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+const BUFFER_BYTES: usize = 1 << 20;
+
+fn allocate_and_release() {
+    let buffer = vec![1u8; BUFFER_BYTES];
+    std::hint::black_box(&buffer);
+}
+
+// Un solo test: los del mismo binario correrían en paralelo y se solaparían
+#[test]
+fn peak_is_reported_only_for_runs_without_overlap() {
+    let recorder = ProofRecorder::start("alone");
+    allocate_and_release();
+    let stats = recorder.finish(0).unwrap();
+    assert!(stats.peak_allocation_bytes.unwrap() >= BUFFER_BYTES as u64);
+    assert_eq!(stats.wasm_memory_bytes, None);
+
+    // Ejecución anidada en el mismo hilo: no se registra y no cuenta como solape
+    let outer = ProofRecorder::start("outer");
+    assert!(ProofRecorder::start("inner").finish(0).is_none());
+    assert!(outer.finish(0).unwrap().peak_allocation_bytes.is_some());
+
+    let started = Arc::new(Barrier::new(2));
+    let finished = Arc::new(Barrier::new(2));
+    let (thread_started, thread_finished) = (started.clone(), finished.clone());
+    let other = std::thread::spawn(move || {
+        let recorder = ProofRecorder::start("other_thread");
+        thread_started.wait();
+        allocate_and_release();
+        thread_finished.wait();
+        recorder.finish(0).unwrap()
+    });
+
+    let recorder = ProofRecorder::start("overlapped");
+    started.wait();
+    finished.wait();
+    let stats = recorder.finish(0).unwrap();
+    assert_eq!(stats.peak_allocation_bytes, None);
+    assert_eq!(other.join().unwrap().peak_allocation_bytes, None);
+
+    // Terminadas ambas, la siguiente vuelve a medirse
+    let recorder = ProofRecorder::start("after");
+    allocate_and_release();
+    assert!(recorder.finish(0).unwrap().peak_allocation_bytes.unwrap() >= BUFFER_BYTES as u64);
+}
@@ -0,0 +1,60 @@
+//! `CeaserProofJob` por etapas: el progreso solo avanza y una cancelación a
+//! mitad de prueba es terminal. Solo se ejecutan las etapas previas a la del
+//! árbol Merkle, que necesita el anonymous set del navegador.
+
+use zkp_ceaser::{CancellationToken, CeaserProofJob, ProofStage, ZKProofConfig};
+
+fn job() -> CeaserProofJob {
+    CeaserProofJob::new(
+        "5000000000000000",
+        &format!("0x{:064x}", 1),
+        &format!("0x{:064x}", 2),
+        "0x2d4c0a53f31f0f359b5f439728a05273c23f0fa6fe2405a691dfd09fafafa49",
+        "0x0",
+        "0",
+        ZKProofConfig::default(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn progress_only_increases() {
+    let percents: Vec<u8> = ProofStage::ALL.iter().map(ProofStage::percent_done).collect();
+    assert!(percents.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", percents);
+    assert_eq!(percents.last(), Some(&100));
+
+    let mut job = job();
+    let mut progress = vec![job.percent_done()];
+    for expected in [ProofStage::Commitment, ProofStage::RangeProof, ProofStage::Nullifier] {
+        assert_eq!(job.run_stage().unwrap(), Some(expected));
+        progress.push(job.percent_done());
+    }
+    assert_eq!(progress, vec![0, 5, 75, 80]);
+    assert_eq!(job.next_stage(), Some(ProofStage::Merkle));
+}
+
+#[test]
+fn cancelling_mid_job_is_terminal() {
+    let token = CancellationToken::new();
+    let mut job = job();
+    let mut completed = Vec::new();
+
+    // Como el bucle del driver: se comprueba el token antes de cada etapa
+    while let Some(stage) = job.next_stage() {
+        if token.cancelled() {
+            job.cancel();
+            break;
+        }
+        assert_eq!(job.run_stage().unwrap(), Some(stage));
+        completed.push(stage);
+        if stage == ProofStage::RangeProof {
+            token.clone().cancel();
+        }
+    }
+
+    assert_eq!(completed, vec![ProofStage::Commitment, ProofStage::RangeProof]);
+    assert!(job.is_cancelled());
+    assert_eq!(job.next_stage(), None);
+    assert_eq!(job.run_stage().unwrap(), None, "a cancelled job runs no further stages");
+    assert_eq!(job.percent_done(), 75);
+}
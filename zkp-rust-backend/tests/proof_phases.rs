@@ -0,0 +1,60 @@
+//! Fases por ejecución: dos pruebas por etapas intercaladas en el mismo hilo
+//! (como dos Promises que ceden el event loop) no se atribuyen las fases de la
+//! otra, y una ejecución normal en medio tampoco las recibe.
+
+use zkp_ceaser::{record_phase_ms, ProofPhase, ProofRecorder};
+
+#[test]
+fn interleaved_detached_runs_keep_their_own_phases() {
+    let mut first = ProofRecorder::start_detached("first");
+    let mut second = ProofRecorder::start_detached("second");
+
+    first.resume();
+    record_phase_ms(ProofPhase::Commitment, 1.0);
+    first.suspend();
+
+    // Fuera de una etapa no hay ejecución activa: esto no va a ninguna
+    record_phase_ms(ProofPhase::Merkle, 100.0);
+
+    second.resume();
+    record_phase_ms(ProofPhase::Commitment, 10.0);
+    record_phase_ms(ProofPhase::Fri, 20.0);
+    second.suspend();
+
+    let plain = ProofRecorder::start("plain");
+    record_phase_ms(ProofPhase::Serialization, 1000.0);
+    let plain = plain.finish(0).unwrap();
+
+    first.resume();
+    record_phase_ms(ProofPhase::Commitment, 2.0);
+    first.suspend();
+
+    let first = first.finish(0).unwrap();
+    let second = second.finish(0).unwrap();
+    assert_eq!(first.phase_ms.into_iter().collect::<Vec<_>>(), vec![(ProofPhase::Commitment, 3.0)]);
+    assert_eq!(
+        second.phase_ms.into_iter().collect::<Vec<_>>(),
+        vec![(ProofPhase::Commitment, 10.0), (ProofPhase::Fri, 20.0)]
+    );
+    assert_eq!(plain.phase_ms.into_iter().collect::<Vec<_>>(), vec![(ProofPhase::Serialization, 1000.0)]);
+}
+
+#[test]
+fn stage_inside_a_running_proof_restores_it() {
+    let outer = ProofRecorder::start("outer");
+    let mut job = ProofRecorder::start_detached("job");
+    job.resume();
+    record_phase_ms(ProofPhase::Fri, 5.0);
+    job.suspend();
+    record_phase_ms(ProofPhase::Merkle, 7.0);
+
+    let outer = outer.finish(0).unwrap();
+    assert_eq!(outer.phase_ms.into_iter().collect::<Vec<_>>(), vec![(ProofPhase::Merkle, 7.0)]);
+
+    // Abandonada a mitad de etapa no deja fases en el hilo
+    job.resume();
+    drop(job);
+    let after = ProofRecorder::start("after");
+    record_phase_ms(ProofPhase::Commitment, 1.0);
+    assert_eq!(after.finish(0).unwrap().phase_ms.len(), 1);
+}
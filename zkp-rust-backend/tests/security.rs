@@ -0,0 +1,71 @@
+//! Parámetros de seguridad: estimación, límites del dominio de evaluación,
+//! `security` del config del pool y, con `real-stwo`, que la prueba usa la
+//! configuración pedida.
+
+use zkp_ceaser::{SecurityParams, ZKProofConfig, MAX_LOG_DOMAIN_SIZE};
+
+#[test]
+fn default_params_estimate() {
+    let estimate = SecurityParams::default().estimate(20).unwrap();
+    assert_eq!(estimate.log_domain_size, 21);
+    assert_eq!(estimate.query_bits_conjectured, 70);
+    assert_eq!(estimate.conjectured_bits, 96);
+    assert!(estimate.proven_bits < estimate.conjectured_bits);
+}
+
+#[test]
+fn oversized_domains_are_rejected() {
+    let params = SecurityParams { log_blowup_factor: 16, ..SecurityParams::default() };
+    assert_eq!(params.log_domain_size(MAX_LOG_DOMAIN_SIZE - 16), Ok(MAX_LOG_DOMAIN_SIZE));
+    for log_trace_size in [MAX_LOG_DOMAIN_SIZE - 15, u32::MAX - 15, u32::MAX] {
+        assert!(params.log_domain_size(log_trace_size).is_err(), "log trace size {}", log_trace_size);
+        assert!(params.estimate(log_trace_size).is_err());
+        assert!(params.fri_params(log_trace_size).is_err());
+    }
+}
+
+#[test]
+fn pool_config_carries_security_params() {
+    let config: ZKProofConfig = serde_json::from_str(
+        r#"{"min_amount":"1","max_amount":"10","merkle_tree_height":20,"use_stwo":true,
+            "security":{"log_blowup_factor":2,"n_queries":40,"pow_bits":10,"log_last_layer_degree_bound":1}}"#,
+    )
+    .unwrap();
+    let params = SecurityParams { log_blowup_factor: 2, n_queries: 40, pow_bits: 10, log_last_layer_degree_bound: 1 };
+    assert_eq!(config.security_params(), params);
+
+    // Sin `security` el pool usa los parámetros por defecto y no los serializa
+    let default_config = ZKProofConfig::default();
+    assert_eq!(default_config.security_params(), SecurityParams::default());
+    assert!(!serde_json::to_string(&default_config).unwrap().contains("security"));
+}
+
+#[cfg(feature = "real-stwo")]
+#[test]
+fn deposits_are_verified_with_the_pool_params() {
+    use zkp_ceaser::{build_deposit_proof, verify_deposit, VerificationFailure};
+
+    let params = SecurityParams { log_blowup_factor: 2, n_queries: 40, pow_bits: 10, log_last_layer_degree_bound: 1 };
+    let config = ZKProofConfig { security: Some(params), ..ZKProofConfig::default() };
+    let deposit = build_deposit_proof("5000000000000000", &format!("0x{:064x}", 1), &format!("0x{:064x}", 2), &config)
+        .unwrap();
+    assert!(verify_deposit(&deposit, &config).unwrap().valid);
+    let report = verify_deposit(&deposit, &ZKProofConfig::default()).unwrap();
+    assert_eq!(report.failure, Some(VerificationFailure::InvalidRangeProof));
+}
+
+#[cfg(feature = "real-stwo")]
+#[test]
+fn prover_uses_requested_params() {
+    use zkp_ceaser::{generate_real_stwo_range_proof_with_params, verify_real_stwo_range_proof_with_params};
+
+    let params = SecurityParams { log_blowup_factor: 2, n_queries: 40, pow_bits: 10, log_last_layer_degree_bound: 1 };
+    let proof = generate_real_stwo_range_proof_with_params("5", "0x01", "1", "10", &format!("0x{:064x}", 1), &params).unwrap();
+    let config = &proof.stark_proof.as_ref().unwrap().config;
+    assert_eq!(
+        (config.log_blowup_factor, config.n_queries, config.pow_bits, config.log_last_layer_degree_bound),
+        (2, 40, 10, 1)
+    );
+    assert!(verify_real_stwo_range_proof_with_params(&proof, &params).unwrap());
+    assert!(!verify_real_stwo_range_proof_with_params(&proof, &SecurityParams::default()).unwrap());
+}
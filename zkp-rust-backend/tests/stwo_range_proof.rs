@@ -0,0 +1,74 @@
+//! Range proof con STWO real: la prueba del prover pasa por el formato de
+//! cable y el verificador de STWO la acepta; límites, commitment, parámetros
+//! o datos alterados se rechazan.
+//!
+//! `cargo test --no-default-features --features real-stwo --test stwo_range_proof`
+
+#![cfg(feature = "real-stwo")]
+
+use zkp_ceaser::{
+    convert_real_to_compatible_proof, generate_real_stwo_range_proof, verify_real_stwo_range_proof,
+    verify_stwo_range_air_proof, CircleStarkRangeProof, SecurityParams, VersionedStarkProof,
+};
+
+const MIN: &str = "1000000000000000";
+const MAX: &str = "1000000000000000000000";
+const COMMITMENT_HASH: &str = "0xd78890a1ce6d182ba25c324dc99e089670c35518c39c8c82dde0378f9eaaa5db";
+
+fn wire_proof(amount: &str) -> CircleStarkRangeProof {
+    let real_proof = generate_real_stwo_range_proof(amount, "0x01", MIN, MAX, COMMITMENT_HASH).unwrap();
+    assert!(real_proof.stark_proof.is_some());
+    assert_eq!(real_proof.public_inputs, vec![MIN.to_string(), MAX.to_string(), COMMITMENT_HASH.to_string()]);
+    assert!(verify_real_stwo_range_proof(&real_proof).unwrap());
+    convert_real_to_compatible_proof(&real_proof).unwrap()
+}
+
+#[test]
+fn proof_round_trips_through_wire_format_and_verifies() {
+    for amount in [MIN, "5000000000000000000", MAX] {
+        let range_proof = wire_proof(amount);
+        let json = serde_json::to_string(&range_proof).unwrap();
+        let decoded: CircleStarkRangeProof = serde_json::from_str(&json).unwrap();
+        let stark_proof = VersionedStarkProof::from_range_proof(&decoded).unwrap();
+        assert_eq!(VersionedStarkProof::from_stwo(&stark_proof.to_stwo().unwrap()), stark_proof);
+        assert!(verify_stwo_range_air_proof(&decoded, &SecurityParams::default()).unwrap(), "amount {}", amount);
+    }
+}
+
+#[test]
+fn amounts_outside_range_cannot_be_proven() {
+    for amount in ["999999999999999", "1000000000000000000001"] {
+        assert!(generate_real_stwo_range_proof(amount, "0x01", MIN, MAX, COMMITMENT_HASH).is_err());
+    }
+}
+
+#[test]
+fn altered_proofs_are_rejected() {
+    let range_proof = wire_proof("5000000000000000000");
+    let defaults = SecurityParams::default();
+
+    let mut other_bounds = range_proof.clone();
+    other_bounds.public_inputs[1] = "2000000000000000000000".to_string();
+    assert!(!verify_stwo_range_air_proof(&other_bounds, &defaults).unwrap());
+
+    // El commitment está en el canal: la prueba no sirve para otro commitment
+    let mut other_commitment = range_proof.clone();
+    other_commitment.public_inputs[2] = format!("0x{:064x}", 1);
+    assert!(!verify_stwo_range_air_proof(&other_commitment, &defaults).unwrap());
+
+    let mut extra_input = range_proof.clone();
+    extra_input.public_inputs.push("0".to_string());
+    assert!(!verify_stwo_range_air_proof(&extra_input, &defaults).unwrap());
+
+    let weaker = SecurityParams { n_queries: defaults.n_queries + 1, ..defaults.clone() };
+    assert!(!verify_stwo_range_air_proof(&range_proof, &weaker).unwrap());
+
+    let mut stark_proof = VersionedStarkProof::from_range_proof(&range_proof).unwrap();
+    stark_proof.proof_of_work ^= 1;
+    let tampered = stark_proof.to_range_proof(range_proof.public_inputs.clone()).unwrap();
+    assert!(!verify_stwo_range_air_proof(&tampered, &defaults).unwrap());
+
+    let mut legacy = range_proof;
+    legacy.proof_data = vec!["stwo_circle_stark_proof_v2:amount_1_nonce_0x01".to_string()];
+    assert!(verify_stwo_range_air_proof(&legacy, &defaults).is_err());
+}
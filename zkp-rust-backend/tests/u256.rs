@@ -0,0 +1,49 @@
+//! u256: limbs M31 de 30 bits y codificación de ancho fijo en los commitments.
+
+use zkp_ceaser::{
+    generate_pedersen_commitment_for_domain, m31_elements_to_u256, u256_to_m31_elements, Domain, U256,
+    U256_M31_LIMBS,
+};
+
+#[test]
+fn m31_limbs_round_trip() {
+    let values = [
+        U256::ZERO,
+        U256::from((1u128 << 31) - 1),
+        U256::from(u128::MAX),
+        U256::from_parts(0, 1),
+        U256::MAX,
+    ];
+    for value in values {
+        let limbs = value.to_m31_limbs();
+        assert!(limbs.iter().all(|limb| *limb < 1 << 30), "{}", value);
+        assert_eq!(U256::from_m31_limbs(&limbs), Ok(value));
+
+        let elements = u256_to_m31_elements(&value.to_string()).unwrap();
+        assert_eq!(m31_elements_to_u256(&elements).unwrap(), value.to_string());
+    }
+    // 2^31 − 1 ya no es un limb: queda como [2^30 − 1, 1]
+    assert_eq!(U256::from((1u128 << 31) - 1).to_m31_limbs()[..2], [(1 << 30) - 1, 1]);
+}
+
+#[test]
+fn out_of_range_limbs_are_rejected() {
+    assert!(U256::from_m31_limbs(&[1 << 30]).is_err());
+    assert!(U256::from_m31_limbs(&[0; U256_M31_LIMBS + 1]).is_err());
+    let mut limbs = [0u32; U256_M31_LIMBS];
+    limbs[U256_M31_LIMBS - 1] = 1 << 16;
+    assert!(U256::from_m31_limbs(&limbs).is_err());
+}
+
+#[test]
+fn commitment_encoding_is_unambiguous() {
+    // Con `high` de ancho variable, (2^128, n) y (0, 01‖00…‖n) hasheaban los mismos bytes
+    let nonce = "0x".to_string() + &"ab".repeat(32);
+    let mut shifted_nonce = "0x01".to_string() + &"00".repeat(15);
+    shifted_nonce.push_str(&"ab".repeat(32));
+    let domain = Domain::default();
+
+    let high = generate_pedersen_commitment_for_domain("340282366920938463463374607431768211456", &nonce, &domain);
+    let low = generate_pedersen_commitment_for_domain("0", &shifted_nonce, &domain);
+    assert_ne!(high.unwrap().commitment_hash, low.unwrap().commitment_hash);
+}
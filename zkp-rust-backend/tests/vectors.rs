@@ -0,0 +1,311 @@
+//! Vectores JSON de las primitivas (`proofs/vectors/`): commitments,
+//! nullifiers, cifrado de metadata y calldata en felts. Los mismos ficheros
+//! sirven para comprobar la implementación TypeScript.
+//!
+//! Regenerar tras un cambio intencionado: `UPDATE_GOLDEN=1 cargo test --test vectors`
+
+mod common;
+
+use common::{check_vectors, hex32, read_json};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use zkp_ceaser::{
+    deposit_public_inputs, encrypt_receiver_metadata, generate_asset_commitment_for_domain, generate_nullifier,
+    generate_nullifier_for_domain, generate_pedersen_commitment, generate_pedersen_commitment_for_domain, normalize_felt_hex,
+    resolve_asset_id, Domain, PedersenCommitment, U256, SN_MAIN,
+};
+
+const MAINNET_CONTRACT: &str = "0x27f726c6917c75df9277fab3c3267ec47a056065a9a0e5d59c316d51f20e28e";
+const RECEIVER: &str = "0x02d4c0a53f31F0f359B5f439728A05273c23f0fA6FE2405A691DFd09FAfAFa49";
+
+fn mainnet() -> Domain {
+    Domain::new(SN_MAIN, MAINNET_CONTRACT, 1)
+}
+
+#[derive(Serialize)]
+struct CommitmentInput {
+    amount_wei: String,
+    nonce: String,
+    domain: Domain,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    asset: Option<String>,
+}
+
+#[derive(Serialize)]
+struct CommitmentExpected {
+    x: String,
+    y: String,
+    commitment_hash: String,
+}
+
+impl From<PedersenCommitment> for CommitmentExpected {
+    fn from(commitment: PedersenCommitment) -> Self {
+        CommitmentExpected { x: commitment.x, y: commitment.y, commitment_hash: commitment.commitment_hash }
+    }
+}
+
+fn commitment_input(amount_wei: &str, nonce: u64, domain: Domain, asset: Option<&str>) -> CommitmentInput {
+    CommitmentInput {
+        amount_wei: amount_wei.to_string(),
+        nonce: hex32(nonce),
+        domain,
+        asset: asset.map(str::to_string),
+    }
+}
+
+#[test]
+fn commitment_vectors() {
+    check_vectors(
+        "commitments.json",
+        "Amount commitments: Keccak Pedersen-style commitment bound to the domain (protocol_version 0 is the \
+         original derivation without a separator); with `asset`, bound to the asset address too",
+        vec![
+            ("one_strk_sepolia", commitment_input("1000000000000000000", 1, Domain::default(), None)),
+            ("min_amount_sepolia", commitment_input("1000000000000000", 2, Domain::default(), None)),
+            ("above_u128_sepolia", commitment_input("340282366920938463463374607431768211456", 3, Domain::default(), None)),
+            ("one_strk_mainnet", commitment_input("1000000000000000000", 1, mainnet(), None)),
+            ("one_strk_legacy", commitment_input("1000000000000000000", 1, Domain::legacy(), None)),
+            ("asset_strk", commitment_input("1000000000000000000", 1, Domain::default(), Some("STRK"))),
+            ("asset_eth", commitment_input("1000000000000000000", 1, Domain::default(), Some("ETH"))),
+        ],
+        |input| {
+            let commitment = match &input.asset {
+                Some(asset) => generate_asset_commitment_for_domain(
+                    &resolve_asset_id(asset).unwrap(), &input.amount_wei, &input.nonce, &input.domain,
+                ),
+                None => generate_pedersen_commitment_for_domain(&input.amount_wei, &input.nonce, &input.domain),
+            };
+            CommitmentExpected::from(commitment.unwrap())
+        },
+    );
+}
+
+#[test]
+fn unsuffixed_api_keeps_legacy_derivation() {
+    let nonce = hex32(1);
+    let mut hasher = Keccak256::new();
+    hasher.update(1_000_000_000_000_000_000u128.to_le_bytes());
+    hasher.update(hex::decode(&nonce[2..]).unwrap());
+    let commitment_hash = format!("0x{}", hex::encode(hasher.finalize()));
+    assert_eq!(generate_pedersen_commitment("1000000000000000000", &nonce).unwrap().commitment_hash, commitment_hash);
+
+    let mut hasher = Keccak256::new();
+    hasher.update(hex::decode(&commitment_hash[2..]).unwrap());
+    hasher.update(hex::decode(&hex32(0xabcdef)[2..]).unwrap());
+    hasher.update(b"nullifier");
+    let nullifier = format!("0x{}", hex::encode(hasher.finalize()));
+    assert_eq!(generate_nullifier(&commitment_hash, &hex32(0xabcdef)).unwrap(), nullifier);
+}
+
+#[derive(Serialize)]
+struct NullifierInput {
+    commitment_hash: String,
+    user_secret: String,
+    domain: Domain,
+}
+
+#[test]
+fn nullifier_vectors() {
+    let commitment_hash = generate_pedersen_commitment_for_domain("1000000000000000000", &hex32(1), &Domain::default())
+        .unwrap()
+        .commitment_hash;
+    let input = |secret: u64, domain: Domain| NullifierInput {
+        commitment_hash: commitment_hash.clone(),
+        user_secret: hex32(secret),
+        domain,
+    };
+
+    check_vectors(
+        "nullifiers.json",
+        "Nullifiers: Keccak(domain separator, spend key, \"nullifier\") with spend key s·K_d and \
+         s = Keccak(domain separator, commitment hash, user secret); protocol_version 0 is \
+         Keccak(commitment hash, user secret, \"nullifier\")",
+        vec![
+            ("sepolia", input(0xabcdef, Domain::default())),
+            ("other_secret", input(0xabcdf0, Domain::default())),
+            ("mainnet", input(0xabcdef, mainnet())),
+            ("legacy", input(0xabcdef, Domain::legacy())),
+        ],
+        |input| generate_nullifier_for_domain(&input.commitment_hash, &input.user_secret, &input.domain).unwrap(),
+    );
+}
+
+#[derive(Serialize)]
+struct MetadataInput {
+    receiver_address: String,
+    user_secret: String,
+}
+
+#[test]
+fn metadata_encryption_vectors() {
+    let input = |receiver: &str, secret: u64| MetadataInput {
+        receiver_address: receiver.to_string(),
+        user_secret: hex32(secret),
+    };
+
+    check_vectors(
+        "metadata_encryption.json",
+        "Receiver metadata: UTF-8 address XOR Keccak(secret || \"encryption_key\") repeated; XOR again to decrypt",
+        vec![
+            ("starknet_receiver", input(RECEIVER, 0xabcdef)),
+            ("short_receiver", input("0x1234", 0xabcdef)),
+            ("other_secret", input(RECEIVER, 0x123456)),
+        ],
+        |input| encrypt_receiver_metadata(&input.receiver_address, &input.user_secret).unwrap(),
+    );
+}
+
+#[derive(Serialize)]
+struct FeltExpected {
+    low: String,
+    high: String,
+}
+
+#[test]
+fn u256_felt_vectors() {
+    let values = [
+        ("zero", "0"),
+        ("one_strk", "1000000000000000000"),
+        ("u128_max", "340282366920938463463374607431768211455"),
+        ("two_pow_128", "340282366920938463463374607431768211456"),
+        ("u256_max", "115792089237316195423570985008687907853269984665640564039457584007913129639935"),
+    ];
+
+    check_vectors(
+        "u256_felts.json",
+        "u256 calldata: decimal value split into (low, high) 128-bit felts",
+        values.iter().map(|(name, value)| (*name, value.to_string())).collect(),
+        |value| {
+            let (low, high) = U256::parse(value).unwrap().to_felts();
+            assert_eq!(U256::from_felts(&low, &high).unwrap(), U256::parse(value).unwrap());
+            FeltExpected { low, high }
+        },
+    );
+}
+
+#[derive(Serialize)]
+struct DepositCalldataInput {
+    commitment_hash: String,
+    min_amount: String,
+    max_amount: String,
+    domain: Domain,
+}
+
+#[test]
+fn deposit_calldata_vectors() {
+    let commitment_hash = generate_pedersen_commitment_for_domain("1000000000000000000", &hex32(1), &Domain::default())
+        .unwrap()
+        .commitment_hash;
+    let input = |domain: Domain| DepositCalldataInput {
+        commitment_hash: commitment_hash.clone(),
+        min_amount: "1000000000000000".to_string(),
+        max_amount: "1000000000000000000000".to_string(),
+        domain,
+    };
+
+    check_vectors(
+        "deposit_calldata.json",
+        "deposit_private public inputs: commitment_hash, min_amount, max_amount, domain_separator",
+        vec![("sepolia", input(Domain::default())), ("mainnet", input(mainnet()))],
+        |input| {
+            deposit_public_inputs(
+                &input.commitment_hash,
+                U256::parse(&input.min_amount).unwrap(),
+                U256::parse(&input.max_amount).unwrap(),
+                &input.domain,
+            )
+        },
+    );
+}
+
+#[derive(Serialize, Deserialize)]
+struct DomainExpected {
+    separator: String,
+    normalized_contract: String,
+}
+
+#[test]
+fn domain_separator_vectors() {
+    check_vectors(
+        "domains.json",
+        "Domain separator (Keccak of chain id, normalized contract felt and protocol version)",
+        vec![
+            ("sepolia_default", Domain::default()),
+            ("mainnet", mainnet()),
+            ("mixed_case_padded_contract", Domain::new("sn_main", "0x027F726C6917C75DF9277FAB3C3267EC47A056065A9A0E5D59C316D51F20E28E", 1)),
+            ("protocol_v2", Domain::new(SN_MAIN, MAINNET_CONTRACT, 2)),
+        ],
+        |domain| DomainExpected {
+            separator: domain.separator_hex(),
+            normalized_contract: normalize_felt_hex(&domain.contract_address),
+        },
+    );
+}
+
+/// Caso `name` de `proofs/vectors/<file>`
+fn vector_case(file: &str, name: &str) -> serde_json::Value {
+    let vectors: serde_json::Value = read_json(&format!("vectors/{}", file));
+    vectors["cases"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|case| case["name"] == name)
+        .unwrap_or_else(|| panic!("missing case {} in {}", name, file))["expected"]
+        .clone()
+}
+
+fn keccak(parts: &[&[u8]]) -> String {
+    let mut hasher = Keccak256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    format!("0x{}", hex::encode(hasher.finalize()))
+}
+
+/// Valores esperados calculados a mano (o con Keccak directo sobre el formato
+/// documentado), no con el código que genera los ficheros
+#[test]
+fn generated_vectors_match_hand_derived_values() {
+    // u256 → (low, high): 2^128 = (0, 1), 1 STRK = 10^18 = 0xde0b6b3a7640000
+    let two_pow_128 = vector_case("u256_felts.json", "two_pow_128");
+    assert_eq!((two_pow_128["low"].as_str(), two_pow_128["high"].as_str()), (Some("0x0"), Some("0x1")));
+    let one_strk = vector_case("u256_felts.json", "one_strk");
+    assert_eq!((one_strk["low"].as_str(), one_strk["high"].as_str()), (Some("0xde0b6b3a7640000"), Some("0x0")));
+    let u256_max = vector_case("u256_felts.json", "u256_max");
+    assert_eq!(u256_max["low"], format!("0x{}", "f".repeat(32)));
+    assert_eq!(u256_max["high"], format!("0x{}", "f".repeat(32)));
+
+    // Límites del depósito en 32 dígitos hex: 10^15 = 0x38d7ea4c68000 y 10^21 = 0x3635c9adc5dea00000
+    let calldata = vector_case("deposit_calldata.json", "sepolia");
+    assert_eq!(calldata[1], "0x000000000000000000038d7ea4c68000");
+    assert_eq!(calldata[2], "0x000000000000003635c9adc5dea00000");
+
+    // Commitment v0: Keccak(10^18 como u128 LE ‖ nonce) y coordenadas Keccak(hash ‖ "x_coord"/"y_coord")
+    let nonce = hex::decode(&hex32(1)[2..]).unwrap();
+    let legacy_hash = keccak(&[&1_000_000_000_000_000_000u128.to_le_bytes(), &nonce]);
+    let legacy_bytes = hex::decode(&legacy_hash[2..]).unwrap();
+    let legacy = vector_case("commitments.json", "one_strk_legacy");
+    assert_eq!(legacy["commitment_hash"], legacy_hash);
+    assert_eq!(legacy["x"], keccak(&[&legacy_bytes, b"x_coord"]));
+    assert_eq!(legacy["y"], keccak(&[&legacy_bytes, b"y_coord"]));
+
+    // Metadata: "0x1234" XOR los 6 primeros bytes de Keccak(secreto ‖ "encryption_key")
+    let key = keccak(&[&hex::decode(&hex32(0xabcdef)[2..]).unwrap(), b"encryption_key"]);
+    let key = hex::decode(&key[2..]).unwrap();
+    let encrypted: Vec<u8> = b"0x1234".iter().zip(&key).map(|(byte, key)| byte ^ key).collect();
+    assert_eq!(vector_case("metadata_encryption.json", "short_receiver"), format!("0x{}", hex::encode(encrypted)));
+
+    // Merkle: raíz recalculada nivel a nivel con el hermano a la izquierda en índices impares
+    let merkle: serde_json::Value = read_json("vectors/merkle.json");
+    let case = &merkle["cases"][0];
+    let mut node = hex::decode(case["input"]["leaf_hash"].as_str().unwrap().trim_start_matches("0x")).unwrap();
+    let mut index = case["input"]["leaf_index"].as_u64().unwrap();
+    for sibling in case["input"]["proof_path"].as_array().unwrap() {
+        let sibling = hex::decode(sibling.as_str().unwrap().trim_start_matches("0x")).unwrap();
+        let parent = if index & 1 == 0 { keccak(&[&node, &sibling]) } else { keccak(&[&sibling, &node]) };
+        node = hex::decode(&parent[2..]).unwrap();
+        index /= 2;
+    }
+    assert_eq!(case["input"]["root"], format!("0x{}", hex::encode(node)));
+    assert_eq!(case["expected"]["valid"], true);
+}
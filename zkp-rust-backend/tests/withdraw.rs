@@ -0,0 +1,186 @@
+//! Retiro: una nota de la ventana del anonymous set se gasta revelando solo el
+//! monto y la clave de gasto. La hoja del depósito no aparece en la prueba, el
+//! monto queda ligado a la nota y un monto ilegible se rechaza con un informe.
+//! En un pool de denominaciones solo se retiran denominaciones con su ID.
+
+mod common;
+
+use common::{hex32, window_proof_at};
+use zkp_ceaser::value_commitment::{point_from_hex, point_to_hex};
+use zkp_ceaser::{
+    anonymity_window_root, build_withdraw_proof, deposit_note_commitment, note_leaf, verify_withdraw, AmountMode,
+    InMemoryNullifierSet, MerkleProof, NullifierSet, ProofRng, VerificationFailure, WithdrawProof, ZKProofConfig,
+};
+
+const AMOUNT_WEI: &str = "1000000000000000000";
+const RECIPIENT: &str = "0x2d4c0a53f31f0f359b5f439728a05273c23f0fa6fe2405a691dfd09fafafa49";
+const RELAYER: &str = "0x5793";
+const FEE_WEI: &str = "100000000000000";
+const SPENT_INDEX: u64 = 2;
+
+/// Ventana de 4 notas de 1 STRK con nonce i + 1 y secreto 0xc0ffee + i
+fn anonymity_set(config: &ZKProofConfig) -> Vec<String> {
+    (0..4)
+        .map(|i| {
+            let (note, _) = deposit_note_commitment(AMOUNT_WEI, &hex32(i + 1), &hex32(0xc0ffee + i), config).unwrap();
+            point_to_hex(&note)
+        })
+        .collect()
+}
+
+/// Ventana como subárbol 5 del nivel 2 (hojas 20..24) del árbol del pool
+fn window_proof(anonymity_set: &[String], config: &ZKProofConfig) -> MerkleProof {
+    let denomination_id = config.amount_mode.denomination_id(AMOUNT_WEI).unwrap();
+    let leaves: Vec<String> = anonymity_set
+        .iter()
+        .map(|note| note_leaf(&point_from_hex(note).unwrap(), denomination_id, &config.domain).unwrap().commitment_hash)
+        .collect();
+    let proof = window_proof_at(&leaves, 5);
+    let notes: Vec<_> = anonymity_set.iter().map(|note| point_from_hex(note).unwrap()).collect();
+    assert_eq!(proof.leaf_hash, anonymity_window_root(&notes, denomination_id, &config.domain).unwrap());
+    proof
+}
+
+fn withdraw_note(index: u64, config: &ZKProofConfig) -> WithdrawProof {
+    withdraw_note_with_rng(index, config, &mut ProofRng::from_entropy())
+}
+
+fn withdraw_note_with_rng(index: u64, config: &ZKProofConfig, rng: &mut ProofRng) -> WithdrawProof {
+    let anonymity_set = anonymity_set(config);
+    let window_proof = window_proof(&anonymity_set, config);
+    build_withdraw_proof(
+        AMOUNT_WEI,
+        &hex32(index + 1),
+        &hex32(0xc0ffee + index),
+        RECIPIENT,
+        RELAYER,
+        FEE_WEI,
+        &anonymity_set,
+        window_proof,
+        config,
+        rng,
+    )
+    .unwrap()
+}
+
+fn failure(proof: &WithdrawProof, config: &ZKProofConfig) -> Option<VerificationFailure> {
+    verify_withdraw(proof, config, None).unwrap().failure
+}
+
+#[test]
+fn valid_withdraw_verifies_once() {
+    let config = ZKProofConfig::default();
+    let proof = withdraw_note(SPENT_INDEX, &config);
+    assert_eq!(failure(&proof, &config), None);
+
+    let decoded: WithdrawProof = serde_json::from_str(&serde_json::to_string(&proof).unwrap()).unwrap();
+    assert_eq!(failure(&decoded, &config), None);
+
+    let mut nullifiers = InMemoryNullifierSet::new();
+    nullifiers.insert_if_absent(&proof.nullifier).unwrap();
+    let report = verify_withdraw(&proof, &config, Some(&nullifiers)).unwrap();
+    assert_eq!(report.failure, Some(VerificationFailure::AlreadySpent));
+}
+
+#[test]
+fn proof_does_not_reveal_the_deposit_leaf() {
+    let config = ZKProofConfig::default();
+    let proof = withdraw_note(SPENT_INDEX, &config);
+    let json = serde_json::to_string(&proof).unwrap();
+
+    let (note, _) = deposit_note_commitment(AMOUNT_WEI, &hex32(SPENT_INDEX + 1), &hex32(0xc0ffee + SPENT_INDEX), &config).unwrap();
+    let leaf = note_leaf(&note, None, &config.domain).unwrap();
+    assert!(!json.contains(&leaf.commitment_hash));
+    assert_ne!(proof.merkle_proof.leaf_hash, leaf.commitment_hash);
+
+    // Todas las notas de la ventana son candidatas por igual
+    assert_eq!(proof.anonymity_set, anonymity_set(&config));
+}
+
+#[test]
+fn withdraw_amount_is_bound_to_the_note() {
+    let config = ZKProofConfig::default();
+    let proof = withdraw_note(SPENT_INDEX, &config);
+    let mut higher = proof.clone();
+    higher.amount = "2000000000000000000".to_string();
+    assert_eq!(failure(&higher, &config), Some(VerificationFailure::InvalidPublicInputs));
+
+    // Prueba de pertenencia de otro statement: el reto ya no cuadra
+    let mut other = withdraw_note(SPENT_INDEX, &config);
+    other.membership_proof.challenges.reverse();
+    assert_eq!(failure(&other, &config), Some(VerificationFailure::InvalidCommitment));
+
+    // Misma nota en un pool de otro activo: v·A usa otro generador
+    let strk_pool = ZKProofConfig { asset: Some("STRK".to_string()), ..config.clone() };
+    assert_eq!(failure(&proof, &strk_pool), Some(VerificationFailure::InvalidCommitment));
+}
+
+#[test]
+fn seeded_withdraw_is_reproducible() {
+    let config = ZKProofConfig::default();
+    let seeded = |seed: u8| withdraw_note_with_rng(SPENT_INDEX, &config, &mut ProofRng::from_seed([seed; 32]));
+    let json = |proof: &WithdrawProof| serde_json::to_string(proof).unwrap();
+
+    assert_eq!(json(&seeded(1)), json(&seeded(1)));
+    let other_seed = seeded(2);
+    assert_ne!(other_seed.membership_proof, seeded(1).membership_proof);
+    assert_eq!(failure(&other_seed, &config), None);
+}
+
+#[test]
+fn unparsable_amount_is_reported() {
+    let config = ZKProofConfig::default();
+    let mut proof = withdraw_note(SPENT_INDEX, &config);
+    proof.amount = "one strk".to_string();
+    let report = verify_withdraw(&proof, &config, None).unwrap();
+    assert!(!report.valid);
+    assert_eq!(report.failure, Some(VerificationFailure::InvalidPublicInputs));
+}
+
+#[test]
+fn tampered_spend_key_or_nullifier_is_rejected() {
+    let config = ZKProofConfig::default();
+    let proof = withdraw_note(SPENT_INDEX, &config);
+    let other = withdraw_note(0, &config);
+
+    let mut spend_key = proof.clone();
+    spend_key.spend_key = other.spend_key.clone();
+    assert_ne!(failure(&spend_key, &config), None);
+
+    let mut nullifier = proof.clone();
+    nullifier.nullifier = other.nullifier.clone();
+    nullifier.public_inputs[1] = other.nullifier.clone();
+    assert_ne!(failure(&nullifier, &config), None);
+
+    let mut signature = proof;
+    signature.spend_signature = other.spend_signature;
+    assert_eq!(failure(&signature, &config), Some(VerificationFailure::InvalidSignature));
+}
+
+#[test]
+fn denomination_pool_withdrawals_declare_their_id() {
+    let config = ZKProofConfig { amount_mode: AmountMode::denominations_strk(), ..ZKProofConfig::default() };
+    let proof = withdraw_note(SPENT_INDEX, &config);
+    assert_eq!(proof.denomination_id, Some(0));
+    assert_eq!(failure(&proof, &config), None);
+
+    // Fuera del set de denominaciones o con otro ID declarado
+    let mut off_denomination = proof.clone();
+    off_denomination.amount = "24929000000000000000".to_string();
+    assert_eq!(failure(&off_denomination, &config), Some(VerificationFailure::InvalidDenomination));
+
+    let mut relabeled = proof.clone();
+    relabeled.denomination_id = Some(1);
+    assert_eq!(failure(&relabeled, &config), Some(VerificationFailure::InvalidDenomination));
+
+    // El mismo retiro no vale en un pool de rango, ni uno de rango en este pool
+    assert_eq!(failure(&proof, &ZKProofConfig::default()), Some(VerificationFailure::InvalidDenomination));
+    let range_proof = withdraw_note(SPENT_INDEX, &ZKProofConfig::default());
+    assert_eq!(failure(&range_proof, &config), Some(VerificationFailure::InvalidDenomination));
+
+    // Sin el ID en la declaración la ventana no es la del pool de denominaciones
+    let mut unbound = proof;
+    unbound.denomination_id = None;
+    let range_pool = ZKProofConfig::default();
+    assert_ne!(failure(&unbound, &range_pool), None);
+}